@@ -0,0 +1,219 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::domain::{Version, Versioned};
+use crate::aggregate::{AccommodationId, NumberOfSeats, ReservationId};
+
+/// guests sharing a room, children included
+pub const GUESTS_PER_ROOM: NumberOfSeats = 2;
+
+#[derive(Serialize, Deserialize, Error, Debug, PartialEq)]
+pub enum AvailabilityAccommodationError {
+    #[error("insufficient rooms")]
+    InsufficientRooms,
+}
+
+/// nights in an accommodation, from check-in until the morning of check-out
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Stay {
+    pub accommodation: AccommodationId,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+}
+
+impl Stay {
+    pub fn contains(&self, night: NaiveDate) -> bool {
+        self.check_in <= night && night < self.check_out
+    }
+
+    pub fn nights(&self) -> impl Iterator<Item=NaiveDate> + '_ {
+        self.check_in.iter_days()
+            .take_while(|night| night < &self.check_out)
+    }
+}
+
+pub fn rooms_for(guests: NumberOfSeats) -> u8 {
+    guests.div_ceil(GUESTS_PER_ROOM)
+}
+
+/// rooms of a stay of a reservation, reserved at a version of it
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RoomReservation {
+    pub reservation: ReservationId,
+    pub version: Version,
+    pub stay: Stay,
+    pub rooms: u8,
+}
+
+/// rooms allotted to us by a partner accommodation, per night
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AccommodationAvailability {
+    pub accommodation: AccommodationId,
+    pub rooms: u8,
+    pub room_reservations: Vec<RoomReservation>,
+}
+
+impl AccommodationAvailability {
+    pub fn from_allotment(accommodation: AccommodationId, rooms: u8) -> Self {
+        Self {
+            accommodation,
+            rooms,
+            room_reservations: Vec::new(),
+        }
+    }
+
+    /// lowering the allotment keeps the rooms already reserved
+    pub fn allot(self, rooms: u8) -> Self {
+        Self {
+            rooms,
+            ..self
+        }
+    }
+
+    pub fn rooms_reserved(&self, night: NaiveDate) -> u8 {
+        let rooms: u32 = self.room_reservations.iter()
+            .filter(|reservation| reservation.stay.contains(night))
+            .map(|reservation| reservation.rooms as u32)
+            .sum();
+
+        rooms.min(u8::MAX as u32) as u8
+    }
+
+    pub fn rooms_available(&self, night: NaiveDate) -> u8 {
+        self.rooms.saturating_sub(self.rooms_reserved(night))
+    }
+
+    /// replaces the rooms of the reservation's stay checking in on the same night, unless reserved by
+    /// a later version of it already; rooms of earlier versions are replaced altogether, as their stays
+    /// are annulled with the revision; reserving no rooms releases those of the stay only
+    pub fn reserve(self, reservation: &Versioned<ReservationId>, stay: &Stay, rooms: u8) -> Result<Self, AvailabilityAccommodationError> {
+        let id = *reservation.value_ref();
+
+        let superseded = self.room_reservations.iter()
+            .any(|existing| existing.reservation == id && existing.version > reservation.version());
+        if superseded {
+            return Ok(self);
+        }
+
+        let mut availability = Self {
+            room_reservations: self.room_reservations.into_iter()
+                .filter(|existing| {
+                    existing.reservation != id
+                        || (existing.version == reservation.version() && existing.stay.check_in != stay.check_in)
+                })
+                .collect(),
+            ..self
+        };
+
+        if rooms > 0 {
+            if stay.nights().any(|night| availability.rooms_available(night) < rooms) {
+                return Err(AvailabilityAccommodationError::InsufficientRooms);
+            }
+
+            availability.room_reservations.push(RoomReservation {
+                reservation: id,
+                version: reservation.version(),
+                stay: stay.clone(),
+                rooms,
+            });
+        }
+
+        Ok(availability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use prelude::domain::Versioned;
+    use crate::aggregate::{rooms_for, AccommodationAvailability, AccommodationId, AvailabilityAccommodationError, ReservationId, Stay};
+
+    #[test]
+    fn can_reserve_rooms() {
+        let availability = AccommodationAvailability::from_allotment(accommodation(), 3);
+
+        let availability = availability.reserve(&reservation(0), &stay("2024-05-08", "2024-05-14"), 2).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-07")), 3);
+        assert_eq!(availability.rooms_available(date("2024-05-08")), 1);
+        assert_eq!(availability.rooms_available(date("2024-05-13")), 1);
+        assert_eq!(availability.rooms_available(date("2024-05-14")), 3);
+    }
+
+    #[test]
+    fn errors_on_insufficient_rooms() {
+        let availability = AccommodationAvailability::from_allotment(accommodation(), 3)
+            .reserve(&reservation(0), &stay("2024-05-08", "2024-05-14"), 2).unwrap();
+
+        // a single night sold out fails the stay
+        let result = availability.clone().reserve(&reservation2(), &stay("2024-05-13", "2024-05-20"), 2);
+        assert_eq!(result, Err(AvailabilityAccommodationError::InsufficientRooms));
+
+        let result = availability.reserve(&reservation2(), &stay("2024-05-14", "2024-05-20"), 2);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn replaces_rooms_of_later_versions_only() {
+        let availability = AccommodationAvailability::from_allotment(accommodation(), 3)
+            .reserve(&reservation(0), &stay("2024-05-08", "2024-05-14"), 2).unwrap();
+
+        // revised stay
+        let availability = availability.reserve(&reservation(1), &stay("2024-05-10", "2024-05-16"), 3).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-08")), 3);
+        assert_eq!(availability.rooms_available(date("2024-05-10")), 0);
+
+        // annulment of the earlier version keeps the revised stay
+        let availability = availability.reserve(&reservation(0), &stay("2024-05-08", "2024-05-14"), 0).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-10")), 0);
+
+        let availability = availability.reserve(&reservation(1), &stay("2024-05-10", "2024-05-16"), 0).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-10")), 3);
+    }
+
+    #[test]
+    fn keeps_rooms_per_stay() {
+        let availability = AccommodationAvailability::from_allotment(accommodation(), 3)
+            .reserve(&reservation(0), &stay("2024-05-08", "2024-05-10"), 2).unwrap()
+            .reserve(&reservation(0), &stay("2024-05-14", "2024-05-16"), 1).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-08")), 1);
+        assert_eq!(availability.rooms_available(date("2024-05-14")), 2);
+
+        // releasing a stay keeps the other
+        let availability = availability.reserve(&reservation(0), &stay("2024-05-08", "2024-05-10"), 0).unwrap();
+        assert_eq!(availability.rooms_available(date("2024-05-08")), 3);
+        assert_eq!(availability.rooms_available(date("2024-05-14")), 2);
+    }
+
+    #[test]
+    fn guests_share_rooms() {
+        assert_eq!(rooms_for(0), 0);
+        assert_eq!(rooms_for(1), 1);
+        assert_eq!(rooms_for(2), 1);
+        assert_eq!(rooms_for(3), 2);
+    }
+
+    // test data
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn accommodation() -> AccommodationId {
+        "6EPFciXgSxB70tAE8iERl6".parse().unwrap()
+    }
+
+    fn stay(check_in: &str, check_out: &str) -> Stay {
+        Stay {
+            accommodation: accommodation(),
+            check_in: date(check_in),
+            check_out: date(check_out),
+        }
+    }
+
+    fn reservation(version: u32) -> Versioned<ReservationId> {
+        Versioned::from_version("7EPFciXgSxB70tAE8iERl6".parse().unwrap(), version)
+    }
+
+    fn reservation2() -> Versioned<ReservationId> {
+        Versioned::from_version("7EPFciXgSxB70tAE8iERl7".parse().unwrap(), 0)
+    }
+}
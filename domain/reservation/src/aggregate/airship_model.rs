@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::parse::{alphanumeric, capital, end, Parser, spaces, sym};
+
+/// as named by the scheduling context, the layout of the seats on board depends on it
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
+pub struct AirshipModel(String);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AirshipModelError {
+    #[error("malformed value")]
+    MalformedValue,
+}
+
+impl AirshipModel {
+    fn parser<'a>() -> Parser<'a, &'a str> {
+        (capital()
+            + (spaces().opt() + (sym('-') | alphanumeric())).repeat(0..99).collect()
+            + end::<char>()
+        ).collect()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for AirshipModel {
+    type Err = AirshipModelError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let value = Self::parser()
+            .parse_str(input)
+            .map_err(|_| AirshipModelError::MalformedValue)?
+            .to_owned();
+
+        Ok(Self(value))
+    }
+}
+
+impl Display for AirshipModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::airship_model::{AirshipModel, AirshipModelError};
+
+    #[test]
+    fn is_parsable() {
+        let result: Result<AirshipModel, AirshipModelError> = "H2 Clipper".parse();
+        assert!(result.is_ok());
+
+        let result: Result<AirshipModel, AirshipModelError> = "N/A".parse();
+        assert_eq!(result, Err(AirshipModelError::MalformedValue));
+    }
+}
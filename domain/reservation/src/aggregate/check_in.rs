@@ -0,0 +1,143 @@
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::data::Uid;
+use crate::aggregate::{equals_in_constant_time, FlightId, PersonFullName, SeatNumber};
+
+pub const CHECK_IN_OPENS_HOURS_BEFORE_DEPARTURE: i64 = 48;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CheckInError {
+    #[error("reservation is cancelled")]
+    ReservationIsCancelled,
+
+    #[error("reservation is held, confirm it first")]
+    ReservationIsHeld,
+
+    #[error("flight is not reserved")]
+    FlightNotReserved,
+
+    #[error("passengers are not listed by name")]
+    PassengersNotListed,
+
+    #[error("unknown passenger: {0}")]
+    UnknownPassenger(String),
+
+    #[error("check-in is not open yet")]
+    CheckInNotOpen,
+
+    #[error("check-in is closed")]
+    CheckInClosed,
+
+    #[error("boarding pass don't match")]
+    BoardingPassDontMatch,
+
+    #[error("passenger has boarded already")]
+    AlreadyBoarded,
+
+    #[error("passenger did not show up")]
+    NoShow,
+
+    #[error("flight has not departed yet")]
+    FlightNotDeparted,
+}
+
+/// check-in opens a number of hours before departure, and closes at departure
+pub fn check_in_window(departure: DateTime<FixedOffset>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let closes_at = departure.with_timezone(&Utc);
+    (closes_at - Duration::hours(CHECK_IN_OPENS_HOURS_BEFORE_DEPARTURE), closes_at)
+}
+
+/// issued at check-in; its random code is verified at the gate when boarding
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BoardingPass {
+    pub flight: FlightId,
+    pub passenger: PersonFullName,
+    pub seat: Option<SeatNumber>,
+    code: String,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl BoardingPass {
+    pub fn new_random(flight: FlightId, passenger: PersonFullName, seat: Option<SeatNumber>, now: DateTime<Utc>) -> Self {
+        Self {
+            flight,
+            passenger,
+            seat,
+            code: Uid::new_random().to_string(),
+            issued_at: now,
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn verify(&self, code: &str) -> Result<(), CheckInError> {
+        if equals_in_constant_time(&self.code, code) {
+            Ok(())
+        }
+        else {
+            Err(CheckInError::BoardingPassDontMatch)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum BoardingStatus {
+    CheckedIn(BoardingPass),
+    Boarded(BoardingPass, DateTime<Utc>),
+    /// not boarded at departure, checked in or not
+    NoShow,
+}
+
+/// status of a passenger on a flight, from check-in onwards
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct PassengerBoarding {
+    pub flight: FlightId,
+    pub passenger: PersonFullName,
+    pub status: BoardingStatus,
+}
+
+impl PassengerBoarding {
+    pub fn boarding_pass(&self) -> Option<&BoardingPass> {
+        match &self.status {
+            BoardingStatus::CheckedIn(pass) | BoardingStatus::Boarded(pass, _) => Some(pass),
+            BoardingStatus::NoShow => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, Utc};
+    use crate::aggregate::{check_in_window, BoardingPass, CheckInError};
+
+    #[test]
+    fn verifies_matching_code() {
+        let pass = boarding_pass();
+
+        assert_eq!(pass.verify(pass.code()), Ok(()));
+        assert_eq!(pass.verify("0000"), Err(CheckInError::BoardingPassDontMatch));
+        assert_ne!(pass.code(), boarding_pass().code());
+    }
+
+    #[test]
+    fn check_in_opens_two_days_before_departure() {
+        let departure: DateTime<FixedOffset> = "2024-05-08T09:00:00+05:00".parse().unwrap();
+        let (opens_at, closes_at) = check_in_window(departure);
+
+        assert_eq!(opens_at, "2024-05-06T04:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(closes_at, "2024-05-08T04:00:00Z".parse::<DateTime<Utc>>().unwrap());
+    }
+
+    // test data
+    fn boarding_pass() -> BoardingPass {
+        BoardingPass::new_random(
+            "9EPFciXgSxB70tAE8iERl6".to_owned(),
+            "Jane Doe".parse().unwrap(),
+            None,
+            "2024-05-07T09:00:00Z".parse().unwrap(),
+        )
+    }
+}
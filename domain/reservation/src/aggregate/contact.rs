@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::aggregate::{EmailAddress, EmailVerificationError, PersonFullName, PhoneNumber};
+use crate::aggregate::{EmailAddress, PersonFullName, PhoneNumber};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct Contact {
@@ -32,15 +32,11 @@ impl Contact {
         self.email.is_verified()
     }
 
-    pub fn email_verify_challenge(&self) -> Option<String> {
-        self.email.verify_challenge()
-    }
-
-    pub fn verify_email(self, challenge: String) -> Result<Contact, EmailVerificationError> {
-        Ok(Self {
-            email: self.email.verify(challenge)?,
+    pub fn verify_email(self) -> Contact {
+        Self {
+            email: self.email.verified(),
             ..self
-        })
+        }
     }
 }
 
@@ -82,8 +78,7 @@ mod tests {
         let before = Contact::new(name(), email(), None);
         assert!(!before.email_is_verified());
 
-        let challenge = before.email_verify_challenge().unwrap();
-        let after = before.verify_email(challenge).unwrap();
+        let after = before.verify_email();
         assert!(after.email_is_verified());
     }
 
@@ -1,6 +1,4 @@
-use std::collections::hash_map::DefaultHasher;
 use std::fmt::{Display, Formatter};
-use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -16,6 +14,18 @@ pub enum EmailParseError {
 pub enum EmailVerificationError {
     #[error("challenge don't match")]
     ChallengeDontMatch,
+
+    #[error("challenge is expired")]
+    ChallengeExpired,
+
+    #[error("too many failed attempts, a new challenge must be requested")]
+    TooManyFailedAttempts,
+
+    #[error("no challenge issued")]
+    NoChallengeIssued,
+
+    #[error("email address is already verified")]
+    AlreadyVerified,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -39,6 +49,13 @@ impl EmailAddress {
         (local_part - sym('@') + domain + end::<char>()).collect()
     }
 
+    pub fn address(&self) -> &str {
+        match self {
+            EmailAddress::Unverified(address) => address,
+            EmailAddress::Verified(address) => address,
+        }
+    }
+
     pub fn is_verified(&self) -> bool {
         match self {
             EmailAddress::Unverified(_) => false,
@@ -46,34 +63,18 @@ impl EmailAddress {
         }
     }
 
-    pub fn verify_challenge(&self) -> Option<String> {
-        if let EmailAddress::Unverified(address) = &self {
-            // simplistic implementation, easy guessable
-            let mut hasher = DefaultHasher::new();
-            address.hash(&mut hasher);
-
-            Some(hasher.finish().to_string())
-        }
-        else {
-            None
+    /// ownership of the address is proven elsewhere (e.g. by a challenge)
+    pub fn verified(self) -> Self {
+        match self {
+            Self::Unverified(address) => Self::Verified(address),
+            Self::Verified(_) => self,
         }
     }
 
-    pub fn verify(self, challenge: String) -> Result<Self, EmailVerificationError> {
-        let counter_challenge = self.verify_challenge().clone();
-
+    pub fn unverified(self) -> Self {
         match self {
-            Self::Unverified(address) => {
-                if counter_challenge == Some(challenge) {
-                    Ok(Self::Verified(address))
-                }
-                else {
-                    Err(EmailVerificationError::ChallengeDontMatch)
-                }
-            }
-            Self::Verified(_) => {
-                Ok(self)
-            }
+            Self::Verified(address) => Self::Unverified(address),
+            Self::Unverified(_) => self,
         }
     }
 }
@@ -103,7 +104,7 @@ impl Display for EmailAddress {
 
 #[cfg(test)]
 mod tests {
-    use crate::aggregate::{EmailAddress, EmailParseError, EmailVerificationError};
+    use crate::aggregate::{EmailAddress, EmailParseError};
 
     #[test]
     fn parsed_values_are_unverified() {
@@ -140,12 +141,11 @@ mod tests {
     }
 
     #[test]
-    fn verify_an_unverified_by_a_challenge() {
+    fn can_be_marked_verified() {
         let before: EmailAddress = "n.bergsma@internet.com".parse().unwrap();
         assert_eq!(before, EmailAddress::Unverified("n.bergsma@internet.com".to_owned()));
 
-        let challenge = before.verify_challenge().unwrap();
-        let after = before.verify(challenge).unwrap();
+        let after = before.verified();
         assert_eq!(after, EmailAddress::Verified("n.bergsma@internet.com".to_owned()));
     }
 
@@ -154,17 +154,7 @@ mod tests {
         let before: EmailAddress = "n.bergsma@internet.com".parse().unwrap();
         assert!(!before.is_verified());
 
-        let challenge = before.verify_challenge().unwrap();
-        let after = before.verify(challenge).unwrap();
+        let after = before.verified();
         assert!(after.is_verified());
     }
-
-    #[test]
-    fn errors_on_malformed_challenge() {
-        let before: EmailAddress = "n.bergsma@internet.com".parse().unwrap();
-        assert_eq!(before, EmailAddress::Unverified("n.bergsma@internet.com".to_owned()));
-
-        let result = before.verify("0000".to_owned());
-        assert_eq!(result, Err(EmailVerificationError::ChallengeDontMatch));
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::data::Uid;
+use crate::aggregate::{equals_in_constant_time, EmailVerificationError};
+
+pub const EMAIL_VERIFICATION_CHALLENGE_VALIDITY_HOURS: i64 = 24;
+pub const EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS: u8 = 5;
+
+/// random value sent to the email address, proves ownership when returned before it expires
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct EmailVerificationChallenge {
+    value: String,
+    expires_at: DateTime<Utc>,
+    /// the challenge is invalidated after too many, such that it can't be guessed
+    #[serde(default)]
+    failed_attempts: u8,
+}
+
+impl EmailVerificationChallenge {
+    pub fn new_random(now: DateTime<Utc>) -> Self {
+        Self {
+            value: Uid::new_random().to_string(),
+            expires_at: now + Duration::hours(EMAIL_VERIFICATION_CHALLENGE_VALIDITY_HOURS),
+            failed_attempts: 0,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    pub fn verify(&self, challenge: &str, now: DateTime<Utc>) -> Result<(), EmailVerificationError> {
+        if now >= self.expires_at {
+            return Err(EmailVerificationError::ChallengeExpired);
+        }
+
+        if self.failed_attempts >= EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS {
+            return Err(EmailVerificationError::TooManyFailedAttempts);
+        }
+
+        if equals_in_constant_time(&self.value, challenge) {
+            Ok(())
+        }
+        else {
+            Err(EmailVerificationError::ChallengeDontMatch)
+        }
+    }
+
+    pub fn fail(self) -> Self {
+        Self {
+            failed_attempts: self.failed_attempts.saturating_add(1),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use crate::aggregate::{EmailVerificationChallenge, EmailVerificationError, EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS};
+
+    #[test]
+    fn verifies_matching_challenge() {
+        let challenge = EmailVerificationChallenge::new_random(now());
+
+        let result = challenge.verify(challenge.value(), now());
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn challenges_are_random() {
+        let challenge1 = EmailVerificationChallenge::new_random(now());
+        let challenge2 = EmailVerificationChallenge::new_random(now());
+
+        assert_ne!(challenge1.value(), challenge2.value());
+    }
+
+    #[test]
+    fn errors_on_other_challenge() {
+        let challenge = EmailVerificationChallenge::new_random(now());
+
+        let result = challenge.verify("0000", now());
+        assert_eq!(result, Err(EmailVerificationError::ChallengeDontMatch));
+
+        let result = challenge.verify(&challenge.value()[1..], now());
+        assert_eq!(result, Err(EmailVerificationError::ChallengeDontMatch));
+    }
+
+    #[test]
+    fn errors_on_expired_challenge() {
+        let challenge = EmailVerificationChallenge::new_random(now());
+
+        let result = challenge.verify(challenge.value(), now() + Duration::hours(24));
+        assert_eq!(result, Err(EmailVerificationError::ChallengeExpired));
+    }
+
+    #[test]
+    fn errors_after_too_many_failed_attempts() {
+        let mut challenge = EmailVerificationChallenge::new_random(now());
+        for _ in 0..EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS {
+            assert_eq!(challenge.verify("0000", now()), Err(EmailVerificationError::ChallengeDontMatch));
+            challenge = challenge.fail();
+        }
+
+        let result = challenge.verify(challenge.value(), now());
+        assert_eq!(result, Err(EmailVerificationError::TooManyFailedAttempts));
+    }
+
+    // test data
+    fn now() -> DateTime<Utc> {
+        "2024-01-08T09:00:00Z".parse().unwrap()
+    }
+}
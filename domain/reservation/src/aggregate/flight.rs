@@ -2,7 +2,7 @@ use std::hash::{Hash, Hasher};
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use crate::aggregate::{FlightId, FlightRoute, NumberOfSeats};
+use crate::aggregate::{AirshipModel, FlightId, FlightRoute, NumberOfSeats};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum FlightError {
@@ -17,6 +17,9 @@ pub struct Flight {
     pub departure: DateTime<FixedOffset>,
     pub arrival: DateTime<FixedOffset>,
     pub seats: NumberOfSeats,
+    /// unknown for flights scheduled before it was carried
+    #[serde(default)]
+    pub model: Option<AirshipModel>,
 }
 
 impl Flight {
@@ -31,6 +34,21 @@ impl Flight {
             departure,
             arrival,
             seats,
+            model: None,
+        })
+    }
+
+    pub fn with_model(self, model: AirshipModel) -> Self {
+        Self {
+            model: Some(model),
+            ..self
+        }
+    }
+
+    pub fn reschedule(self, departure: DateTime<FixedOffset>, arrival: DateTime<FixedOffset>) -> Result<Self, FlightError> {
+        Ok(Self {
+            model: self.model,
+            ..Self::build(self.id, self.route, departure, arrival, self.seats)?
         })
     }
 }
@@ -64,6 +82,23 @@ mod tests {
         assert_eq!(flight, Err(FlightError::ArrivalBeforeDeparture));
     }
 
+    #[test]
+    fn can_be_rescheduled() {
+        let flight = Flight::build(id(), route_eham_enli(), departure(), arrival(), seats()).unwrap();
+
+        let result = flight.clone().reschedule(departure(), departure());
+        assert_eq!(result, Err(FlightError::ArrivalBeforeDeparture));
+
+        let departure2: DateTime<FixedOffset> = "2024-01-09T09:00:00+05:00".parse().unwrap();
+        let arrival2: DateTime<FixedOffset> = "2024-01-09T12:00:00+05:00".parse().unwrap();
+        let flight = flight.reschedule(departure2, arrival2).unwrap();
+        assert_eq!(flight.id, id());
+        assert_eq!(flight.route, route_eham_enli());
+        assert_eq!(flight.departure, departure2);
+        assert_eq!(flight.arrival, arrival2);
+        assert_eq!(flight.seats, seats());
+    }
+
     #[test]
     fn equals_by_id() {
         let flight1 = Flight::build(id(), route_eham_enli(), departure(), arrival(), seats()).unwrap();
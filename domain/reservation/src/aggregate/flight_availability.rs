@@ -1,19 +1,71 @@
 use std::iter;
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use crate::aggregate::{Flight, NumberOfSeats, ReservationId};
-use crate::event::FlightAvailabilityChangedV1;
+use prelude::domain::{Version, Versioned};
+use crate::aggregate::{AssignedSeat, Flight, FlightError, FlightRoute, NumberOfSeats, PersonFullName, ReservationId, SeatAssignmentError, SeatMap, SeatNumber, SeatRequest};
+use crate::event::{FlightAvailabilityChangedV1, FlightOversoldV1, WaitlistPromotedV1, WaitlistedV1};
+use crate::policy::NO_OVERBOOKING_PERCENTAGE;
 
 #[derive(Serialize, Deserialize, Error, Debug, PartialEq)]
 pub enum AvailabilityFlightError {
     #[error("insufficient seats")]
-    InsufficientSeats
+    InsufficientSeats,
+
+    #[error("flight is cancelled")]
+    FlightCancelled,
+
+    /// in the accommodation of the stage
+    #[error("insufficient rooms")]
+    InsufficientRooms,
+}
+
+/// change to a flight, which affects reservations holding seats on it
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum FlightDisruption {
+    Cancelled,
+    Rescheduled(Flight),
+}
+
+/// reservation (at version) waiting for seats to be freed
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WaitlistEntry {
+    pub reservation: ReservationId,
+    pub version: Version,
+    pub seats: NumberOfSeats,
+    pub time: DateTime<Utc>,
+}
+
+/// seat of a passenger, assigned at a version of the reservation
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct SeatAssignment {
+    pub seat: SeatNumber,
+    pub reservation: ReservationId,
+    pub version: Version,
+    pub passenger: PersonFullName,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct FlightAvailability {
     pub flight: Flight,
     pub seat_reservations: Vec<ReservationId>,
+    #[serde(default)]
+    pub cancelled: bool,
+    /// in order of request time
+    #[serde(default)]
+    pub waitlist: Vec<WaitlistEntry>,
+    /// seats sold relative to the seats on board, see OverbookingPolicy
+    #[serde(default = "no_overbooking")]
+    pub overbooking_percentage: u16,
+    /// whether the seats sold were reconciled with the seats on board, once check-in opened
+    #[serde(default)]
+    pub reconciled: bool,
+    #[serde(default)]
+    pub seat_assignments: Vec<SeatAssignment>,
+}
+
+fn no_overbooking() -> u16 {
+    NO_OVERBOOKING_PERCENTAGE
 }
 
 impl FlightAvailability {
@@ -21,45 +73,158 @@ impl FlightAvailability {
         let availability = FlightAvailability {
             flight: flight.clone(),
             seat_reservations: Vec::new(),
+            cancelled: false,
+            waitlist: Vec::new(),
+            overbooking_percentage: NO_OVERBOOKING_PERCENTAGE,
+            reconciled: false,
+            seat_assignments: Vec::new(),
         };
 
-        let event = FlightAvailabilityChangedV1 {
-            flight: flight.id,
-            route: flight.route,
-            departure: flight.departure,
-            arrival: flight.arrival,
-            seats_available: availability.seats_available(),
-        };
+        let event = availability.availability_changed();
 
         (availability, event)
     }
 
+    /// seats on board not yet reserved
     pub fn seats_available(&self) -> NumberOfSeats {
-        self.flight.seats - self.seat_reservations.len() as NumberOfSeats
+        if self.cancelled {
+            return 0;
+        }
+
+        self.flight.seats.saturating_sub(self.seats_reserved())
+    }
+
+    /// seats on board, plus overbooking
+    pub fn sellable_seats(&self) -> NumberOfSeats {
+        let seats = self.flight.seats as u32 * self.overbooking_percentage as u32 / 100;
+
+        seats.min(NumberOfSeats::MAX as u32) as NumberOfSeats
+    }
+
+    /// seats that can still be sold, including overbooking
+    pub fn sellable_seats_available(&self) -> NumberOfSeats {
+        if self.cancelled {
+            return 0;
+        }
+
+        self.sellable_seats().saturating_sub(self.seats_reserved())
+    }
+
+    pub fn seats_reserved(&self) -> NumberOfSeats {
+        self.seat_reservations.len() as NumberOfSeats
+    }
+
+    /// reserved seats beyond the seats on board
+    pub fn seats_oversold(&self) -> NumberOfSeats {
+        self.seats_reserved().saturating_sub(self.flight.seats)
+    }
+
+    /// lowering the percentage keeps the seats already sold, waitlisted reservations may be promoted when raised
+    pub fn overbook(self, percentage: u16) -> (Self, FlightAvailabilityChangedV1, Vec<WaitlistPromotedV1>) {
+        Self {
+            overbooking_percentage: percentage,
+            ..self
+        }.promote_waitlisted()
+    }
+
+    /// cancelled flights are left out
+    pub fn departs_on_route(&self, route: &FlightRoute, departing_after: DateTime<Utc>, departing_before: DateTime<Utc>) -> bool {
+        !self.cancelled && &self.flight.route == route && self.flight.departure > departing_after && self.flight.departure < departing_before
+    }
+
+    pub fn is_due_for_reconciliation(&self, departing_before: DateTime<Utc>) -> bool {
+        !self.cancelled && !self.reconciled && self.flight.departure < departing_before
+    }
+
+    /// once check-in opens, sold seats are compared against the seats on board;
+    /// an oversold flight names the most recent seat holders, which didn't fit
+    pub fn reconcile(self) -> (Self, Option<FlightOversoldV1>) {
+        let oversold = self.seats_oversold() as usize;
+
+        let event = if oversold > 0 {
+            let mut reservations: Vec<ReservationId> = Vec::new();
+            for id in self.seat_reservations.iter().rev().take(oversold) {
+                if !reservations.contains(id) {
+                    reservations.push(*id);
+                }
+            }
+
+            Some(FlightOversoldV1 {
+                flight: self.flight.id.clone(),
+                seats: self.flight.seats,
+                seats_reserved: self.seats_reserved(),
+                reservations,
+            })
+        }
+        else {
+            None
+        };
+
+        let availability = Self {
+            reconciled: true,
+            ..self
+        };
+
+        (availability, event)
+    }
+
+    /// returns the reservations holding seats on, or waiting for, the flight, these are disrupted by the cancellation
+    pub fn cancel(self) -> (Self, FlightAvailabilityChangedV1, Vec<ReservationId>) {
+        let disrupted_reservations = self.seat_holders_and_waitlisted();
+
+        let availability = Self {
+            cancelled: true,
+            waitlist: Vec::new(),
+            ..self
+        };
+        let event = availability.availability_changed();
+
+        (availability, event, disrupted_reservations)
+    }
+
+    /// returns the reservations holding seats on, or waiting for, the flight, these need to revalidate their itinerary
+    pub fn reschedule(self, departure: DateTime<FixedOffset>, arrival: DateTime<FixedOffset>) -> Result<(Self, FlightAvailabilityChangedV1, Vec<ReservationId>), FlightError> {
+        let disrupted_reservations = self.seat_holders_and_waitlisted();
+        let previous_departure = self.flight.departure;
+
+        let availability = Self {
+            flight: self.flight.reschedule(departure, arrival)?,
+            ..self
+        };
+        let event = FlightAvailabilityChangedV1 {
+            rescheduled_from: Some(previous_departure),
+            ..availability.availability_changed()
+        };
+
+        Ok((availability, event, disrupted_reservations))
     }
 
     pub fn reserve(self, id: &ReservationId, seats: NumberOfSeats) -> Result<(Self, FlightAvailabilityChangedV1), AvailabilityFlightError> {
+        // releasing seats on a cancelled flight is fine
+        if self.cancelled && seats > 0 {
+            return Err(AvailabilityFlightError::FlightCancelled);
+        }
+
         let seats_reserved: Vec<ReservationId> = self.seat_reservations.clone()
             .into_iter()
             .filter(|r| r != id)
             .collect();
 
-        let seats_available: NumberOfSeats = self.flight.seats - seats_reserved.len() as u8;
+        let seats_available: NumberOfSeats = self.sellable_seats().saturating_sub(seats_reserved.len() as NumberOfSeats);
         if seats_available >= seats {
-            let reservation_seats: Vec<ReservationId> = iter::repeat(*id).take(seats as usize).collect();
+            let reservation_seats: Vec<ReservationId> = iter::repeat_n(*id, seats as usize).collect();
 
-            let availability = Self {
+            let mut availability = Self {
                 seat_reservations: [seats_reserved, reservation_seats].concat(),
                 ..self.clone()
             };
 
-            let event = FlightAvailabilityChangedV1 {
-                flight: self.flight.id,
-                route: self.flight.route,
-                departure: self.flight.departure,
-                arrival: self.flight.arrival,
-                seats_available: availability.seats_available(),
-            };
+            // released seats are no longer assigned
+            if seats == 0 {
+                availability.seat_assignments.retain(|assignment| assignment.reservation != *id);
+            }
+
+            let event = availability.availability_changed();
 
             Ok((availability, event))
         }
@@ -67,11 +232,231 @@ impl FlightAvailability {
             Err(AvailabilityFlightError::InsufficientSeats)
         }
     }
+
+    /// a reservation waits in line once, but keeps its place when requesting again (e.g. when revised)
+    pub fn join_waitlist(mut self, reservation: &Versioned<ReservationId>, seats: NumberOfSeats, time: DateTime<Utc>) -> (Self, WaitlistedV1) {
+        let id = *reservation.value_ref();
+        let time = self.waitlist.iter()
+            .find(|entry| entry.reservation == id)
+            .map(|entry| entry.time)
+            .unwrap_or(time);
+
+        self.waitlist.retain(|entry| entry.reservation != id);
+        self.waitlist.push(WaitlistEntry {
+            reservation: id,
+            version: reservation.version(),
+            seats,
+            time,
+        });
+        self.waitlist.sort_by(|a, b| a.time.cmp(&b.time)
+            .then_with(|| a.reservation.to_string().cmp(&b.reservation.to_string())));
+
+        let position = self.waitlist.iter()
+            .position(|entry| entry.reservation == id)
+            .expect("entry was just added") + 1;
+
+        let event = WaitlistedV1 {
+            reservation: Versioned::from_version(id, reservation.version()),
+            flight: self.flight.id.clone(),
+            seats,
+            position,
+        };
+
+        (self, event)
+    }
+
+    /// a request (or annulment) supersedes waiting with the same, or an earlier, version of the reservation
+    pub fn leave_waitlist(mut self, reservation: &Versioned<ReservationId>) -> Self {
+        self.waitlist.retain(|entry| {
+            entry.reservation != *reservation.value_ref() || entry.version > reservation.version()
+        });
+
+        self
+    }
+
+    /// offers available seats to the waitlist in order, skipping entries needing more seats than available
+    pub fn promote_waitlisted(mut self) -> (Self, FlightAvailabilityChangedV1, Vec<WaitlistPromotedV1>) {
+        let mut promoted = Vec::new();
+
+        if !self.cancelled {
+            let mut waiting = Vec::new();
+            for entry in std::mem::take(&mut self.waitlist) {
+                if entry.seats <= self.sellable_seats_available() {
+                    self.seat_reservations.retain(|id| *id != entry.reservation);
+                    self.seat_reservations.extend(iter::repeat_n(entry.reservation, entry.seats as usize));
+
+                    promoted.push(WaitlistPromotedV1 {
+                        reservation: Versioned::from_version(entry.reservation, entry.version),
+                        flight: self.flight.id.clone(),
+                        seats: entry.seats,
+                    });
+                }
+                else {
+                    waiting.push(entry);
+                }
+            }
+            self.waitlist = waiting;
+        }
+
+        let event = self.availability_changed();
+
+        (self, event, promoted)
+    }
+
+    pub fn seat_map(&self) -> SeatMap {
+        SeatMap::from_flight(&self.flight)
+    }
+
+    pub fn seat_taken(&self, seat: &SeatNumber) -> bool {
+        self.seat_assignments.iter().any(|assignment| &assignment.seat == seat)
+    }
+
+    /// replaces the seats assigned to the reservation; chosen seats are assigned first,
+    /// other passengers are assigned the first free seat, saving window seats for last
+    pub fn assign_seats(self, reservation: &Versioned<ReservationId>, requests: Vec<SeatRequest>) -> Result<(Self, Vec<AssignedSeat>), SeatAssignmentError> {
+        if self.cancelled {
+            return Err(SeatAssignmentError::FlightCancelled);
+        }
+
+        let id = *reservation.value_ref();
+        let seats_reserved = self.seat_reservations.iter()
+            .filter(|holder| **holder == id)
+            .count();
+        if requests.len() > seats_reserved {
+            return Err(SeatAssignmentError::InsufficientSeatsReserved);
+        }
+
+        let seat_map = self.seat_map();
+        let mut assignments: Vec<SeatAssignment> = self.seat_assignments.iter()
+            .filter(|assignment| assignment.reservation != id)
+            .cloned()
+            .collect();
+        let mut assigned = Vec::new();
+
+        let (chosen, automatic): (Vec<SeatRequest>, Vec<SeatRequest>) = requests.into_iter()
+            .partition(|request| request.seat.is_some());
+
+        for request in chosen {
+            let number = request.seat.expect("partitioned by chosen seat");
+            let seat = seat_map.find(&number)
+                .ok_or(SeatAssignmentError::UnknownSeat(number.clone()))?;
+
+            if assignments.iter().any(|assignment| assignment.seat == number) {
+                return Err(SeatAssignmentError::SeatTaken(number));
+            }
+
+            assignments.push(SeatAssignment {
+                seat: number.clone(),
+                reservation: id,
+                version: reservation.version(),
+                passenger: request.passenger.clone(),
+            });
+            assigned.push(AssignedSeat {
+                flight: self.flight.id.clone(),
+                passenger: request.passenger,
+                seat: number,
+                window: seat.window,
+                chosen: true,
+            });
+        }
+
+        for request in automatic {
+            let seat = seat_map.seats().iter()
+                .filter(|seat| !seat.window)
+                .chain(seat_map.seats().iter().filter(|seat| seat.window))
+                .find(|seat| !assignments.iter().any(|assignment| assignment.seat == seat.number))
+                .ok_or(SeatAssignmentError::NoSeatAvailable)?;
+
+            assignments.push(SeatAssignment {
+                seat: seat.number.clone(),
+                reservation: id,
+                version: reservation.version(),
+                passenger: request.passenger.clone(),
+            });
+            assigned.push(AssignedSeat {
+                flight: self.flight.id.clone(),
+                passenger: request.passenger,
+                seat: seat.number.clone(),
+                window: seat.window,
+                chosen: false,
+            });
+        }
+
+        let availability = Self {
+            seat_assignments: assignments,
+            ..self
+        };
+
+        Ok((availability, assigned))
+    }
+
+    /// puts back the seats of the reservation as assigned before, e.g. when the reservation failed to record
+    /// a new assignment; seats taken by others meanwhile stay theirs
+    pub fn restore_seats(mut self, reservation: &ReservationId, assignments: Vec<SeatAssignment>) -> Self {
+        self.seat_assignments.retain(|assignment| assignment.reservation != *reservation);
+
+        for assignment in assignments {
+            if !self.seat_taken(&assignment.seat) {
+                self.seat_assignments.push(assignment);
+            }
+        }
+
+        self
+    }
+
+    /// seats assigned to an earlier version of the reservation no longer apply, e.g. after revising its passengers
+    pub fn unassign_seats(mut self, reservation: &Versioned<ReservationId>) -> Self {
+        self.seat_assignments.retain(|assignment| {
+            assignment.reservation != *reservation.value_ref() || assignment.version >= reservation.version()
+        });
+
+        self
+    }
+
+    /// reservations holding at least one seat, in order of reservation
+    pub fn seat_holders(&self) -> Vec<ReservationId> {
+        let mut reservations: Vec<ReservationId> = Vec::new();
+        for id in self.seat_reservations.iter() {
+            if !reservations.contains(id) {
+                reservations.push(*id);
+            }
+        }
+
+        reservations
+    }
+
+    fn seat_holders_and_waitlisted(&self) -> Vec<ReservationId> {
+        let mut reservations = self.seat_holders();
+        for entry in self.waitlist.iter() {
+            if !reservations.contains(&entry.reservation) {
+                reservations.push(entry.reservation);
+            }
+        }
+
+        reservations
+    }
+
+    fn availability_changed(&self) -> FlightAvailabilityChangedV1 {
+        FlightAvailabilityChangedV1 {
+            flight: self.flight.id.clone(),
+            route: self.flight.route.clone(),
+            departure: self.flight.departure,
+            arrival: self.flight.arrival,
+            seats_available: self.seats_available(),
+            seats: self.flight.seats,
+            sellable_seats: self.sellable_seats(),
+            sellable_seats_available: self.sellable_seats_available(),
+            cancelled: self.cancelled,
+            rescheduled_from: None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::aggregate::{FlightAvailability, Flight, FlightRoute, ReservationId, AvailabilityFlightError};
+    use chrono::{DateTime, Utc};
+    use prelude::domain::Versioned;
+    use crate::aggregate::{FlightAvailability, Flight, FlightError, FlightRoute, ReservationId, AvailabilityFlightError, SeatAssignmentError, SeatRequest};
 
     #[test]
     fn equality_by_flight() {
@@ -138,6 +523,259 @@ mod tests {
         assert_eq!(availability.seats_available(), 10u8);
     }
 
+    #[test]
+    fn cancel_releases_no_seats_but_disrupts_reservations() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 2).unwrap();
+        let (availability, _) = availability.reserve(&reservation2(), 1).unwrap();
+
+        let (availability, event, disrupted) = availability.cancel();
+        assert_eq!(availability.seats_available(), 0);
+        assert_eq!(availability.seat_reservations.len(), 3);
+        assert!(event.cancelled);
+        assert_eq!(event.seats_available, 0);
+        assert_eq!(disrupted, vec![reservation(), reservation2()]);
+    }
+
+    #[test]
+    fn errors_on_reserving_seats_on_cancelled_flight() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 2).unwrap();
+        let (availability, _, _) = availability.cancel();
+
+        let result = availability.clone().reserve(&reservation2(), 1);
+        assert_eq!(result, Err(AvailabilityFlightError::FlightCancelled));
+
+        // can still be annulled
+        let (availability, _) = availability.reserve(&reservation(), 0).unwrap();
+        assert!(availability.seat_reservations.is_empty());
+    }
+
+    #[test]
+    fn reschedule_keeps_seats_and_disrupts_reservations() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 2).unwrap();
+        let (availability, _) = availability.reserve(&reservation2(), 1).unwrap();
+
+        let departure = "2024-02-01T09:00:00+05:00".parse().unwrap();
+        let arrival = "2024-02-01T12:00:00+05:00".parse().unwrap();
+        let (availability, event, disrupted) = availability.reschedule(departure, arrival).unwrap();
+        assert_eq!(availability.flight.departure, departure);
+        assert_eq!(availability.flight.arrival, arrival);
+        assert_eq!(availability.seats_available(), 7);
+        assert_eq!(event.departure, departure);
+        assert_eq!(event.rescheduled_from, Some(flight().departure));
+        assert_eq!(disrupted, vec![reservation(), reservation2()]);
+
+        // errors on malformed times
+        let result = availability.reschedule(arrival, departure);
+        assert_eq!(result, Err(FlightError::ArrivalBeforeDeparture));
+    }
+
+    #[test]
+    fn reschedule_disrupts_waitlisted_reservations() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 10).unwrap();
+        let (availability, _) = availability.join_waitlist(&Versioned::from_version(reservation2(), 1), 1, time(1));
+
+        let departure = "2024-02-01T09:00:00+05:00".parse().unwrap();
+        let arrival = "2024-02-01T12:00:00+05:00".parse().unwrap();
+        let (availability, _, disrupted) = availability.reschedule(departure, arrival).unwrap();
+        assert_eq!(availability.waitlist.len(), 1);
+        assert_eq!(disrupted, vec![reservation(), reservation2()]);
+    }
+
+    #[test]
+    fn waitlist_is_ordered_by_request_time() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 10).unwrap();
+
+        let (availability, event) = availability.join_waitlist(&Versioned::from_version(reservation2(), 1), 2, time(2));
+        assert_eq!(event.position, 1);
+
+        let (availability, event) = availability.join_waitlist(&Versioned::from_version(reservation3(), 1), 1, time(1));
+        assert_eq!(event.position, 1);
+
+        // requesting again keeps the place in line
+        let (availability, event) = availability.join_waitlist(&Versioned::from_version(reservation2(), 2), 3, time(3));
+        assert_eq!(event.position, 2);
+        assert_eq!(availability.waitlist.len(), 2);
+        assert_eq!(availability.waitlist[1].version, 2);
+        assert_eq!(availability.waitlist[1].seats, 3);
+    }
+
+    #[test]
+    fn annulment_promotes_waitlisted_reservations() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 8).unwrap();
+        let (availability, _) = availability.join_waitlist(&Versioned::from_version(reservation2(), 1), 4, time(1));
+        let (availability, _) = availability.join_waitlist(&Versioned::from_version(reservation3(), 1), 1, time(2));
+
+        // first in line does not fit yet, second does
+        let (availability, event, promoted) = availability.promote_waitlisted();
+        assert_eq!(event.seats_available, 1);
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(*promoted[0].reservation.value_ref(), reservation3());
+        assert_eq!(availability.waitlist.len(), 1);
+
+        let (availability, _) = availability.reserve(&reservation(), 0).unwrap();
+        let (availability, event, promoted) = availability.promote_waitlisted();
+        assert_eq!(event.seats_available, 5);
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(*promoted[0].reservation.value_ref(), reservation2());
+        assert_eq!(promoted[0].seats, 4);
+        assert!(availability.waitlist.is_empty());
+    }
+
+    #[test]
+    fn annulment_leaves_waitlist() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 10).unwrap();
+        let (availability, _) = availability.join_waitlist(&Versioned::from_version(reservation2(), 2), 1, time(1));
+
+        // an older version does not supersede the waiting request
+        let availability = availability.leave_waitlist(&Versioned::from_version(reservation2(), 1));
+        assert_eq!(availability.waitlist.len(), 1);
+
+        let availability = availability.leave_waitlist(&Versioned::from_version(reservation2(), 2));
+        assert!(availability.waitlist.is_empty());
+    }
+
+    #[test]
+    fn cancel_disrupts_waitlisted_reservations() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 10).unwrap();
+        let (availability, _) = availability.join_waitlist(&Versioned::from_version(reservation2(), 1), 1, time(1));
+
+        let (availability, _, disrupted) = availability.cancel();
+        assert!(availability.waitlist.is_empty());
+        assert_eq!(disrupted, vec![reservation(), reservation2()]);
+
+        let (_, _, promoted) = availability.reserve(&reservation(), 0).unwrap().0.promote_waitlisted();
+        assert!(promoted.is_empty());
+    }
+
+    #[test]
+    fn overbooking_sells_beyond_the_seats_on_board() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, event, _) = availability.overbook(120);
+        assert_eq!(event.seats, 10);
+        assert_eq!(event.sellable_seats, 12);
+        assert_eq!(event.sellable_seats_available, 12);
+
+        let (availability, _) = availability.reserve(&reservation(), 11).unwrap();
+        assert_eq!(availability.seats_available(), 0);
+        assert_eq!(availability.sellable_seats_available(), 1);
+        assert_eq!(availability.seats_oversold(), 1);
+
+        let result = availability.clone().reserve(&reservation2(), 2);
+        assert_eq!(result, Err(AvailabilityFlightError::InsufficientSeats));
+
+        // lowering keeps the seats sold
+        let (availability, event, _) = availability.overbook(100);
+        assert_eq!(event.sellable_seats_available, 0);
+        assert_eq!(availability.seats_reserved(), 11);
+    }
+
+    #[test]
+    fn reconciliation_names_most_recent_seat_holders_of_oversold_flight() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _, _) = availability.overbook(150);
+        let (availability, _) = availability.reserve(&reservation(), 9).unwrap();
+        let (availability, _) = availability.reserve(&reservation2(), 3).unwrap();
+
+        // check-in opened a day before departure
+        let departing_before: DateTime<Utc> = "2024-01-09T00:00:00Z".parse().unwrap();
+        assert!(availability.is_due_for_reconciliation(departing_before));
+
+        let (availability, event) = availability.reconcile();
+        let event = event.unwrap();
+        assert_eq!(event.seats_reserved, 12);
+        assert_eq!(event.reservations, vec![reservation2()]);
+        assert!(!availability.is_due_for_reconciliation(departing_before));
+
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (_, event) = availability.reconcile();
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn chosen_seats_are_assigned_before_automatic_ones() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 2).unwrap();
+        let (availability, _) = availability.reserve(&reservation2(), 1).unwrap();
+
+        let (availability, assigned) = availability.assign_seats(&Versioned::from_version(reservation(), 1), vec![
+            request("John Doe", None),
+            request("Jane Doe", Some("1B")),
+        ]).unwrap();
+        let seats: Vec<_> = assigned.iter()
+            .map(|seat| (seat.passenger.to_string(), seat.seat.to_string(), seat.chosen))
+            .collect();
+        assert_eq!(seats, vec![
+            ("Jane Doe".to_owned(), "1B".to_owned(), true),
+            ("John Doe".to_owned(), "1C".to_owned(), false),
+        ]);
+        assert!(availability.seat_taken(&"1B".parse().unwrap()));
+
+        let result = availability.clone().assign_seats(&Versioned::from_version(reservation2(), 1), vec![request("Joe Bloggs", Some("1B"))]);
+        assert_eq!(result.err(), Some(SeatAssignmentError::SeatTaken("1B".parse().unwrap())));
+
+        let result = availability.clone().assign_seats(&Versioned::from_version(reservation2(), 1), vec![request("Joe Bloggs", Some("9A"))]);
+        assert_eq!(result.err(), Some(SeatAssignmentError::UnknownSeat("9A".parse().unwrap())));
+
+        let result = availability.clone().assign_seats(&Versioned::from_version(reservation2(), 1), vec![request("Joe Bloggs", None), request("Jill Bloggs", None)]);
+        assert_eq!(result.err(), Some(SeatAssignmentError::InsufficientSeatsReserved));
+
+        // choosing anew frees the seats assigned before
+        let (availability, _) = availability.assign_seats(&Versioned::from_version(reservation(), 1), vec![
+            request("John Doe", Some("2A")),
+            request("Jane Doe", Some("2B")),
+        ]).unwrap();
+        assert!(!availability.seat_taken(&"1B".parse().unwrap()));
+    }
+
+    #[test]
+    fn automatic_seats_save_window_seats_for_last() {
+        let (availability, _) = FlightAvailability::from_flight(wide_flight());
+        let (availability, _) = availability.reserve(&reservation(), 3).unwrap();
+
+        let (_, assigned) = availability.assign_seats(&Versioned::from_version(reservation(), 1), vec![
+            request("John Doe", None),
+            request("Jane Doe", None),
+            request("Jim Doe", None),
+        ]).unwrap();
+        let seats: Vec<_> = assigned.iter()
+            .map(|seat| (seat.seat.to_string(), seat.window))
+            .collect();
+        assert_eq!(seats, vec![
+            ("1B".to_owned(), false),
+            ("1C".to_owned(), false),
+            ("2B".to_owned(), false),
+        ]);
+    }
+
+    #[test]
+    fn revised_reservations_lose_their_seats() {
+        let (availability, _) = FlightAvailability::from_flight(flight());
+        let (availability, _) = availability.reserve(&reservation(), 1).unwrap();
+        let (availability, _) = availability.assign_seats(&Versioned::from_version(reservation(), 1), vec![request("Jane Doe", Some("3A"))]).unwrap();
+
+        let availability = availability.unassign_seats(&Versioned::from_version(reservation(), 1));
+        assert!(availability.seat_taken(&"3A".parse().unwrap()));
+
+        let availability = availability.unassign_seats(&Versioned::from_version(reservation(), 2));
+        assert!(!availability.seat_taken(&"3A".parse().unwrap()));
+
+        let (availability, _) = availability.assign_seats(&Versioned::from_version(reservation(), 2), vec![request("Jane Doe", Some("3A"))]).unwrap();
+        let (availability, _) = availability.reserve(&reservation(), 0).unwrap();
+        assert!(!availability.seat_taken(&"3A".parse().unwrap()));
+
+        let (availability, _, _) = availability.cancel();
+        let result = availability.assign_seats(&Versioned::from_version(reservation(), 2), vec![]);
+        assert_eq!(result.err(), Some(SeatAssignmentError::FlightCancelled));
+    }
+
     fn flight() -> Flight {
         let id = "5EPFciXgSxB70tAE8iERl6".to_owned();
         let route = FlightRoute::build("EHAM".parse().unwrap(), "ENLI".parse().unwrap()).unwrap();
@@ -158,7 +796,36 @@ mod tests {
         Flight::build(id, route, departure, arrival, seats).unwrap()
     }
 
+    fn wide_flight() -> Flight {
+        let id = "5EPFciXgSxB70tAE8iERl8".to_owned();
+        let route = FlightRoute::build("EHAM".parse().unwrap(), "ENLI".parse().unwrap()).unwrap();
+        let departure = "2024-03-08T09:00:00+05:00".parse().unwrap();
+        let arrival = "2024-03-08T11:00:00+05:00".parse().unwrap();
+        let seats = 20u8;
+
+        Flight::build(id, route, departure, arrival, seats).unwrap()
+    }
+
     fn reservation() -> ReservationId {
         "6APFciXgSxB70tAE8iERl1".parse().unwrap()
     }
+
+    fn reservation2() -> ReservationId {
+        "6APFciXgSxB70tAE8iERl2".parse().unwrap()
+    }
+
+    fn reservation3() -> ReservationId {
+        "6APFciXgSxB70tAE8iERl3".parse().unwrap()
+    }
+
+    fn request(passenger: &str, seat: Option<&str>) -> SeatRequest {
+        SeatRequest {
+            passenger: passenger.parse().unwrap(),
+            seat: seat.map(|seat| seat.parse().unwrap()),
+        }
+    }
+
+    fn time(minute: u32) -> DateTime<Utc> {
+        format!("2024-01-01T09:{:02}:00Z", minute).parse().unwrap()
+    }
 }
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::collections::LinkedList;
 use chrono::{Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use crate::aggregate::{Accommodation, AccommodationId, Flight, FlightId};
+use crate::aggregate::{Accommodation, AccommodationId, Flight, FlightId, Stay};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct Itinerary(LinkedList<ItineraryStage>);
@@ -21,7 +21,7 @@ impl Itinerary {
         }
     }
 
-    pub fn stages(&self) -> Iter<ItineraryStage> {
+    pub fn stages(&self) -> Iter<'_, ItineraryStage> {
         self.0.iter()
     }
 
@@ -53,7 +53,8 @@ impl Itinerary {
             ItineraryStage::Planned(flight, _)
             | ItineraryStage::Reserved(flight, _)
             | ItineraryStage::ReservedFailed(flight, _, _)
-            | ItineraryStage::Annulled(flight, _) => flight.departure.date_naive()
+            | ItineraryStage::Annulled(flight, _)
+            | ItineraryStage::Disrupted(flight, _, _) => flight.departure.date_naive()
         }
     }
 
@@ -62,19 +63,42 @@ impl Itinerary {
             ItineraryStage::Planned(flight, _)
             | ItineraryStage::Reserved(flight, _)
             | ItineraryStage::ReservedFailed(flight, _, _)
-            | ItineraryStage::Annulled(flight, _) => flight.departure,
+            | ItineraryStage::Annulled(flight, _)
+            | ItineraryStage::Disrupted(flight, _, _) => flight.departure,
         };
 
         let arrival = match self.last_stage() {
             ItineraryStage::Planned(flight, _)
             | ItineraryStage::Reserved(flight, _)
             | ItineraryStage::ReservedFailed(flight, _, _)
-            | ItineraryStage::Annulled(flight, _) => flight.arrival,
+            | ItineraryStage::Annulled(flight, _)
+            | ItineraryStage::Disrupted(flight, _, _) => flight.arrival,
         };
 
         arrival - departure
     }
 
+    /// per stage, the nights in its accommodation until the next flight departs
+    pub fn stays(&self) -> Vec<Option<Stay>> {
+        let stages: Vec<(Flight, Option<Accommodation>)> = self.stages()
+            .map(|stage| stage.clone().flight_and_accommodation())
+            .collect();
+
+        stages.iter()
+            .enumerate()
+            .map(|(i, (flight, accommodation))| {
+                let (next_flight, _) = stages.get(i + 1)?;
+
+                Some(Stay {
+                    accommodation: accommodation.as_ref()?.id,
+                    check_in: flight.arrival.date_naive(),
+                    check_out: next_flight.departure.date_naive(),
+                })
+            })
+            .map(|stay| stay.filter(|stay| stay.check_in < stay.check_out))
+            .collect()
+    }
+
     pub fn mark_flight_as_reserved(self, flight: &FlightId) -> Self {
         self.mark_flight(flight, |stage| stage.mark_flight_as_reserved())
     }
@@ -87,6 +111,15 @@ impl Itinerary {
         self.mark_flight(flight, |stage| stage.mark_flight_as_annulled())
     }
 
+    pub fn mark_flight_as_disrupted(self, flight: &FlightId, reason: &ItineraryStageDisruption) -> Self {
+        self.mark_flight(flight, |stage| stage.mark_flight_as_disrupted(reason))
+    }
+
+    /// replaces the copy of a (rescheduled) flight, leaving the stage state as is
+    pub fn reschedule_flight(self, flight: &Flight) -> Self {
+        self.mark_flight(&flight.id, |stage| stage.with_flight(flight.clone()))
+    }
+
     fn mark_flight<F>(self, flight: &FlightId, mark: F) -> Self
         where F: Fn(ItineraryStage) -> ItineraryStage {
 
@@ -110,6 +143,7 @@ pub enum ItineraryStage {
     Reserved(Flight, Option<Accommodation>),
     ReservedFailed(Flight, Option<Accommodation>, ItineraryStageError),
     Annulled(Flight, Option<Accommodation>),
+    Disrupted(Flight, Option<Accommodation>, ItineraryStageDisruption),
 }
 
 impl ItineraryStage {
@@ -119,6 +153,7 @@ impl ItineraryStage {
             ItineraryStage::Reserved(flight, _) => flight.id.clone(),
             ItineraryStage::ReservedFailed(flight, _, _) => flight.id.clone(),
             ItineraryStage::Annulled(flight, _) => flight.id.clone(),
+            ItineraryStage::Disrupted(flight, _, _) => flight.id.clone(),
         }
     }
 
@@ -127,7 +162,8 @@ impl ItineraryStage {
             ItineraryStage::Planned(flight, accommodation)
             | ItineraryStage::Reserved(flight, accommodation)
             | ItineraryStage::ReservedFailed(flight, accommodation, _)
-            | ItineraryStage::Annulled(flight, accommodation) => {
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => {
                 ItineraryStage::Planned(flight, accommodation)
             }
         }
@@ -138,7 +174,8 @@ impl ItineraryStage {
             ItineraryStage::Planned(flight, accommodation)
             | ItineraryStage::Reserved(flight, accommodation)
             | ItineraryStage::ReservedFailed(flight, accommodation, _)
-            | ItineraryStage::Annulled(flight, accommodation) => {
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => {
                 ItineraryStage::Reserved(flight, accommodation)
             }
         }
@@ -149,7 +186,8 @@ impl ItineraryStage {
             ItineraryStage::Planned(flight, accommodation)
             | ItineraryStage::Reserved(flight, accommodation)
             | ItineraryStage::ReservedFailed(flight, accommodation, _)
-            | ItineraryStage::Annulled(flight, accommodation) => {
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => {
                 ItineraryStage::ReservedFailed(flight, accommodation, reason.clone())
             }
         }
@@ -160,11 +198,44 @@ impl ItineraryStage {
             ItineraryStage::Planned(flight, accommodation)
             | ItineraryStage::Reserved(flight, accommodation)
             | ItineraryStage::ReservedFailed(flight, accommodation, _)
-            | ItineraryStage::Annulled(flight, accommodation) => {
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => {
                 ItineraryStage::Annulled(flight, accommodation)
             }
         }
     }
+
+    pub fn mark_flight_as_disrupted(self, reason: &ItineraryStageDisruption) -> Self {
+        match self {
+            ItineraryStage::Planned(flight, accommodation)
+            | ItineraryStage::Reserved(flight, accommodation)
+            | ItineraryStage::ReservedFailed(flight, accommodation, _)
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => {
+                ItineraryStage::Disrupted(flight, accommodation, reason.clone())
+            }
+        }
+    }
+
+    pub fn with_flight(self, flight: Flight) -> Self {
+        match self {
+            ItineraryStage::Planned(_, accommodation) => ItineraryStage::Planned(flight, accommodation),
+            ItineraryStage::Reserved(_, accommodation) => ItineraryStage::Reserved(flight, accommodation),
+            ItineraryStage::ReservedFailed(_, accommodation, reason) => ItineraryStage::ReservedFailed(flight, accommodation, reason),
+            ItineraryStage::Annulled(_, accommodation) => ItineraryStage::Annulled(flight, accommodation),
+            ItineraryStage::Disrupted(_, accommodation, reason) => ItineraryStage::Disrupted(flight, accommodation, reason),
+        }
+    }
+
+    pub fn flight_and_accommodation(self) -> (Flight, Option<Accommodation>) {
+        match self {
+            ItineraryStage::Planned(flight, accommodation)
+            | ItineraryStage::Reserved(flight, accommodation)
+            | ItineraryStage::ReservedFailed(flight, accommodation, _)
+            | ItineraryStage::Annulled(flight, accommodation)
+            | ItineraryStage::Disrupted(flight, accommodation, _) => (flight, accommodation)
+        }
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Clone)]
@@ -204,4 +275,14 @@ pub enum ItineraryError {
 pub enum ItineraryStageError {
     #[error("insufficient seats")]
     InsufficientSeats,
-}
\ No newline at end of file
+
+    #[error("insufficient rooms")]
+    InsufficientRooms,
+}
+
+/// stage can no longer be travelled as planned, an agent needs to rebook it
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum ItineraryStageDisruption {
+    FlightCancelled,
+    FlightRescheduled,
+}
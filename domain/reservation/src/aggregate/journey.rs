@@ -3,8 +3,10 @@ use chrono::Duration;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use prelude::collection::SortedSet;
-use crate::aggregate::{Accommodation, AccommodationId, AirfieldId, Flight, Itinerary, ItineraryError, JourneyId, JourneyName, Segment};
-use crate::event::JourneyPublishedV1;
+use prelude::data::Money;
+use crate::aggregate::{Accommodation, AccommodationId, AirfieldId, Flight, FlightRoute, Itinerary, ItineraryError, JourneyId, JourneyName, JourneyShape, Segment, SegmentError};
+use crate::event::{JourneyPublishedV1, JourneyRevisedV1, JourneyWithdrawnV1};
+use crate::policy::{AccommodationSurcharge, PricingPolicy, PricingPolicyError};
 
 pub const JOURNEY_MIN_DAYS_IN_ACCOMMODATION: i64 = 3;
 pub const JOURNEY_MAX_DAYS_IN_ACCOMMODATION: i64 = 21;
@@ -17,8 +19,44 @@ pub enum JourneyError {
     #[error("too many segments")]
     TooManySegments,
 
-    #[error("segments don't form a round trip")]
-    SegmentsDontFormARoundTrip,
+    #[error("segments don't connect as the shape of the journey")]
+    SegmentsDontFormShape,
+
+    #[error("journey is withdrawn")]
+    JourneyWithdrawn,
+
+    #[error("unknown segment")]
+    UnknownSegment,
+
+    #[error("unknown accommodation: {0}")]
+    UnknownAccommodation(AccommodationId),
+
+    #[error("{0}")]
+    SegmentError(SegmentError),
+
+    #[error("{0}")]
+    PricingPolicyError(PricingPolicyError),
+}
+
+impl From<SegmentError> for JourneyError {
+    fn from(value: SegmentError) -> Self {
+        Self::SegmentError(value)
+    }
+}
+
+impl From<PricingPolicyError> for JourneyError {
+    fn from(value: PricingPolicyError) -> Self {
+        Self::PricingPolicyError(value)
+    }
+}
+
+/// change to the product content of a journey, the routes of its flights stay as is
+#[derive(Debug, Clone)]
+pub enum JourneyRevision {
+    Rename(JourneyName),
+    /// with the surcharge per night per passenger, if any
+    AddAccommodation(FlightRoute, Accommodation, Option<Money>),
+    RemoveAccommodation(FlightRoute, AccommodationId),
 }
 
 /// Journey is a "blueprint" for the actual itinerary
@@ -27,7 +65,14 @@ pub enum JourneyError {
 pub struct Journey {
     pub id: JourneyId,
     name: JourneyName,
+    #[serde(default)]
+    shape: JourneyShape,
     segments: SortedSet<Segment>,
+    #[serde(default)]
+    pricing_policy: PricingPolicy,
+    /// no longer for sale, reservations made before stay valid
+    #[serde(default)]
+    withdrawn: bool,
 }
 
 impl PartialEq for Journey {
@@ -37,33 +82,157 @@ impl PartialEq for Journey {
 }
 
 impl Journey {
-    pub fn build(id: JourneyId, name: JourneyName, segments: SortedSet<Segment>) -> Result<(Self, JourneyPublishedV1), JourneyError> {
-        if segments.len() < 2 {
+    pub fn build(id: JourneyId, name: JourneyName, shape: JourneyShape, segments: SortedSet<Segment>) -> Result<(Self, JourneyPublishedV1), JourneyError> {
+        if segments.len() < shape.min_segments() {
             return Err(JourneyError::TooFewSegments);
         }
         if segments.len() > 20 {
             return Err(JourneyError::TooManySegments);
         }
-        if !Self::segments_form_round_trip(&segments) {
-            return Err(JourneyError::SegmentsDontFormARoundTrip);
+        if !Self::segments_form_shape(&shape, &segments) {
+            return Err(JourneyError::SegmentsDontFormShape);
         }
 
         let journey = Self {
             id,
             name: name.clone(),
+            shape: shape.clone(),
             segments: segments.clone(),
+            pricing_policy: PricingPolicy::standard(),
+            withdrawn: false,
         };
 
         let event = JourneyPublishedV1 {
             id,
+            name,
+            shape,
+            segments,
+        };
+
+        Ok((journey, event))
+    }
+
+    pub fn with_pricing_policy(self, pricing_policy: PricingPolicy) -> Self {
+        Self {
+            pricing_policy,
+            ..self
+        }
+    }
+
+    pub fn pricing_policy(&self) -> &PricingPolicy {
+        &self.pricing_policy
+    }
+
+    pub fn shape(&self) -> &JourneyShape {
+        &self.shape
+    }
+
+    pub fn is_withdrawn(&self) -> bool {
+        self.withdrawn
+    }
+
+    /// surcharges of removed accommodations are kept, as reservations made before the pricing policy was
+    /// snapshotted on them are still priced by the journey's
+    pub fn revise(self, revisions: Vec<JourneyRevision>) -> Result<(Self, JourneyRevisedV1), JourneyError> {
+        if self.withdrawn {
+            return Err(JourneyError::JourneyWithdrawn);
+        }
+
+        let mut name = self.name.clone();
+        let mut segments = self.segments.clone().to_vec();
+        let mut pricing_policy = self.pricing_policy.clone();
+
+        for revision in revisions {
+            match revision {
+                JourneyRevision::Rename(revised_name) => {
+                    name = revised_name;
+                }
+
+                JourneyRevision::AddAccommodation(route, accommodation, surcharge) => {
+                    let segment = segments.iter_mut()
+                        .find(|segment| segment.flight == route)
+                        .ok_or(JourneyError::UnknownSegment)?;
+
+                    if let Some(surcharge) = surcharge {
+                        pricing_policy = pricing_policy.with_accommodation_surcharge(AccommodationSurcharge {
+                            accommodation: accommodation.id,
+                            price_per_night_per_passenger: surcharge,
+                        })?;
+                    }
+
+                    *segment = Segment::build(segment.flight.clone(), segment.accommodations.clone().insert(accommodation))?;
+                }
+
+                JourneyRevision::RemoveAccommodation(route, id) => {
+                    let segment = segments.iter_mut()
+                        .find(|segment| segment.flight == route)
+                        .ok_or(JourneyError::UnknownSegment)?;
+
+                    if !segment.accommodations.iter().any(|accommodation| accommodation.id == id) {
+                        return Err(JourneyError::UnknownAccommodation(id));
+                    }
+
+                    let accommodations = segment.accommodations.clone().to_vec().into_iter()
+                        .filter(|accommodation| accommodation.id != id)
+                        .collect();
+
+                    *segment = Segment::build(segment.flight.clone(), SortedSet::from_vec(accommodations))?;
+                }
+            }
+        }
+
+        let segments = SortedSet::from_vec(segments);
+        let event = JourneyRevisedV1 {
+            id: self.id,
+            name: name.clone(),
+            shape: self.shape.clone(),
+            segments: segments.clone(),
+        };
+
+        let journey = Self {
             name,
             segments,
+            pricing_policy,
+            ..self
+        };
+
+        Ok((journey, event))
+    }
+
+    pub fn withdraw(self) -> Result<(Self, JourneyWithdrawnV1), JourneyError> {
+        if self.withdrawn {
+            return Err(JourneyError::JourneyWithdrawn);
+        }
+
+        let event = JourneyWithdrawnV1 {
+            id: self.id,
+        };
+
+        let journey = Self {
+            withdrawn: true,
+            ..self
         };
 
         Ok((journey, event))
     }
 
     pub fn parse_itinerary(&self, stages: LinkedList<(Flight, Option<Accommodation>)>) -> Result<Itinerary, ItineraryError> {
+        self.validate_stages(&stages, true)?;
+
+        Itinerary::from_iter(stages)
+    }
+
+    /// re-applies the itinerary rules, e.g. after a flight in it was rescheduled;
+    /// accommodations removed from the journey since are kept
+    pub fn validate_itinerary(&self, itinerary: &Itinerary) -> Result<(), ItineraryError> {
+        let stages = itinerary.stages()
+            .map(|stage| stage.clone().flight_and_accommodation())
+            .collect();
+
+        self.validate_stages(&stages, false)
+    }
+
+    fn validate_stages(&self, stages: &LinkedList<(Flight, Option<Accommodation>)>, check_accommodations: bool) -> Result<(), ItineraryError> {
         let first_stage = stages.front().ok_or(ItineraryError::NoStages)?;
         let last_stage = stages.back().ok_or(ItineraryError::NoStages)?;
 
@@ -81,13 +250,13 @@ impl Journey {
         }
 
         // verify stages follow journey (flights) and accommodations in segment
-        for (segment, (stage_flight, stage_accommodation)) in route.iter().zip(stages.clone()) {
+        for (segment, (stage_flight, stage_accommodation)) in route.iter().zip(stages.iter()) {
             if segment.flight != stage_flight.route {
                return Err(ItineraryError::MalformedRoute);
             }
 
-            if let Some(accommodation) = stage_accommodation {
-                if !segment.accommodations.contains(&accommodation) {
+            if let Some(accommodation) = stage_accommodation.as_ref().filter(|_| check_accommodations) {
+                if !segment.accommodations.contains(accommodation) {
                     return Err(ItineraryError::AccommodationNotInStage);
                 }
             }
@@ -111,7 +280,7 @@ impl Journey {
             }
         }
 
-        Itinerary::from_iter(stages)
+        Ok(())
     }
 
     pub fn find_accommodation(&self, location: &AirfieldId, id: &AccommodationId) -> Option<&Accommodation> {
@@ -121,6 +290,24 @@ impl Journey {
             .find(|accommodation| accommodation.id == *id)
     }
 
+    /// segments departing from the airfield onwards, until back at it or at the end of a one-way journey
+    pub fn route_from(&self, airfield: &AirfieldId) -> Option<LinkedList<&Segment>> {
+        let mut route = LinkedList::new();
+        let mut departs_from = airfield.clone();
+
+        while let Some(segment) = self.segments.find(|p| p.departs_from() == departs_from) {
+            route.push_back(segment);
+
+            departs_from = self.shape.continues_from(segment.arrives_at());
+            if &departs_from == airfield || route.len() >= self.segments.len() {
+                break;
+            }
+        }
+
+        if route.is_empty() { None } else { Some(route) }
+    }
+
+    /// crosses the overland sector of open-jaw journeys
     fn resolve_route(&self, departure: &AirfieldId, arrival: &AirfieldId) -> Option<LinkedList<&Segment>> {
         let mut route = LinkedList::new();
         let mut departs_from = departure.clone();
@@ -134,18 +321,34 @@ impl Journey {
                 return None;
             }
 
-            departs_from = segment.arrives_at();
-            resolved = &departs_from == arrival;
+            resolved = &segment.arrives_at() == arrival;
+            departs_from = self.shape.continues_from(segment.arrives_at());
         }
 
         Some(route)
     }
 
-    fn segments_form_round_trip(segments: &SortedSet<Segment>) -> bool {
+    fn segments_form_shape(shape: &JourneyShape, segments: &SortedSet<Segment>) -> bool {
+        match shape {
+            JourneyShape::RoundTrip => Self::segments_form_loop(shape, segments),
+
+            JourneyShape::OneWay => Self::segments_form_line(segments),
+
+            // the overland sector closes the loop, so no flights depart from its start or arrive at its end
+            JourneyShape::OpenJaw { from, to } => {
+                from != to
+                    && segments.iter().any(|s| &s.arrives_at() == from)
+                    && !segments.iter().any(|s| &s.departs_from() == from || &s.arrives_at() == to)
+                    && Self::segments_form_loop(shape, segments)
+            }
+        }
+    }
+
+    fn segments_form_loop(shape: &JourneyShape, segments: &SortedSet<Segment>) -> bool {
         for segment in segments.iter() {
             let connects_exactly_once = segments
                 .iter()
-                .filter(|s| s.departs_from() == segment.arrives_at())
+                .filter(|s| s.departs_from() == shape.continues_from(segment.arrives_at()))
                 .count() == 1;
 
             if !connects_exactly_once {
@@ -155,6 +358,29 @@ impl Journey {
 
         segments.len() > 1
     }
+
+    /// a single route through all segments, from the only segment no other segment connects to
+    fn segments_form_line(segments: &SortedSet<Segment>) -> bool {
+        let mut origins = segments.iter()
+            .filter(|segment| !segments.iter().any(|s| s.arrives_at() == segment.departs_from()));
+
+        let (Some(origin), None) = (origins.next(), origins.next()) else {
+            return false;
+        };
+
+        let mut connected = 1;
+        let mut arrives_at = origin.arrives_at();
+        while let Some(segment) = segments.find(|s| s.departs_from() == arrives_at) {
+            connected += 1;
+            if connected > segments.len() {
+                return false;
+            }
+
+            arrives_at = segment.arrives_at();
+        }
+
+        connected == segments.len()
+    }
 }
 
 
@@ -162,44 +388,93 @@ impl Journey {
 mod tests {
     use std::collections::{LinkedList};
     use prelude::collection::SortedSet;
-    use crate::aggregate::{Accommodation, Flight, FlightRoute, ItineraryError, Journey, JourneyError, JourneyId, JourneyName, Picture, Place, Segment};
+    use prelude::data::Money;
+    use crate::aggregate::{Accommodation, Flight, FlightRoute, ItineraryError, Journey, JourneyError, JourneyId, JourneyName, JourneyRevision, JourneyShape, Picture, Place, Segment};
 
     #[test]
     fn is_buildable() {
-        let journey = Journey::build(id(), name(), segments());
+        let journey = Journey::build(id(), name(), JourneyShape::RoundTrip, segments());
         assert!(journey.is_ok());
     }
 
     #[test]
     fn errors_on_malformed_input() {
         // too few segments
-        let journey = Journey::build(id(), name(), SortedSet::empty());
+        let journey = Journey::build(id(), name(), JourneyShape::RoundTrip, SortedSet::empty());
         assert_eq!(journey, Err(JourneyError::TooFewSegments));
 
-        let journey = Journey::build(id(), name(), SortedSet::singleton(segment_eham_enli()));
+        let journey = Journey::build(id(), name(), JourneyShape::RoundTrip, SortedSet::singleton(segment_eham_enli()));
         assert_eq!(journey, Err(JourneyError::TooFewSegments));
 
         // segments don't form round trip
         let segments = SortedSet::empty()
             .insert(segment_eham_enli())
             .insert(segment_eham_enbr());
-        let journey = Journey::build(id(), name(), segments);
-        assert_eq!(journey, Err(JourneyError::SegmentsDontFormARoundTrip));
+        let journey = Journey::build(id(), name(), JourneyShape::RoundTrip, segments.clone());
+        assert_eq!(journey, Err(JourneyError::SegmentsDontFormShape));
+
+        // nor a single line
+        let journey = Journey::build(id(), name(), JourneyShape::OneWay, segments);
+        assert_eq!(journey, Err(JourneyError::SegmentsDontFormShape));
+
+        // the overland sector doesn't close the loop
+        let journey = Journey::build(id(), name(), open_jaw_enbr_enli(), segments_open_jaw());
+        assert_eq!(journey, Err(JourneyError::SegmentsDontFormShape));
+        let journey = Journey::build(id(), name(), JourneyShape::RoundTrip, segments_open_jaw());
+        assert_eq!(journey, Err(JourneyError::SegmentsDontFormShape));
+    }
+
+    #[test]
+    fn one_way_journeys_run_point_to_point() {
+        let (journey, _) = Journey::build(id(), name(), JourneyShape::OneWay, SortedSet::singleton(segment_eham_enli())).unwrap();
+        assert!(journey.parse_itinerary(LinkedList::from([(flight_eham_enli_8jan(), None)])).is_ok());
+
+        let (journey, _) = Journey::build(id(), name(), JourneyShape::OneWay, segments_one_way()).unwrap();
+        let stages = LinkedList::from([
+            (flight_enbr_eham_14jan(), None),
+            (flight_eham_enli_20jan(), None),
+        ]);
+        assert!(journey.parse_itinerary(stages).is_ok());
+
+        let result = Journey::build(id(), name(), JourneyShape::OneWay, segments());
+        assert_eq!(result, Err(JourneyError::SegmentsDontFormShape));
+
+        // starting mid-journey
+        assert!(journey.parse_itinerary(LinkedList::from([(flight_eham_enli_20jan(), None)])).is_ok());
+        assert_eq!(journey.route_from(&"ENBR".parse().unwrap()).unwrap().len(), 2);
+        assert_eq!(journey.route_from(&"EHAM".parse().unwrap()).unwrap().len(), 1);
+        assert!(journey.route_from(&"ENLI".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn open_jaw_journeys_cross_the_overland_sector() {
+        let (journey, _) = Journey::build(id(), name(), open_jaw_enli_enbr(), segments_open_jaw()).unwrap();
+        let stages = LinkedList::from([
+            (flight_eham_enli_8jan(), Some(accommodation_enli())),
+            (flight_enbr_eham_14jan(), None),
+        ]);
+        assert!(journey.parse_itinerary(stages).is_ok());
+
+        let route: Vec<String> = journey.route_from(&"ENBR".parse().unwrap()).unwrap().iter()
+            .map(|segment| segment.arrives_at().to_string())
+            .collect();
+        assert_eq!(route, vec!["EHAM".to_owned(), "ENLI".to_owned()]);
+        assert_eq!(journey.route_from(&"EHAM".parse().unwrap()).unwrap().len(), 2);
     }
 
     #[test]
     fn equality_on_id() {
-        let (journey1, _) = Journey::build(id(), name(), segments()).unwrap();
-        let (journey2, _) = Journey::build(id(), name2(), segments()).unwrap();
+        let (journey1, _) = Journey::build(id(), name(), JourneyShape::RoundTrip, segments()).unwrap();
+        let (journey2, _) = Journey::build(id(), name2(), JourneyShape::RoundTrip, segments()).unwrap();
         assert_eq!(journey1, journey2);
 
-        let (journey3, _) = Journey::build(id2(), name(), segments()).unwrap();
+        let (journey3, _) = Journey::build(id2(), name(), JourneyShape::RoundTrip, segments()).unwrap();
         assert_ne!(journey1, journey3);
     }
 
     #[test]
     fn is_serializable() {
-        let (original, _) = Journey::build(id(), name(), segments()).unwrap();
+        let (original, _) = Journey::build(id(), name(), JourneyShape::RoundTrip, segments()).unwrap();
         let serialized = serde_json::to_string(&original).unwrap();
         let deserialized: Journey = serde_json::from_str(&serialized).unwrap();
 
@@ -313,6 +588,65 @@ mod tests {
         assert_eq!(result, Err(ItineraryError::DaysInAccommodationIsTooLong));
     }
 
+    #[test]
+    fn itinerary_can_be_revalidated() {
+        let stages = LinkedList::from([
+            (flight_eham_enli_8jan(), Some(accommodation_enli())),
+            (flight_enli_eham_14jan(), None),
+        ]);
+
+        let journey = journey();
+        let itinerary = journey.parse_itinerary(stages).unwrap();
+        assert_eq!(journey.validate_itinerary(&itinerary), Ok(()));
+
+        // return flight moved forward, stay becomes too short
+        let itinerary = itinerary.reschedule_flight(&flight_enli_eham_9jan());
+        assert_eq!(journey.validate_itinerary(&itinerary), Err(ItineraryError::DaysInAccommodationIsTooShort));
+    }
+
+    #[test]
+    fn accommodations_can_be_revised() {
+        let stages = LinkedList::from([
+            (flight_eham_enli_8jan(), Some(accommodation_enli())),
+            (flight_enli_eham_14jan(), None),
+        ]);
+        let itinerary = journey().parse_itinerary(stages.clone()).unwrap();
+
+        let (journey, event) = journey().revise(vec![
+            JourneyRevision::Rename(name2()),
+            JourneyRevision::AddAccommodation(route_eham_enli(), accommodation_enli2(), Some(Money::usd(5000))),
+            JourneyRevision::RemoveAccommodation(route_eham_enli(), accommodation_enli().id),
+        ]).unwrap();
+
+        assert_eq!(event.name, name2());
+        assert_eq!(event.segments.find(|segment| segment.flight == route_eham_enli()).unwrap().accommodations, SortedSet::singleton(accommodation_enli2()));
+        assert_eq!(journey.pricing_policy().surcharge_per_night_per_passenger(&accommodation_enli2().id), Some(Money::usd(5000)));
+
+        // no longer for sale, but reservations made before stay valid
+        assert_eq!(journey.parse_itinerary(stages), Err(ItineraryError::AccommodationNotInStage));
+        assert_eq!(journey.validate_itinerary(&itinerary), Ok(()));
+    }
+
+    #[test]
+    fn errors_on_malformed_revision() {
+        let result = journey().revise(vec![JourneyRevision::RemoveAccommodation(route_eham_enbr(), accommodation_enli().id)]);
+        assert_eq!(result.err(), Some(JourneyError::UnknownSegment));
+
+        let result = journey().revise(vec![JourneyRevision::RemoveAccommodation(route_eham_enli(), accommodation_enli2().id)]);
+        assert_eq!(result.err(), Some(JourneyError::UnknownAccommodation(accommodation_enli2().id)));
+    }
+
+    #[test]
+    fn withdrawn_journeys_cannot_be_revised() {
+        let (journey, event) = journey().withdraw().unwrap();
+        assert!(journey.is_withdrawn());
+        assert_eq!(event.id, id());
+
+        let result = journey.clone().revise(vec![JourneyRevision::Rename(name2())]);
+        assert_eq!(result.err(), Some(JourneyError::JourneyWithdrawn));
+        assert_eq!(journey.withdraw().err(), Some(JourneyError::JourneyWithdrawn));
+    }
+
     // data generators
     fn id() -> JourneyId {
         "5EPFciXgSxB70tAE8iERl6".parse().unwrap()
@@ -336,6 +670,26 @@ mod tests {
             .insert(segment_enli_eham())
     }
 
+    fn segments_one_way() -> SortedSet<Segment> {
+        SortedSet::empty()
+            .insert(Segment::build(route_enbr_eham(), SortedSet::empty()).unwrap())
+            .insert(segment_eham_enli())
+    }
+
+    fn segments_open_jaw() -> SortedSet<Segment> {
+        SortedSet::empty()
+            .insert(segment_eham_enli())
+            .insert(Segment::build(route_enbr_eham(), SortedSet::empty()).unwrap())
+    }
+
+    fn open_jaw_enli_enbr() -> JourneyShape {
+        JourneyShape::OpenJaw { from: "ENLI".parse().unwrap(), to: "ENBR".parse().unwrap() }
+    }
+
+    fn open_jaw_enbr_enli() -> JourneyShape {
+        JourneyShape::OpenJaw { from: "ENBR".parse().unwrap(), to: "ENLI".parse().unwrap() }
+    }
+
     fn segment_eham_enli() -> Segment {
         let flight = route_eham_enli();
         let accommodation = accommodation_enli();
@@ -351,7 +705,7 @@ mod tests {
     }
 
     fn journey() -> Journey {
-        let (journey, _) = Journey::build(id(), name(), segments()).unwrap();
+        let (journey, _) = Journey::build(id(), name(), JourneyShape::RoundTrip, segments()).unwrap();
         journey
     }
 
@@ -457,4 +811,19 @@ mod tests {
             ).unwrap())
         ).unwrap()
     }
+
+    fn accommodation_enli2() -> Accommodation {
+        Accommodation::build(
+            "5EPFciXgSxB70tAE8iERl7".parse().unwrap(),
+            "Lista Fyr".parse().unwrap(),
+            Place::new(
+                "Farsund, Norway".parse().unwrap(),
+                "u4kf6x".parse().unwrap(),
+            ),
+            SortedSet::singleton(Picture::build(
+                "https://www.visitnorway.com/img/lista.jpg".parse().unwrap(),
+                "Lista Lighthouse".to_owned(),
+            ).unwrap())
+        ).unwrap()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use crate::aggregate::AirfieldId;
+
+/// how the segments of a journey connect
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JourneyShape {
+    /// segments form a loop, back to any airfield departed from
+    #[default]
+    RoundTrip,
+    /// segments form a single route, from an origin to a different destination
+    OneWay,
+    /// segments form a loop, of which the sector between the airfields is travelled overland
+    OpenJaw {
+        from: AirfieldId,
+        to: AirfieldId,
+    },
+}
+
+impl JourneyShape {
+    /// the airfield the route continues from, after arriving at the given one
+    pub fn continues_from(&self, arrival: AirfieldId) -> AirfieldId {
+        match self {
+            Self::OpenJaw { from, to } if from == &arrival => to.clone(),
+            _ => arrival,
+        }
+    }
+
+    pub(super) fn min_segments(&self) -> usize {
+        match self {
+            Self::OneWay => 1,
+            Self::RoundTrip | Self::OpenJaw { .. } => 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{AirfieldId, JourneyShape};
+
+    #[test]
+    fn continues_across_the_jaw() {
+        let shape = JourneyShape::OpenJaw { from: airfield("ENLI"), to: airfield("ENBR") };
+
+        assert_eq!(shape.continues_from(airfield("ENLI")), airfield("ENBR"));
+        assert_eq!(shape.continues_from(airfield("EHAM")), airfield("EHAM"));
+        assert_eq!(JourneyShape::RoundTrip.continues_from(airfield("ENLI")), airfield("ENLI"));
+    }
+
+    #[test]
+    fn is_serializable() {
+        let shape = JourneyShape::OpenJaw { from: airfield("ENLI"), to: airfield("ENBR") };
+        let serialized = serde_json::to_string(&shape).unwrap();
+
+        assert_eq!(serialized, r#"{"open_jaw":{"from":"ENLI","to":"ENBR"}}"#);
+        assert_eq!(serde_json::from_str::<JourneyShape>(&serialized).unwrap(), shape);
+        assert_eq!(serde_json::to_string(&JourneyShape::OneWay).unwrap(), r#""one_way""#);
+    }
+
+    // test data
+    fn airfield(value: &str) -> AirfieldId {
+        value.parse().unwrap()
+    }
+}
@@ -1,6 +1,7 @@
 mod journey_id;
 mod journey_name;
 mod journey;
+mod journey_shape;
 mod segment;
 mod flight_route;
 mod icao_code;
@@ -12,30 +13,41 @@ mod accommodation_name;
 mod place;
 mod place_name;
 mod flight_availability;
+mod accommodation_availability;
 mod flight_id;
 mod reservation;
 mod reservation_id;
 mod passengers;
+mod payment;
 mod contact;
 mod person_full_name;
 mod email_address;
+mod email_verification_challenge;
 mod phone_number;
 mod itinerary;
 mod flight;
+mod airship_model;
 mod number_of_seats;
 mod airfield;
+mod seat_number;
+mod seat_map;
+mod check_in;
+mod secret;
 
 
 pub use journey_id::*;
 pub use journey_name::*;
 pub use journey::*;
+pub use journey_shape::*;
 pub use icao_code::*;
 pub use airfield_id::*;
 pub use airfield::*;
 pub use segment::*;
 pub use flight_availability::*;
+pub use accommodation_availability::*;
 pub use flight_id::*;
 pub use flight::*;
+pub use airship_model::*;
 pub use flight_route::*;
 pub use picture::*;
 pub use accommodation::*;
@@ -46,9 +58,15 @@ pub use place_name::*;
 pub use reservation::*;
 pub use reservation_id::*;
 pub use passengers::*;
+pub use payment::*;
 pub use contact::*;
 pub use person_full_name::*;
 pub use phone_number::*;
 pub use email_address::*;
+pub use email_verification_challenge::*;
 pub use itinerary::*;
 pub use number_of_seats::*;
+pub use seat_number::*;
+pub use seat_map::*;
+pub use check_in::*;
+pub(crate) use secret::*;
@@ -110,6 +110,23 @@ pub struct Passenger {
     date_of_birth: NaiveDate
 }
 
+impl Passenger {
+    pub fn new(name: PersonFullName, date_of_birth: NaiveDate) -> Self {
+        Self {
+            name,
+            date_of_birth,
+        }
+    }
+
+    pub fn name(&self) -> &PersonFullName {
+        &self.name
+    }
+
+    pub fn date_of_birth(&self) -> NaiveDate {
+        self.date_of_birth
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
@@ -0,0 +1,251 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::data::{Currency, Money, MoneyError};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PaymentError {
+    #[error("reservation is cancelled")]
+    ReservationIsCancelled,
+
+    #[error("hold has expired")]
+    HoldExpired,
+
+    #[error("amount should be positive")]
+    AmountNotPositive,
+
+    #[error("amount is finer than the minor unit of its currency")]
+    AmountFinerThanMinorUnit,
+
+    #[error("amount exceeds balance")]
+    AmountExceedsBalance,
+
+    #[error("unknown payment")]
+    UnknownPayment,
+
+    #[error("refund exceeds refundable amount of payment")]
+    RefundExceedsPayment,
+
+    #[error("{0}")]
+    MoneyError(MoneyError),
+}
+
+impl From<MoneyError> for PaymentError {
+    fn from(value: MoneyError) -> Self {
+        Self::MoneyError(value)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PaymentReferenceError {
+    #[error("empty reference")]
+    Empty,
+}
+
+/// reference of a payment, or refund, at the payment gateway
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PaymentReference(String);
+
+impl FromStr for PaymentReference {
+    type Err = PaymentReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(PaymentReferenceError::Empty);
+        }
+
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl Display for PaymentReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Payment {
+    pub reference: PaymentReference,
+    pub amount: Money,
+    pub time: DateTime<Utc>,
+}
+
+/// refunds (part of) a payment
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Refund {
+    pub payment: PaymentReference,
+    pub reference: PaymentReference,
+    pub amount: Money,
+    pub time: DateTime<Utc>,
+}
+
+/// ledger of money received for a reservation
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct Payments {
+    payments: Vec<Payment>,
+    refunds: Vec<Refund>,
+}
+
+impl Payments {
+    pub fn payments(&self) -> &[Payment] {
+        &self.payments
+    }
+
+    pub fn refunds(&self) -> &[Refund] {
+        &self.refunds
+    }
+
+    /// payments minus refunds
+    pub fn received(&self, currency: Currency) -> Result<Money, MoneyError> {
+        let paid = self.payments.iter()
+            .try_fold(Money::zero(currency), |total, payment| total.checked_add(payment.amount))?;
+
+        self.refunds.iter()
+            .try_fold(paid, |total, refund| total.checked_sub(refund.amount))
+    }
+
+    /// amount of the payment not refunded yet
+    pub fn refundable(&self, payment: &PaymentReference) -> Result<Money, PaymentError> {
+        let paid = self.payments.iter()
+            .find(|candidate| &candidate.reference == payment)
+            .ok_or(PaymentError::UnknownPayment)?
+            .amount;
+
+        let refundable = self.refunds.iter()
+            .filter(|refund| &refund.payment == payment)
+            .try_fold(paid, |total, refund| total.checked_sub(refund.amount))?;
+
+        Ok(refundable)
+    }
+
+    /// amount of the payment refunded so far
+    pub fn refunded(&self, payment: &PaymentReference) -> Result<Money, PaymentError> {
+        let paid = self.payments.iter()
+            .find(|candidate| &candidate.reference == payment)
+            .ok_or(PaymentError::UnknownPayment)?
+            .amount;
+
+        Ok(paid.checked_sub(self.refundable(payment)?)?)
+    }
+
+    /// spreads the amount over the payments, most recent payment first
+    pub fn plan_refunds(&self, amount: Money) -> Result<Vec<(PaymentReference, Money)>, PaymentError> {
+        let mut remaining = amount;
+        let mut refunds = Vec::new();
+
+        for payment in self.payments.iter().rev() {
+            if remaining <= Money::zero(remaining.currency()) {
+                break;
+            }
+
+            let refundable = self.refundable(&payment.reference)?;
+            if refundable <= Money::zero(refundable.currency()) {
+                continue;
+            }
+
+            let refund = if refundable < remaining { refundable } else { remaining };
+            remaining = remaining.checked_sub(refund)?;
+            refunds.push((payment.reference.clone(), refund));
+        }
+
+        Ok(refunds)
+    }
+
+    pub(super) fn record_payment(mut self, payment: Payment) -> Self {
+        self.payments.push(payment);
+        self
+    }
+
+    pub(super) fn record_refund(mut self, refund: Refund) -> Result<Self, PaymentError> {
+        let refundable = self.refundable(&refund.payment)?;
+        if refund.amount > refundable {
+            return Err(PaymentError::RefundExceedsPayment);
+        }
+
+        self.refunds.push(refund);
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use prelude::data::{Currency, Money};
+    use crate::aggregate::{Payment, PaymentError, PaymentReference, Payments, Refund};
+
+    #[test]
+    fn received_is_payments_minus_refunds() {
+        let payments = payments()
+            .record_refund(refund("pay_2", "re_1", 50_00)).unwrap();
+
+        assert_eq!(payments.received(Currency::USD), Ok(Money::usd(250_00)));
+    }
+
+    #[test]
+    fn refunds_are_planned_from_most_recent_payment() {
+        let payments = payments();
+
+        let refunds = payments.plan_refunds(Money::usd(250_00)).unwrap();
+        assert_eq!(refunds, vec![
+            (reference("pay_2"), Money::usd(200_00)),
+            (reference("pay_1"), Money::usd(50_00)),
+        ]);
+
+        let payments = payments
+            .record_refund(refund("pay_2", "re_1", 150_00)).unwrap();
+
+        let refunds = payments.plan_refunds(Money::usd(100_00)).unwrap();
+        assert_eq!(refunds, vec![
+            (reference("pay_2"), Money::usd(50_00)),
+            (reference("pay_1"), Money::usd(50_00)),
+        ]);
+    }
+
+    #[test]
+    fn errors_on_refund_exceeding_payment() {
+        let result = payments()
+            .record_refund(refund("pay_1", "re_1", 100_01));
+        assert_eq!(result, Err(PaymentError::RefundExceedsPayment));
+
+        let result = payments()
+            .record_refund(refund("pay_3", "re_1", 10_00));
+        assert_eq!(result, Err(PaymentError::UnknownPayment));
+    }
+
+    // test data
+    fn payments() -> Payments {
+        Payments::default()
+            .record_payment(payment("pay_1", 100_00))
+            .record_payment(payment("pay_2", 200_00))
+    }
+
+    fn payment(reference: &str, cents: i64) -> Payment {
+        Payment {
+            reference: self::reference(reference),
+            amount: Money::usd(cents),
+            time: now(),
+        }
+    }
+
+    fn refund(payment: &str, reference: &str, cents: i64) -> Refund {
+        Refund {
+            payment: self::reference(payment),
+            reference: self::reference(reference),
+            amount: Money::usd(cents),
+            time: now(),
+        }
+    }
+
+    fn reference(value: &str) -> PaymentReference {
+        value.parse().unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2024-01-08T09:00:00Z".parse().unwrap()
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -31,6 +32,11 @@ impl FromStr for PhoneNumber {
     }
 }
 
+impl Display for PhoneNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1,26 +1,34 @@
 use std::cmp::Ordering;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use prelude::data::Money;
 use prelude::domain::Version;
-use crate::aggregate::{Contact, EmailVerificationError, FlightId, Itinerary, ItineraryStageError, JourneyId, PassengerArrangement, Passengers, PhoneNumber, ReservationId};
-use crate::event::{ReservationCancelledV1, ReservationConfirmedV1, ReservationRevisedV1};
-use crate::policy::{ReservationPolicy, ReservationRevisionResult};
+use crate::aggregate::{check_in_window, AssignedSeat, BoardingPass, BoardingStatus, CheckInError, Contact, EmailVerificationChallenge, EmailVerificationError, Flight, FlightId, Itinerary, ItineraryStage, ItineraryStageDisruption, ItineraryStageError, Journey, JourneyId, PassengerArrangement, Passengers, Payment, PaymentError, PaymentReference, Payments, PassengerBoarding, PersonFullName, PhoneNumber, Refund, ReservationId, SeatAssignmentError, SeatNumber, SeatRequest};
+use crate::event::{ContactEmailVerificationRequestedV1, ContactEmailVerifiedV1, PaymentRecordedV1, RefundIssuedV1, ReservationCancelledV1, ReservationConfirmedV1, ReservationContactRevisedV1, ReservationHeldV1, ReservationHoldReleasedV1, ReservationRevisedV1, SeatsAssignedV1, PassengersCheckedInV1, PassengerBoardedV1, PassengersNoShowV1};
+use crate::policy::{PricingPolicy, ReservationPolicy, ReservationRevisionResult};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Reservation {
+    Held(HeldReservation),
     Confirmed(ConfirmedReservation),
     Cancelled(CancelledReservation),
 }
 
 impl Reservation {
-    pub fn new_confirmed(policy: &ReservationPolicy, id: ReservationId, journey: JourneyId, contact: Contact, passengers: PassengerArrangement, itinerary: Itinerary) -> ReservationRevisionResult<(Self, ReservationConfirmedV1)> {
+    /// priced by the journey's pricing policy as of now, later revisions of the journey do not affect it
+    pub fn new_confirmed(policy: &ReservationPolicy, id: ReservationId, journey: &Journey, contact: Contact, passengers: PassengerArrangement, itinerary: Itinerary) -> ReservationRevisionResult<(Self, ReservationConfirmedV1)> {
         let reservation = ConfirmedReservation{
             id,
-            journey,
+            journey: journey.id,
             contact: contact.clone(),
             passengers: Passengers::Arrangement(passengers.clone()),
             itinerary: itinerary.clone(),
             revisions: Vec::default(),
+            contact_email_verification: None,
+            payments: Payments::default(),
+            seats: Vec::new(),
+            boarding: Vec::new(),
+            pricing_policy: Some(journey.pricing_policy().clone()),
         };
 
         let reservation = policy.new_confirmed(reservation, |reservation| {
@@ -29,7 +37,7 @@ impl Reservation {
 
         let event =  ReservationConfirmedV1 {
             id,
-            journey,
+            journey: journey.id,
             contact,
             passengers: Passengers::Arrangement(passengers),
             itinerary,
@@ -39,27 +47,134 @@ impl Reservation {
         Ok((reservation, event))
     }
 
+    /// seats are reserved as for a confirmed reservation, but released unless confirmed before the hold expires
+    pub fn new_held(policy: &ReservationPolicy, id: ReservationId, journey: &Journey, contact: Contact, passengers: PassengerArrangement, itinerary: Itinerary) -> ReservationRevisionResult<(Self, ReservationHeldV1)> {
+        let expires_at = policy.hold_expires_at(Utc::now());
+        let reservation = ConfirmedReservation{
+            id,
+            journey: journey.id,
+            contact: contact.clone(),
+            passengers: Passengers::Arrangement(passengers.clone()),
+            itinerary: itinerary.clone(),
+            revisions: Vec::default(),
+            contact_email_verification: None,
+            payments: Payments::default(),
+            seats: Vec::new(),
+            boarding: Vec::new(),
+            pricing_policy: Some(journey.pricing_policy().clone()),
+        };
+
+        let reservation = policy.new_held(reservation, |reservation| {
+            Self::Held(HeldReservation {
+                reservation,
+                expires_at,
+            })
+        })?;
+
+        let event = ReservationHeldV1 {
+            id,
+            journey: journey.id,
+            contact,
+            passengers: Passengers::Arrangement(passengers),
+            itinerary,
+            expires_at,
+        };
+
+        Ok((reservation, event))
+    }
+
+    pub fn confirm_hold(self, policy: &ReservationPolicy) -> ReservationRevisionResult<(Self, Option<ReservationConfirmedV1>)> {
+        policy.confirm_hold(self, Utc::now(), |reservation| {
+            match reservation {
+                Self::Held(held) => {
+                    let reservation = held.reservation;
+                    let event = ReservationConfirmedV1 {
+                        id: reservation.id,
+                        journey: reservation.journey,
+                        contact: reservation.contact.clone(),
+                        passengers: reservation.passengers.clone(),
+                        itinerary: reservation.itinerary.clone(),
+                        revisions: reservation.revisions.clone(),
+                    };
+
+                    (Self::Confirmed(reservation), Some(event))
+                }
+
+                other => {
+                    (other, None)
+                }
+            }
+        })
+    }
+
+    /// an expired hold is cancelled, hence its seats are annulled
+    pub fn release_hold(self, now: DateTime<Utc>) -> (Self, Option<ReservationHoldReleasedV1>) {
+        match self {
+            Self::Held(held) if held.expires_at <= now => {
+                let event = ReservationHoldReleasedV1 {
+                    id: held.reservation.id,
+                    journey: held.reservation.journey,
+                    contact: held.reservation.contact.clone(),
+                };
+
+                let released = CancelledReservation {
+                    released: true,
+                    ..held.reservation.cancel(now)
+                };
+
+                (Self::Cancelled(released), Some(event))
+            }
+
+            other => {
+                (other, None)
+            }
+        }
+    }
+
+    pub fn is_expired_hold(&self, now: DateTime<Utc>) -> bool {
+        matches!(self, Self::Held(held) if held.expires_at <= now)
+    }
+
     pub fn id(&self) -> ReservationId {
         match self {
-            Self::Confirmed(reservation) => reservation.id,
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation.id,
             Self::Cancelled(reservation) => reservation.id,
         }
     }
 
     pub fn journey(&self) -> JourneyId {
         match self {
-            Self::Confirmed(reservation) => reservation.journey,
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation.journey,
             Self::Cancelled(reservation) => reservation.journey,
         }
     }
 
-    pub fn revise_itinerary(self, policy: &ReservationPolicy, itinerary: Itinerary) -> ReservationRevisionResult<(Self, Option<ReservationRevisedV1>)> {
+    pub fn contact(&self) -> &Contact {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => &reservation.contact,
+            Self::Cancelled(reservation) => &reservation.contact,
+        }
+    }
+
+    /// as of confirmation or the latest itinerary revision, none for reservations made before it was kept
+    pub fn pricing_policy(&self) -> Option<&PricingPolicy> {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation.pricing_policy.as_ref(),
+            Self::Cancelled(reservation) => reservation.pricing_policy.as_ref(),
+        }
+    }
+
+    /// a revised itinerary is priced by the journey's pricing policy as of the revision
+    pub fn revise_itinerary(self, policy: &ReservationPolicy, journey: &Journey, itinerary: Itinerary) -> ReservationRevisionResult<(Self, Option<ReservationRevisedV1>)> {
         policy.revise_itinerary(self, itinerary, |reservation, itinerary| {
             match reservation {
                 Self::Confirmed(reservation) if !reservation.itinerary.equivalent(&itinerary) => {
                     let state = Self::Confirmed(ConfirmedReservation{
                         itinerary: itinerary.clone(),
                         passengers: reservation.passengers.clone(),
+                        seats: Vec::new(),
+                        boarding: Vec::new(),
+                        pricing_policy: Some(journey.pricing_policy().clone()),
                         revisions: [
                             vec!((reservation.revisions.len() as Version, reservation.passengers.clone(), reservation.itinerary.clone())),
                             reservation.revisions
@@ -77,12 +192,8 @@ impl Reservation {
                     (state, Some(event))
                 }
 
-                confirmed@Self::Confirmed(_) => {
-                    (confirmed, None)
-                }
-
-                cancelled@Self::Cancelled(_) => {
-                    (cancelled, None)
+                other => {
+                    (other, None)
                 }
             }
         })
@@ -95,6 +206,8 @@ impl Reservation {
                     let state = Self::Confirmed(ConfirmedReservation {
                         passengers: passengers.clone(),
                         itinerary: reservation.itinerary.planned(),
+                        seats: Vec::new(),
+                        boarding: Vec::new(),
                         revisions: [
                             vec!((reservation.revisions.len() as Version, reservation.passengers, reservation.itinerary.clone())),
                             reservation.revisions
@@ -112,39 +225,64 @@ impl Reservation {
                     (state, Some(event))
                 }
 
-                confirmed@Self::Confirmed(_) => {
-                    (confirmed, None)
-                }
-
-                cancelled@Self::Cancelled(_) => {
-                    (cancelled, None)
+                other => {
+                    (other, None)
                 }
             }
         })
     }
 
-    pub fn cancel(self, policy: &ReservationPolicy) -> ReservationRevisionResult<(Self, Option<ReservationCancelledV1>)> {
-        policy.cancel(self, |reservation| {
-            match reservation {
+    /// a changed email address needs to be verified (again)
+    pub fn revise_contact(self, policy: &ReservationPolicy, contact: Contact) -> ReservationRevisionResult<(Self, Option<ReservationContactRevisedV1>)> {
+        policy.revise_contact(self, contact, |reservation, contact| {
+            let (reservation, hold) = reservation.unhold();
+            let (reservation, event) = match reservation {
                 Self::Confirmed(reservation) => {
-                    let state = Self::Cancelled(CancelledReservation {
+                    let same_email = reservation.contact.email.address() == contact.email.address();
+                    let contact = Contact {
+                        email: if same_email { reservation.contact.email.clone() } else { contact.email.unverified() },
+                        ..contact
+                    };
+
+                    if contact == reservation.contact {
+                        return (Self::Confirmed(reservation), None);
+                    }
+
+                    let event = ReservationContactRevisedV1 {
                         id: reservation.id,
                         journey: reservation.journey,
-                        contact: reservation.contact.clone(),
-                        revisions: [
-                            vec!((reservation.revisions.len() as Version, reservation.passengers, reservation.itinerary.clone())),
-                            reservation.revisions
-                        ].concat(),
-                        time: Utc::now(),
+                        contact: contact.clone(),
+                    };
+
+                    let state = Self::Confirmed(ConfirmedReservation {
+                        contact,
+                        contact_email_verification: reservation.contact_email_verification.filter(|_| same_email),
+                        ..reservation
                     });
 
+                    (state, Some(event))
+                }
+
+                other => {
+                    (other, None)
+                }
+            };
+
+            (reservation.hold_until(hold), event)
+        })
+    }
+
+    pub fn cancel(self, policy: &ReservationPolicy) -> ReservationRevisionResult<(Self, Option<ReservationCancelledV1>)> {
+        policy.cancel(self, |reservation| {
+            match reservation {
+                Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => {
                     let event = ReservationCancelledV1 {
                         id: reservation.id,
                         journey: reservation.journey,
-                        contact: reservation.contact,
+                        contact: reservation.contact.clone(),
                     };
 
-                    (state, Some(event))
+                    (Self::Cancelled(reservation.cancel(Utc::now())), Some(event))
                 }
 
                 cancelled@Self::Cancelled(_) => {
@@ -154,38 +292,476 @@ impl Reservation {
         })
     }
 
-    pub fn contact_email_verify_challenge(&self) -> Option<String> {
-        match self {
+    pub fn request_contact_email_verification(self, now: DateTime<Utc>) -> Result<(Self, ContactEmailVerificationRequestedV1), EmailVerificationError> {
+        if self.contact().email_is_verified() {
+            return Err(EmailVerificationError::AlreadyVerified);
+        }
+
+        // a new challenge replaces the previous one
+        let challenge = EmailVerificationChallenge::new_random(now);
+        let event = ContactEmailVerificationRequestedV1 {
+            id: self.id(),
+            email: self.contact().email.clone(),
+            challenge: challenge.value().to_owned(),
+            expires_at: challenge.expires_at(),
+        };
+
+        let (reservation, hold) = self.unhold();
+        let reservation = match reservation {
             Self::Confirmed(reservation) => {
-                reservation.contact.email_verify_challenge()
+                Self::Confirmed(ConfirmedReservation {
+                    contact_email_verification: Some(challenge),
+                    ..reservation
+                })
             }
 
             Self::Cancelled(reservation) => {
-                reservation.contact.email_verify_challenge()
+                Self::Cancelled(CancelledReservation {
+                    contact_email_verification: Some(challenge),
+                    ..reservation
+                })
             }
+
+            held@Self::Held(_) => held,
+        };
+
+        Ok((reservation.hold_until(hold), event))
+    }
+
+    pub fn verify_contact_email(self, challenge: &str, now: DateTime<Utc>) -> Result<(Self, Option<ContactEmailVerifiedV1>), EmailVerificationError> {
+        if self.contact().email_is_verified() {
+            return Ok((self, None));
         }
+
+        self.contact_email_verification()
+            .ok_or(EmailVerificationError::NoChallengeIssued)?
+            .verify(challenge, now)?;
+
+        let (reservation, hold) = self.unhold();
+        let reservation = match reservation {
+            Self::Confirmed(reservation) => {
+                Self::Confirmed(ConfirmedReservation {
+                    contact: reservation.contact.verify_email(),
+                    contact_email_verification: None,
+                    ..reservation
+                })
+            }
+
+            Self::Cancelled(reservation) => {
+                Self::Cancelled(CancelledReservation {
+                    contact: reservation.contact.verify_email(),
+                    contact_email_verification: None,
+                    ..reservation
+                })
+            }
+
+            held@Self::Held(_) => held,
+        }.hold_until(hold);
+
+        let event = ContactEmailVerifiedV1 {
+            id: reservation.id(),
+            email: reservation.contact().email.clone(),
+        };
+
+        Ok((reservation, Some(event)))
     }
 
-    pub fn verify_contact_email(self, challenge: String) -> Result<Reservation, EmailVerificationError> {
+    /// counts a returned challenge that didn't match
+    pub fn fail_contact_email_verification(self) -> Self {
+        let (reservation, hold) = self.unhold();
+        match reservation {
+            Self::Confirmed(reservation) => {
+                Self::Confirmed(ConfirmedReservation {
+                    contact_email_verification: reservation.contact_email_verification.map(|challenge| challenge.fail()),
+                    ..reservation
+                })
+            }
+
+            Self::Cancelled(reservation) => {
+                Self::Cancelled(CancelledReservation {
+                    contact_email_verification: reservation.contact_email_verification.map(|challenge| challenge.fail()),
+                    ..reservation
+                })
+            }
+
+            held@Self::Held(_) => held,
+        }.hold_until(hold)
+    }
+
+    fn contact_email_verification(&self) -> Option<&EmailVerificationChallenge> {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation.contact_email_verification.as_ref(),
+            Self::Cancelled(reservation) => reservation.contact_email_verification.as_ref(),
+        }
+    }
+
+    pub fn payments(&self) -> &Payments {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => &reservation.payments,
+            Self::Cancelled(reservation) => &reservation.payments,
+        }
+    }
+
+    /// cancelled reservations hold no seats
+    pub fn seats(&self) -> &[AssignedSeat] {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => &reservation.seats,
+            Self::Cancelled(_) => &[],
+        }
+    }
+
+    /// cancelled reservations board no one
+    pub fn boarding(&self) -> &[PassengerBoarding] {
         match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => &reservation.boarding,
+            Self::Cancelled(_) => &[],
+        }
+    }
+
+    /// positive when owed by the contact, negative when to be refunded
+    pub fn balance(&self, amount_due: Money) -> Result<Money, PaymentError> {
+        let received = self.payments().received(amount_due.currency())?;
+        Ok(amount_due.checked_sub(received)?)
+    }
+
+    /// verifies a payment is acceptable, before charging it; an expired hold is, even if not released yet
+    pub fn validate_payment(&self, amount_due: Money, amount: Money, now: DateTime<Utc>) -> Result<(), PaymentError> {
+        if let Self::Cancelled(_) = self {
+            return Err(PaymentError::ReservationIsCancelled);
+        }
+        if self.is_expired_hold(now) {
+            return Err(PaymentError::HoldExpired);
+        }
+        if amount <= Money::zero(amount.currency()) {
+            return Err(PaymentError::AmountNotPositive);
+        }
+        if amount != amount.round() {
+            return Err(PaymentError::AmountFinerThanMinorUnit);
+        }
+        if amount > self.balance(amount_due)? {
+            return Err(PaymentError::AmountExceedsBalance);
+        }
+
+        Ok(())
+    }
+
+    pub fn record_payment(self, amount_due: Money, payment: Payment) -> Result<(Self, PaymentRecordedV1), PaymentError> {
+        self.validate_payment(amount_due, payment.amount, payment.time)?;
+
+        let event = PaymentRecordedV1 {
+            id: self.id(),
+            journey: self.journey(),
+            reference: payment.reference.clone(),
+            amount: payment.amount,
+            time: payment.time,
+        };
+
+        let (reservation, hold) = self.unhold();
+        let reservation = match reservation {
+            Self::Confirmed(reservation) => {
+                Self::Confirmed(ConfirmedReservation {
+                    payments: reservation.payments.clone().record_payment(payment),
+                    ..reservation
+                })
+            }
+
+            other => other,
+        }.hold_until(hold);
+
+        Ok((reservation, event))
+    }
+
+    /// refunds owed when more is received than due, e.g. after cancellation
+    pub fn refunds_due(&self, amount_due: Money) -> Result<Vec<(PaymentReference, Money)>, PaymentError> {
+        let balance = self.balance(amount_due)?;
+        if balance >= Money::zero(balance.currency()) {
+            return Ok(vec![]);
+        }
+
+        self.payments().plan_refunds(Money::zero(balance.currency()).checked_sub(balance)?)
+    }
+
+    pub fn record_refund(self, refund: Refund) -> Result<(Self, RefundIssuedV1), PaymentError> {
+        let event = RefundIssuedV1 {
+            id: self.id(),
+            journey: self.journey(),
+            payment: refund.payment.clone(),
+            reference: refund.reference.clone(),
+            amount: refund.amount,
+            time: refund.time,
+        };
+
+        let (reservation, hold) = self.unhold();
+        let reservation = match reservation {
             Self::Confirmed(reservation) => {
-                Ok(Self::Confirmed(ConfirmedReservation {
-                    contact: reservation.contact.verify_email(challenge)?,
+                Self::Confirmed(ConfirmedReservation {
+                    payments: reservation.payments.clone().record_refund(refund)?,
                     ..reservation
-                }))
+                })
             }
 
             Self::Cancelled(reservation) => {
-                Ok(Self::Cancelled(CancelledReservation {
-                    contact: reservation.contact.verify_email(challenge)?,
+                Self::Cancelled(CancelledReservation {
+                    payments: reservation.payments.clone().record_refund(refund)?,
                     ..reservation
-                }))
+                })
             }
+
+            held@Self::Held(_) => held,
+        }.hold_until(hold);
+
+        Ok((reservation, event))
+    }
+
+    /// a seat for every passenger, listed by name, on a reserved flight; chosen seats are taken as is
+    pub fn seat_requests(&self, flight: &FlightId, chosen: Vec<(PersonFullName, SeatNumber)>) -> Result<Vec<SeatRequest>, SeatAssignmentError> {
+        let reservation = match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation,
+            Self::Cancelled(_) => return Err(SeatAssignmentError::FlightNotReserved),
+        };
+
+        if reservation.reserved_flight(flight).is_none() {
+            return Err(SeatAssignmentError::FlightNotReserved);
+        }
+
+        let Passengers::List(passengers) = &reservation.passengers else {
+            return Err(SeatAssignmentError::PassengersNotListed);
+        };
+
+        if let Some((name, _)) = chosen.iter().find(|(name, _)| !passengers.iter().any(|passenger| passenger.name() == name)) {
+            return Err(SeatAssignmentError::UnknownPassenger(name.to_string()));
         }
+
+        Ok(passengers.iter()
+            .map(|passenger| SeatRequest {
+                passenger: passenger.name().clone(),
+                seat: chosen.iter()
+                    .find(|(name, _)| passenger.name() == name)
+                    .map(|(_, seat)| seat.clone()),
+            })
+            .collect())
     }
 
-    pub fn set_contact_phone(self, phone: PhoneNumber) -> Self {
+    /// replaces the seats on the flight, as assigned by its availability
+    pub fn assign_seats(self, policy: &ReservationPolicy, flight: &FlightId, seats: Vec<AssignedSeat>) -> ReservationRevisionResult<(Self, Option<SeatsAssignedV1>)> {
+        policy.assign_seats(self, |reservation| {
+            let (reservation, hold) = reservation.unhold();
+            let (reservation, event) = match reservation {
+                Self::Confirmed(reservation) => {
+                    let unchanged: Vec<&AssignedSeat> = reservation.seats.iter()
+                        .filter(|seat| &seat.flight == flight)
+                        .collect();
+                    if unchanged.len() == seats.len() && unchanged.iter().zip(seats.iter()).all(|(a, b)| *a == b) {
+                        return (Self::Confirmed(reservation).hold_until(hold), None);
+                    }
+
+                    let event = SeatsAssignedV1 {
+                        reservation: reservation.id,
+                        flight: flight.clone(),
+                        seats: seats.clone(),
+                    };
+
+                    let state = Self::Confirmed(ConfirmedReservation {
+                        seats: reservation.seats.into_iter()
+                            .filter(|seat| &seat.flight != flight)
+                            .chain(seats)
+                            .collect(),
+                        ..reservation
+                    });
+
+                    (state, Some(event))
+                }
+
+                other => {
+                    (other, None)
+                }
+            };
+
+            (reservation.hold_until(hold), event)
+        })
+    }
+
+    /// issues boarding passes to passengers on a reserved flight, all listed passengers when none are named;
+    /// checking in again returns the boarding passes issued before
+    pub fn check_in(self, flight: &FlightId, passengers: Vec<PersonFullName>, now: DateTime<Utc>) -> Result<(Self, Vec<BoardingPass>, Option<PassengersCheckedInV1>), CheckInError> {
+        let reservation = self.confirmed_for_boarding()?;
+        let departure = reservation.reserved_flight(flight)
+            .ok_or(CheckInError::FlightNotReserved)?
+            .departure;
+
+        let (opens_at, closes_at) = check_in_window(departure);
+        if now < opens_at {
+            return Err(CheckInError::CheckInNotOpen);
+        }
+        if now >= closes_at {
+            return Err(CheckInError::CheckInClosed);
+        }
+
+        let Passengers::List(listed) = &reservation.passengers else {
+            return Err(CheckInError::PassengersNotListed);
+        };
+
+        if let Some(name) = passengers.iter().find(|name| !listed.iter().any(|passenger| passenger.name() == *name)) {
+            return Err(CheckInError::UnknownPassenger(name.to_string()));
+        }
+
+        let passengers = if passengers.is_empty() {
+            listed.iter().map(|passenger| passenger.name().clone()).collect()
+        }
+        else {
+            passengers
+        };
+
+        let mut boarding = reservation.boarding.clone();
+        let mut boarding_passes = Vec::new();
+        let mut checked_in = Vec::new();
+        for passenger in passengers {
+            let existing = boarding.iter()
+                .find(|boarding| &boarding.flight == flight && boarding.passenger == passenger);
+
+            match existing {
+                Some(existing) => {
+                    boarding_passes.push(existing.boarding_pass().ok_or(CheckInError::NoShow)?.clone());
+                }
+
+                None => {
+                    let seat = reservation.seats.iter()
+                        .find(|seat| &seat.flight == flight && seat.passenger == passenger)
+                        .map(|seat| seat.seat.clone());
+
+                    let boarding_pass = BoardingPass::new_random(flight.clone(), passenger.clone(), seat, now);
+                    boarding.push(PassengerBoarding {
+                        flight: flight.clone(),
+                        passenger: passenger.clone(),
+                        status: BoardingStatus::CheckedIn(boarding_pass.clone()),
+                    });
+
+                    boarding_passes.push(boarding_pass);
+                    checked_in.push(passenger);
+                }
+            }
+        }
+
+        let event = if checked_in.is_empty() {
+            None
+        }
+        else {
+            Some(PassengersCheckedInV1 {
+                reservation: reservation.id,
+                flight: flight.clone(),
+                passengers: checked_in,
+            })
+        };
+
+        let reservation = Self::Confirmed(ConfirmedReservation {
+            boarding,
+            ..reservation
+        });
+
+        Ok((reservation, boarding_passes, event))
+    }
+
+    /// the boarding pass is presented at the gate, before departure
+    pub fn board(self, flight: &FlightId, code: &str, now: DateTime<Utc>) -> Result<(Self, PassengerBoardedV1), CheckInError> {
+        let mut reservation = self.confirmed_for_boarding()?;
+        let departure = reservation.reserved_flight(flight)
+            .ok_or(CheckInError::FlightNotReserved)?
+            .departure;
+
+        let (_, closes_at) = check_in_window(departure);
+        if now >= closes_at {
+            return Err(CheckInError::CheckInClosed);
+        }
+
+        let boarding = reservation.boarding.iter_mut()
+            .filter(|boarding| &boarding.flight == flight)
+            .find(|boarding| boarding.boarding_pass().map(|pass| pass.verify(code).is_ok()).unwrap_or(false))
+            .ok_or(CheckInError::BoardingPassDontMatch)?;
+
+        let boarding_pass = match &boarding.status {
+            BoardingStatus::CheckedIn(pass) => pass.clone(),
+            BoardingStatus::Boarded(_, _) => return Err(CheckInError::AlreadyBoarded),
+            BoardingStatus::NoShow => return Err(CheckInError::NoShow),
+        };
+        boarding.status = BoardingStatus::Boarded(boarding_pass, now);
+
+        let event = PassengerBoardedV1 {
+            reservation: reservation.id,
+            flight: flight.clone(),
+            passenger: boarding.passenger.clone(),
+            time: now,
+        };
+
+        Ok((Self::Confirmed(reservation), event))
+    }
+
+    /// listed passengers that did not board are no-shows, once the flight departed
+    pub fn close_boarding(self, flight: &FlightId, now: DateTime<Utc>) -> Result<(Self, Option<PassengersNoShowV1>), CheckInError> {
+        let reservation = match self {
+            Self::Confirmed(reservation) => reservation,
+            other => return Ok((other, None)),
+        };
+
+        let Some(departure) = reservation.reserved_flight(flight).map(|flight| flight.departure) else {
+            return Ok((Self::Confirmed(reservation), None));
+        };
+        if now < departure.with_timezone(&Utc) {
+            return Err(CheckInError::FlightNotDeparted);
+        }
+
+        let Passengers::List(listed) = &reservation.passengers else {
+            return Ok((Self::Confirmed(reservation), None));
+        };
+
+        let no_shows: Vec<PersonFullName> = listed.iter()
+            .map(|passenger| passenger.name().clone())
+            .filter(|name| {
+                !reservation.boarding.iter().any(|boarding| {
+                    &boarding.flight == flight && &boarding.passenger == name
+                        && matches!(boarding.status, BoardingStatus::Boarded(_, _) | BoardingStatus::NoShow)
+                })
+            })
+            .collect();
+
+        if no_shows.is_empty() {
+            return Ok((Self::Confirmed(reservation), None));
+        }
+
+        let boarding = reservation.boarding.iter()
+            .filter(|boarding| &boarding.flight != flight || !no_shows.contains(&boarding.passenger))
+            .cloned()
+            .chain(no_shows.iter().map(|passenger| PassengerBoarding {
+                flight: flight.clone(),
+                passenger: passenger.clone(),
+                status: BoardingStatus::NoShow,
+            }))
+            .collect();
+
+        let event = PassengersNoShowV1 {
+            reservation: reservation.id,
+            flight: flight.clone(),
+            passengers: no_shows,
+        };
+
+        let reservation = Self::Confirmed(ConfirmedReservation {
+            boarding,
+            ..reservation
+        });
+
+        Ok((reservation, Some(event)))
+    }
+
+    fn confirmed_for_boarding(self) -> Result<ConfirmedReservation, CheckInError> {
         match self {
+            Self::Confirmed(reservation) => Ok(reservation),
+            Self::Held(_) => Err(CheckInError::ReservationIsHeld),
+            Self::Cancelled(_) => Err(CheckInError::ReservationIsCancelled),
+        }
+    }
+
+    pub fn set_contact_phone(self, phone: PhoneNumber) -> Self {
+        let (reservation, hold) = self.unhold();
+        match reservation {
             Self::Confirmed(reservation) => {
                 Self::Confirmed(ConfirmedReservation {
                     contact: reservation.contact.set_phone(phone),
@@ -199,7 +775,9 @@ impl Reservation {
                     ..reservation
                 })
             }
-        }
+
+            held@Self::Held(_) => held,
+        }.hold_until(hold)
     }
 
     pub fn mark_flight_as_reserved(self, flight: &FlightId, version: Version) -> Self {
@@ -214,11 +792,86 @@ impl Reservation {
         self.mark_flight(flight, version, |itinerary| itinerary.mark_flight_as_annulled(flight))
     }
 
+    /// the reservation of the flight failed as it's cancelled, applies to the requesting version only
+    pub fn mark_flight_as_cancelled(self, flight: &FlightId, version: Version) -> Self {
+        self.mark_flight(flight, version, |itinerary| itinerary.mark_flight_as_disrupted(flight, &ItineraryStageDisruption::FlightCancelled))
+    }
+
+    /// disruptions apply to the flight itself, hence the current itinerary is marked regardless of version
+    pub fn mark_flight_as_disrupted(self, flight: &FlightId, reason: &ItineraryStageDisruption) -> Self {
+        let (reservation, hold) = self.unhold();
+        match reservation {
+            Self::Confirmed(reservation) => {
+                Self::Confirmed(ConfirmedReservation {
+                    itinerary: reservation.itinerary.mark_flight_as_disrupted(flight, reason),
+                    ..reservation
+                })
+            }
+
+            other => {
+                other
+            }
+        }.hold_until(hold)
+    }
+
+    /// replaces the copy of a rescheduled flight in every stage, of the current itinerary and its revisions;
+    /// if the current itinerary no longer satisfies the journey, the stage of the flight is marked as disrupted
+    pub fn reschedule_flight(self, flight: &Flight, journey: &Journey) -> Self {
+        let reschedule_revisions = |revisions: Vec<Revision>| revisions.into_iter()
+            .map(|(version, passengers, itinerary)| (version, passengers, itinerary.reschedule_flight(flight)))
+            .collect();
+
+        let (reservation, hold) = self.unhold();
+        match reservation {
+            Self::Confirmed(reservation) => {
+                let itinerary = reservation.itinerary.reschedule_flight(flight);
+                let itinerary = match journey.validate_itinerary(&itinerary) {
+                    Ok(_) => itinerary,
+                    Err(_) => itinerary.mark_flight_as_disrupted(&flight.id, &ItineraryStageDisruption::FlightRescheduled)
+                };
+
+                Self::Confirmed(ConfirmedReservation {
+                    itinerary,
+                    revisions: reschedule_revisions(reservation.revisions),
+                    ..reservation
+                })
+            }
+
+            Self::Cancelled(reservation) => {
+                Self::Cancelled(CancelledReservation {
+                    revisions: reschedule_revisions(reservation.revisions),
+                    ..reservation
+                })
+            }
+
+            held@Self::Held(_) => {
+                held
+            }
+        }.hold_until(hold)
+    }
+
+    /// the current itinerary holds a copy of the flight from before it was rescheduled
+    pub fn holds_outdated_flight(&self, flight: &Flight) -> bool {
+        match self {
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => {
+                reservation.itinerary.stages()
+                    .filter(|stage| stage.flight() == flight.id)
+                    .any(|stage| {
+                        let (copy, _) = stage.clone().flight_and_accommodation();
+                        copy.departure != flight.departure || copy.arrival != flight.arrival
+                    })
+            }
+
+            Self::Cancelled(_) => false,
+        }
+    }
+
     fn mark_flight<F>(self, flight: &FlightId, version: Version, mark: F) -> Self
         where F: Fn(Itinerary) -> Itinerary {
         let current_version = self.version();
+        let (reservation, hold) = self.unhold();
 
-        match self {
+        match reservation {
             Self::Confirmed(mut reservation) => {
                 if current_version == version {
                     reservation.itinerary = mark(reservation.itinerary);
@@ -250,12 +903,31 @@ impl Reservation {
 
                 Self::Cancelled(reservation)
             }
+
+            held@Self::Held(_) => {
+                held
+            }
+        }.hold_until(hold)
+    }
+
+    /// a held reservation changes as a confirmed one, see hold_until to hold it again
+    fn unhold(self) -> (Self, Option<DateTime<Utc>>) {
+        match self {
+            Self::Held(held) => (Self::Confirmed(held.reservation), Some(held.expires_at)),
+            other => (other, None),
+        }
+    }
+
+    fn hold_until(self, expires_at: Option<DateTime<Utc>>) -> Self {
+        match (self, expires_at) {
+            (Self::Confirmed(reservation), Some(expires_at)) => Self::Held(HeldReservation { reservation, expires_at }),
+            (other, _) => other,
         }
     }
 
     pub fn version(&self) -> Version {
         match self {
-            Self::Confirmed(reservation) => reservation.revisions.len() as Version,
+            Self::Held(HeldReservation { reservation, .. }) | Self::Confirmed(reservation) => reservation.revisions.len() as Version,
             Self::Cancelled(reservation) => reservation.revisions.len() as Version
         }
     }
@@ -274,7 +946,58 @@ pub struct ConfirmedReservation {
     pub contact: Contact,
     pub passengers: Passengers,
     pub itinerary: Itinerary,
-    pub revisions: Vec<Revision>
+    pub revisions: Vec<Revision>,
+    #[serde(default)]
+    pub contact_email_verification: Option<EmailVerificationChallenge>,
+    #[serde(default)]
+    pub payments: Payments,
+    /// assigned per flight, revising the reservation requires assigning seats anew
+    #[serde(default)]
+    pub seats: Vec<AssignedSeat>,
+    /// per passenger per flight, from check-in onwards; revising the reservation requires checking in anew
+    #[serde(default)]
+    pub boarding: Vec<PassengerBoarding>,
+    /// the journey's, as of confirmation or the latest itinerary revision
+    #[serde(default)]
+    pub pricing_policy: Option<PricingPolicy>,
+}
+
+impl ConfirmedReservation {
+    /// as copied into the current itinerary, e.g. with its rescheduled departure
+    fn reserved_flight(&self, flight: &FlightId) -> Option<&Flight> {
+        self.itinerary.stages()
+            .find_map(|stage| match stage {
+                ItineraryStage::Reserved(reserved, _) if &reserved.id == flight => Some(reserved),
+                _ => None,
+            })
+    }
+
+    /// the current itinerary becomes a revision, such that its flights are annulled
+    fn cancel(self, time: DateTime<Utc>) -> CancelledReservation {
+        CancelledReservation {
+            id: self.id,
+            journey: self.journey,
+            contact: self.contact,
+            contact_email_verification: self.contact_email_verification,
+            payments: self.payments,
+            seats: self.seats,
+            pricing_policy: self.pricing_policy,
+            released: false,
+            revisions: [
+                vec!((self.revisions.len() as Version, self.passengers, self.itinerary)),
+                self.revisions
+            ].concat(),
+            time,
+        }
+    }
+}
+
+/// seats are reserved provisionally, until confirmed or expired
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HeldReservation {
+    #[serde(flatten)]
+    pub reservation: ConfirmedReservation,
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -283,7 +1006,19 @@ pub struct CancelledReservation {
     pub journey: JourneyId,
     pub contact: Contact,
     pub time: DateTime<Utc>,
-    pub revisions: Vec<Revision>
+    pub revisions: Vec<Revision>,
+    #[serde(default)]
+    pub contact_email_verification: Option<EmailVerificationChallenge>,
+    #[serde(default)]
+    pub payments: Payments,
+    /// as assigned when cancelled, chosen seats are priced still
+    #[serde(default)]
+    pub seats: Vec<AssignedSeat>,
+    #[serde(default)]
+    pub pricing_policy: Option<PricingPolicy>,
+    /// an expired hold, which was never confirmed
+    #[serde(default)]
+    pub released: bool,
 }
 
 pub type Revision = (Version, Passengers, Itinerary);
@@ -292,16 +1027,18 @@ pub type Revision = (Version, Passengers, Itinerary);
 #[cfg(test)]
 mod tests {
     use std::collections::LinkedList;
+    use chrono::{DateTime, Duration, Utc};
     use prelude::collection::SortedSet;
-    use crate::aggregate::{Contact, Flight, FlightId, FlightRoute, Itinerary, ItineraryStage, ItineraryStageError, Journey, JourneyId, PassengerArrangement, Passengers, PhoneNumber, Reservation, ReservationId, Segment};
-    use crate::policy::ReservationPolicy;
+    use prelude::data::{Currency, Money};
+    use crate::aggregate::{BoardingStatus, CheckInError, Contact, EmailVerificationError, EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS, Flight, FlightId, FlightRoute, Itinerary, ItineraryStage, ItineraryStageDisruption, ItineraryStageError, Journey, JourneyShape, JourneyId, Passenger, PassengerArrangement, Passengers, Payment, PaymentError, PhoneNumber, Reservation, ReservationId, Segment};
+    use crate::policy::{ReservationPolicy, ReservationPolicyError};
 
     #[test]
     fn can_create_new_confirmed_reservation() {
         let reservation = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -314,7 +1051,7 @@ mod tests {
         let (reservation1, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -322,7 +1059,7 @@ mod tests {
         let (reservation2, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary2()
@@ -332,7 +1069,7 @@ mod tests {
         let (reservation3, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id2(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -345,7 +1082,7 @@ mod tests {
         let (original, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -362,7 +1099,7 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -380,6 +1117,7 @@ mod tests {
         // change itinerary
         let (reservation, event) = reservation.revise_itinerary(
             &ReservationPolicy::test(),
+            &journey(),
             itinerary2()
         ).unwrap();
         assert_eq!(reservation.version(), 2);
@@ -393,12 +1131,74 @@ mod tests {
         assert!(event.is_some())
     }
 
+    #[test]
+    fn held_reservation_can_be_confirmed() {
+        let (reservation, held) = Reservation::new_held(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+        assert!(matches!(reservation, Reservation::Held(_)));
+
+        // survives serialization
+        let serialized = serde_json::to_string(&reservation).unwrap();
+        let reservation: Reservation = serde_json::from_str(&serialized).unwrap();
+
+        let result = reservation.clone().revise_passengers(&ReservationPolicy::test(), passengers2());
+        assert_eq!(result.err(), Some(ReservationPolicyError::ReservationIsHeld));
+
+        // releasing before expiry has no effect
+        let (reservation, released) = reservation.release_hold(held.expires_at - Duration::seconds(1));
+        assert!(released.is_none());
+
+        let (reservation, confirmed) = reservation.confirm_hold(&ReservationPolicy::test()).unwrap();
+        assert!(matches!(reservation, Reservation::Confirmed(_)));
+        assert!(confirmed.is_some());
+
+        let (reservation, confirmed) = reservation.confirm_hold(&ReservationPolicy::test()).unwrap();
+        assert!(matches!(reservation, Reservation::Confirmed(_)));
+        assert!(confirmed.is_none());
+    }
+
+    #[test]
+    fn expired_hold_is_released() {
+        let (reservation, held) = Reservation::new_held(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+        assert!(reservation.is_expired_hold(held.expires_at));
+
+        let result = ReservationPolicy::test().confirm_hold(reservation.clone(), held.expires_at, |reservation| reservation);
+        assert_eq!(result.err(), Some(ReservationPolicyError::HoldExpired));
+
+        // nor paid, which would confirm it
+        let result = reservation.clone().record_payment(Money::usd(10000), Payment {
+            time: held.expires_at,
+            ..payment(1000)
+        });
+        assert_eq!(result.err(), Some(PaymentError::HoldExpired));
+
+        let (reservation, released) = reservation.release_hold(held.expires_at);
+        assert!(matches!(reservation, Reservation::Cancelled(_)));
+        assert!(released.is_some());
+
+        let result = reservation.confirm_hold(&ReservationPolicy::test());
+        assert_eq!(result.err(), Some(ReservationPolicyError::ReservationIsAlreadyCancelled));
+    }
+
     #[test]
     fn can_revise_passengers() {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -420,7 +1220,7 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -428,6 +1228,7 @@ mod tests {
 
         let (reservation, _) = reservation.revise_itinerary(
             &ReservationPolicy::test(),
+            &journey(),
             itinerary2()
         ).unwrap();
 
@@ -442,7 +1243,7 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -452,10 +1253,7 @@ mod tests {
             &ReservationPolicy::test(),
         ).unwrap();
 
-        assert!(match reservation {
-            Reservation::Cancelled(_) => true,
-            _ => false
-        });
+        assert!(matches!(reservation, Reservation::Cancelled(_)));
     }
 
     #[test]
@@ -463,23 +1261,175 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
         ).unwrap();
 
-        let challenge = reservation.contact_email_verify_challenge();
-        assert_eq!(challenge, Some("9531101938472543805".to_owned()));
+        let (reservation, requested) = reservation.request_contact_email_verification(now()).unwrap();
+        assert_eq!(requested.email, contact().email);
 
-        let result = reservation.verify_contact_email("9531101938472543805".to_owned());
-        assert!(result.is_ok());
+        let result = reservation.clone().verify_contact_email("0000", now());
+        assert_eq!(result.err(), Some(EmailVerificationError::ChallengeDontMatch));
 
-        let reservation = result.unwrap();
-        assert!(match reservation {
-            Reservation::Confirmed(confirmed) => confirmed.contact.email_is_verified(),
-            _ => false
-        });
+        let result = reservation.clone().verify_contact_email(&requested.challenge, now() + Duration::hours(24));
+        assert_eq!(result.err(), Some(EmailVerificationError::ChallengeExpired));
+
+        let (reservation, verified) = reservation.verify_contact_email(&requested.challenge, now()).unwrap();
+        assert!(reservation.contact().email_is_verified());
+        assert!(verified.is_some());
+
+        // verifying again has no effect, requesting again is pointless
+        let (reservation, verified) = reservation.verify_contact_email(&requested.challenge, now()).unwrap();
+        assert!(verified.is_none());
+        assert_eq!(reservation.request_contact_email_verification(now()).err(), Some(EmailVerificationError::AlreadyVerified));
+    }
+
+    #[test]
+    fn errors_on_verifying_contact_email_after_too_many_failed_attempts() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (mut reservation, requested) = reservation.request_contact_email_verification(now()).unwrap();
+        for _ in 0..EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS {
+            reservation = reservation.fail_contact_email_verification();
+        }
+
+        let result = reservation.clone().verify_contact_email(&requested.challenge, now());
+        assert_eq!(result.err(), Some(EmailVerificationError::TooManyFailedAttempts));
+
+        // a new challenge may be requested
+        let (reservation, requested) = reservation.request_contact_email_verification(now()).unwrap();
+        let (reservation, _) = reservation.verify_contact_email(&requested.challenge, now()).unwrap();
+        assert!(reservation.contact().email_is_verified());
+    }
+
+    #[test]
+    fn errors_on_verifying_contact_email_without_challenge() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let result = reservation.verify_contact_email("0000", now());
+        assert_eq!(result.err(), Some(EmailVerificationError::NoChallengeIssued));
+    }
+
+    #[test]
+    fn can_revise_contact() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, revised) = reservation.revise_contact(&ReservationPolicy::test(), contact()).unwrap();
+        assert!(revised.is_none());
+
+        let (reservation, revised) = reservation.revise_contact(&ReservationPolicy::test(), contact().set_phone(phone())).unwrap();
+        assert_eq!(revised.unwrap().contact, contact().set_phone(phone()));
+        assert!(reservation.contact().phone_is_present());
+    }
+
+    #[test]
+    fn revised_contact_email_needs_verification() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, requested) = reservation.request_contact_email_verification(now()).unwrap();
+        let (reservation, _) = reservation.verify_contact_email(&requested.challenge, now()).unwrap();
+
+        // same address (in other case) stays verified
+        let same = Contact::new("Niels Bergsma".parse().unwrap(), "N.Bergsma@internet.com".parse().unwrap(), None);
+        let (reservation, revised) = reservation.revise_contact(&ReservationPolicy::test(), same).unwrap();
+        assert!(revised.is_none());
+        assert!(reservation.contact().email_is_verified());
+
+        let other = Contact::new("Niels Bergsma".parse().unwrap(), "n.bergsma@example.com".parse().unwrap(), None);
+        let (reservation, revised) = reservation.revise_contact(&ReservationPolicy::test(), other.clone().verify_email()).unwrap();
+        assert_eq!(revised.unwrap().contact, other);
+        assert!(!reservation.contact().email_is_verified());
+    }
+
+    #[test]
+    fn errors_on_revising_contact_of_cancelled_reservation() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, _) = reservation.cancel(&ReservationPolicy::test()).unwrap();
+        let result = reservation.revise_contact(&ReservationPolicy::test(), contact().set_phone(phone()));
+        assert_eq!(result.err(), Some(ReservationPolicyError::ReservationIsAlreadyCancelled));
+    }
+
+    #[test]
+    fn payments_are_limited_to_balance_of_confirmed_reservation() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, event) = reservation.record_payment(Money::usd(10000), payment(6000)).unwrap();
+        assert_eq!(event.amount, Money::usd(6000));
+        assert_eq!(reservation.balance(Money::usd(10000)), Ok(Money::usd(4000)));
+
+        let result = reservation.clone().record_payment(Money::usd(10000), payment(4001));
+        assert_eq!(result.err(), Some(PaymentError::AmountExceedsBalance));
+
+        // e.g. 0.004 USD can't be charged
+        let result = reservation.clone().validate_payment(Money::usd(10000), Money::parse("0.004", Currency::USD).unwrap(), Utc::now());
+        assert_eq!(result.err(), Some(PaymentError::AmountFinerThanMinorUnit));
+
+        let (reservation, _) = reservation.cancel(&ReservationPolicy::test()).unwrap();
+        let result = reservation.record_payment(Money::usd(10000), payment(1000));
+        assert_eq!(result.err(), Some(PaymentError::ReservationIsCancelled));
+    }
+
+    #[test]
+    fn refunds_are_due_when_received_exceeds_amount_due() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, _) = reservation.record_payment(Money::usd(10000), payment(10000)).unwrap();
+        assert_eq!(reservation.refunds_due(Money::usd(10000)), Ok(vec![]));
+
+        let refunds = reservation.refunds_due(Money::usd(2500)).unwrap();
+        assert_eq!(refunds, vec![("pay_1".parse().unwrap(), Money::usd(7500))]);
     }
 
     #[test]
@@ -487,7 +1437,7 @@ mod tests {
         let (before, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -505,12 +1455,70 @@ mod tests {
         });
     }
 
+    #[test]
+    fn passengers_check_in_within_window() {
+        let reservation = listed_reservation();
+
+        let result = reservation.clone().check_in(&flight_id_eham_enli_8may(), vec![], "2024-05-06T03:00:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::CheckInNotOpen));
+
+        let result = reservation.clone().check_in(&flight_id_enli_eham_14may(), vec![], "2024-05-07T09:00:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::FlightNotReserved));
+
+        let result = reservation.clone().check_in(&flight_id_eham_enli_8may(), vec!["Jim Doe".parse().unwrap()], "2024-05-07T09:00:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::UnknownPassenger("Jim Doe".to_owned())));
+
+        let (reservation, boarding_passes, event) = reservation.check_in(&flight_id_eham_enli_8may(), vec![], "2024-05-07T09:00:00Z".parse().unwrap()).unwrap();
+        assert_eq!(boarding_passes.len(), 2);
+        assert_eq!(event.unwrap().passengers.len(), 2);
+
+        // is idempotent
+        let (reservation, boarding_passes2, event) = reservation.check_in(&flight_id_eham_enli_8may(), vec!["Jane Doe".parse().unwrap()], "2024-05-07T10:00:00Z".parse().unwrap()).unwrap();
+        assert_eq!(boarding_passes2, vec![boarding_passes[0].clone()]);
+        assert!(event.is_none());
+
+        let result = reservation.check_in(&flight_id_eham_enli_8may(), vec![], "2024-05-08T04:00:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::CheckInClosed));
+    }
+
+    #[test]
+    fn passengers_not_boarded_at_departure_are_no_shows() {
+        let (reservation, boarding_passes, _) = listed_reservation()
+            .check_in(&flight_id_eham_enli_8may(), vec!["Jane Doe".parse().unwrap()], "2024-05-07T09:00:00Z".parse().unwrap())
+            .unwrap();
+        let code = boarding_passes[0].code();
+
+        let result = reservation.clone().board(&flight_id_eham_enli_8may(), "0000", "2024-05-08T03:30:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::BoardingPassDontMatch));
+
+        let (reservation, event) = reservation.board(&flight_id_eham_enli_8may(), code, "2024-05-08T03:30:00Z".parse().unwrap()).unwrap();
+        assert_eq!(event.passenger.to_string(), "Jane Doe");
+
+        let result = reservation.clone().board(&flight_id_eham_enli_8may(), code, "2024-05-08T03:35:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::AlreadyBoarded));
+
+        let result = reservation.clone().close_boarding(&flight_id_eham_enli_8may(), "2024-05-08T03:59:00Z".parse().unwrap());
+        assert_eq!(result.err(), Some(CheckInError::FlightNotDeparted));
+
+        let (reservation, event) = reservation.close_boarding(&flight_id_eham_enli_8may(), "2024-05-08T04:00:00Z".parse().unwrap()).unwrap();
+        assert_eq!(event.unwrap().passengers, vec!["John Doe".parse().unwrap()]);
+
+        let statuses: Vec<_> = reservation.boarding().iter()
+            .map(|boarding| (boarding.passenger.to_string(), matches!(boarding.status, BoardingStatus::NoShow)))
+            .collect();
+        assert_eq!(statuses, vec![("Jane Doe".to_owned(), false), ("John Doe".to_owned(), true)]);
+
+        // is idempotent
+        let (_, event) = reservation.close_boarding(&flight_id_eham_enli_8may(), "2024-05-08T05:00:00Z".parse().unwrap()).unwrap();
+        assert!(event.is_none());
+    }
+
     #[test]
     fn can_reserve_flight_in_itinerary() {
         let (before, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -522,10 +1530,7 @@ mod tests {
 
         assert!(match &after {
             Reservation::Confirmed(confirmed) =>
-                match confirmed.itinerary.first_stage() {
-                    ItineraryStage::Reserved(_, _) => true,
-                    _ => false
-                }
+                matches!(confirmed.itinerary.first_stage(), ItineraryStage::Reserved(_, _)),
             _ => false
         });
 
@@ -536,10 +1541,7 @@ mod tests {
 
         assert!(match &after2 {
             Reservation::Confirmed(confirmed) =>
-                match confirmed.itinerary.first_stage() {
-                    ItineraryStage::Reserved(_, _) => true,
-                    _ => false
-                }
+                matches!(confirmed.itinerary.first_stage(), ItineraryStage::Reserved(_, _)),
             _ => false
         });
     }
@@ -549,7 +1551,7 @@ mod tests {
         let (before, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -561,10 +1563,7 @@ mod tests {
 
         assert!(match &after {
             Reservation::Confirmed(confirmed) =>
-                match confirmed.itinerary.first_stage() {
-                    ItineraryStage::Planned(_, _) => true,
-                    _ => false
-                }
+                matches!(confirmed.itinerary.first_stage(), ItineraryStage::Planned(_, _)),
             _ => false
         });
     }
@@ -574,7 +1573,7 @@ mod tests {
         let (before, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary()
@@ -608,6 +1607,119 @@ mod tests {
         });
     }
 
+    #[test]
+    fn skip_cancelled_flights_for_other_versions() {
+        let (before, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        // late failure of an earlier revision
+        let after = before.mark_flight_as_cancelled(
+            &flight_id_eham_enli_8may(), 99
+        );
+
+        assert!(match &after {
+            Reservation::Confirmed(confirmed) =>
+                matches!(confirmed.itinerary.first_stage(), ItineraryStage::Planned(_, _)),
+            _ => false
+        });
+
+        let after = after.mark_flight_as_cancelled(
+            &flight_id_eham_enli_8may(), 0
+        );
+
+        assert!(match &after {
+            Reservation::Confirmed(confirmed) =>
+                matches!(confirmed.itinerary.first_stage(), ItineraryStage::Disrupted(_, _, ItineraryStageDisruption::FlightCancelled)),
+            _ => false
+        });
+    }
+
+    #[test]
+    fn can_reschedule_flight_in_itinerary() {
+        let (before, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+        let before = before.mark_flight_as_reserved(&flight_id_eham_enli_8may(), 0);
+
+        // still a valid itinerary, stage keeps its state
+        let flight = flight_eham_enli_8may().reschedule(
+            "2024-05-09T09:00:00+05:00".parse().unwrap(),
+            "2024-05-09T11:00:00+05:00".parse().unwrap(),
+        ).unwrap();
+        let after = before.reschedule_flight(&flight, &journey());
+
+        assert!(match &after {
+            Reservation::Confirmed(confirmed) =>
+                match confirmed.itinerary.first_stage() {
+                    ItineraryStage::Reserved(reserved, _) => reserved.departure == flight.departure,
+                    _ => false
+                }
+            _ => false
+        });
+
+        // departs after return flight, stage is disrupted
+        let flight = flight_eham_enli_8may().reschedule(
+            "2024-05-15T09:00:00+05:00".parse().unwrap(),
+            "2024-05-15T11:00:00+05:00".parse().unwrap(),
+        ).unwrap();
+        let after = after.reschedule_flight(&flight, &journey());
+
+        assert!(match &after {
+            Reservation::Confirmed(confirmed) =>
+                match confirmed.itinerary.first_stage() {
+                    ItineraryStage::Disrupted(disrupted, _, reason) =>
+                        disrupted.departure == flight.departure && reason == &ItineraryStageDisruption::FlightRescheduled,
+                    _ => false
+                }
+            _ => false
+        });
+    }
+
+    #[test]
+    fn reschedule_flight_replaces_copies_in_revisions() {
+        let (before, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+        let (before, _) = before.revise_passengers(&ReservationPolicy::test(), passengers2()).unwrap();
+
+        let flight = flight_eham_enli_8may().reschedule(
+            "2024-05-09T09:00:00+05:00".parse().unwrap(),
+            "2024-05-09T11:00:00+05:00".parse().unwrap(),
+        ).unwrap();
+        assert!(before.holds_outdated_flight(&flight));
+
+        let after = before.reschedule_flight(&flight, &journey());
+        assert!(!after.holds_outdated_flight(&flight));
+
+        assert!(match &after {
+            Reservation::Confirmed(confirmed) =>
+                confirmed.revisions.iter().all(|(_, _, itinerary)|
+                    matches!(itinerary.first_stage(), ItineraryStage::Planned(planned, _) if planned.departure == flight.departure)
+                ),
+            _ => false
+        });
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2024-01-08T09:00:00Z".parse().unwrap()
+    }
+
     fn id() -> ReservationId {
         "5FFFciXgSxB70tAE8iERl6".parse().unwrap()
     }
@@ -652,6 +1764,28 @@ mod tests {
         Passengers::Arrangement(PassengerArrangement::build(4, 0).unwrap())
     }
 
+    fn listed_reservation() -> Reservation {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary()
+        ).unwrap();
+
+        let (reservation, _) = reservation.revise_passengers(
+            &ReservationPolicy::test(),
+            Passengers::List(SortedSet::from_vec(vec![
+                Passenger::new("Jane Doe".parse().unwrap(), "1980-01-01".parse().unwrap()),
+                Passenger::new("John Doe".parse().unwrap(), "1980-01-01".parse().unwrap()),
+            ]))
+        ).unwrap();
+
+        let version = reservation.version();
+        reservation.mark_flight_as_reserved(&flight_id_eham_enli_8may(), version)
+    }
+
     fn journey_id() -> JourneyId {
         "5EPFciXgSxB70tAE8iERl6".parse().unwrap()
     }
@@ -660,6 +1794,7 @@ mod tests {
         let (journey, _) = Journey::build(
             journey_id(),
             "Journey Around North Atlantic".parse().unwrap(),
+            JourneyShape::RoundTrip,
             SortedSet::empty()
                 .insert(Segment::build(route_eham_enli(), SortedSet::empty()).unwrap())
                 .insert(Segment::build(route_enli_eham(), SortedSet::empty()).unwrap())
@@ -720,6 +1855,14 @@ mod tests {
         ).unwrap()
     }
 
+    fn payment(cents: i64) -> Payment {
+        Payment {
+            reference: "pay_1".parse().unwrap(),
+            amount: Money::usd(cents),
+            time: "2024-01-08T09:00:00Z".parse().unwrap(),
+        }
+    }
+
     fn phone() -> PhoneNumber {
         "+31653321799".parse().unwrap()
     }
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::aggregate::{AirshipModel, Flight, FlightId, PersonFullName, SeatNumber};
+
+/// seats abreast in the gondola per airship model, the outer ones by the windows
+const SEAT_LAYOUTS: &[(&str, &[char])] = &[
+    ("H2 Clipper", &['A', 'B', 'C', 'D', 'E', 'F']),
+    ("Airlander 10", &['A', 'B', 'C', 'D']),
+    ("Zeppelin NT", &['A', 'B', 'C']),
+];
+
+/// for unknown models, e.g. of flights scheduled before the model was carried
+const DEFAULT_SEAT_LAYOUT: &[char] = &['A', 'B', 'C', 'D'];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SeatAssignmentError {
+    #[error("flight is cancelled")]
+    FlightCancelled,
+
+    #[error("flight is not reserved")]
+    FlightNotReserved,
+
+    #[error("passengers are not listed by name")]
+    PassengersNotListed,
+
+    #[error("unknown passenger: {0}")]
+    UnknownPassenger(String),
+
+    #[error("more passengers than seats reserved")]
+    InsufficientSeatsReserved,
+
+    #[error("unknown seat: {0}")]
+    UnknownSeat(SeatNumber),
+
+    #[error("seat is taken: {0}")]
+    SeatTaken(SeatNumber),
+
+    #[error("no seat available")]
+    NoSeatAvailable,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Seat {
+    pub number: SeatNumber,
+    pub window: bool,
+}
+
+/// layout of the seats on board, rows of the airship model's layout up to the number of seats of the flight
+#[derive(PartialEq, Debug, Clone)]
+pub struct SeatMap(Vec<Seat>);
+
+impl SeatMap {
+    pub fn from_flight(flight: &Flight) -> Self {
+        let letters = flight.model.as_ref()
+            .and_then(Self::layout)
+            .unwrap_or(DEFAULT_SEAT_LAYOUT);
+
+        let seats = (0..flight.seats as usize)
+            .map(|i| {
+                let row = (i / letters.len()) as u16 + 1;
+                let position = i % letters.len();
+
+                Seat {
+                    number: SeatNumber::new(row, letters[position]),
+                    window: position == 0 || position == letters.len() - 1,
+                }
+            })
+            .collect();
+
+        Self(seats)
+    }
+
+    fn layout(model: &AirshipModel) -> Option<&'static [char]> {
+        SEAT_LAYOUTS.iter()
+            .find(|(name, _)| *name == model.as_str())
+            .map(|(_, letters)| *letters)
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.0
+    }
+
+    pub fn find(&self, number: &SeatNumber) -> Option<&Seat> {
+        self.0.iter().find(|seat| &seat.number == number)
+    }
+}
+
+/// passenger for whom a seat is chosen, or assigned automatically
+#[derive(PartialEq, Debug, Clone)]
+pub struct SeatRequest {
+    pub passenger: PersonFullName,
+    pub seat: Option<SeatNumber>,
+}
+
+/// seat of a passenger on a flight, as recorded by the reservation
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AssignedSeat {
+    pub flight: FlightId,
+    pub passenger: PersonFullName,
+    pub seat: SeatNumber,
+    pub window: bool,
+    /// chosen by the customer, rather than assigned automatically
+    pub chosen: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{Flight, FlightRoute, SeatMap};
+
+    #[test]
+    fn layout_follows_the_airship_model() {
+        let map = SeatMap::from_flight(&flight(5).with_model("Zeppelin NT".parse().unwrap()));
+        let seats: Vec<_> = map.seats().iter()
+            .map(|seat| (seat.number.to_string(), seat.window))
+            .collect();
+
+        assert_eq!(seats, vec![
+            ("1A".to_owned(), true),
+            ("1B".to_owned(), false),
+            ("1C".to_owned(), true),
+            ("2A".to_owned(), true),
+            ("2B".to_owned(), false),
+        ]);
+    }
+
+    #[test]
+    fn unknown_models_seat_four_abreast() {
+        let map = SeatMap::from_flight(&flight(20).with_model("Hindenburg".parse().unwrap()));
+        assert_eq!(map, SeatMap::from_flight(&flight(20)));
+        assert_eq!(map.seats().len(), 20);
+
+        let row: Vec<_> = map.seats()[4..8].iter()
+            .map(|seat| (seat.number.to_string(), seat.window))
+            .collect();
+
+        assert_eq!(row, vec![
+            ("2A".to_owned(), true),
+            ("2B".to_owned(), false),
+            ("2C".to_owned(), false),
+            ("2D".to_owned(), true),
+        ]);
+        assert!(map.find(&"5D".parse().unwrap()).is_some());
+        assert!(map.find(&"6A".parse().unwrap()).is_none());
+    }
+
+    // test data
+    fn flight(seats: u8) -> Flight {
+        Flight::build(
+            "5EPFciXgSxB70tAE8iERl6".to_owned(),
+            FlightRoute::build("EHAM".parse().unwrap(), "ENLI".parse().unwrap()).unwrap(),
+            "2024-01-08T09:00:00+05:00".parse().unwrap(),
+            "2024-01-08T11:00:00+05:00".parse().unwrap(),
+            seats,
+        ).unwrap()
+    }
+}
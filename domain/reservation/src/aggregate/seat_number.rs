@@ -0,0 +1,78 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::parse::{Parser, capital, end, is_a, one_of};
+
+/// row followed by the letter of the seat within the row, e.g. 12A
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SeatNumber(String);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SeatNumberError {
+    #[error("malformed value")]
+    MalformedValue,
+}
+
+impl SeatNumber {
+    pub(crate) fn new(row: u16, letter: char) -> Self {
+        Self([row.to_string(), letter.to_string()].concat())
+    }
+
+    fn parser<'a>() -> Parser<'a, &'a str> {
+        (one_of("123456789")
+            + is_a(|c: char| c.is_ascii_digit()).repeat(0..3)
+            + capital()
+            + end::<char>()
+        ).collect()
+    }
+}
+
+impl FromStr for SeatNumber {
+    type Err = SeatNumberError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parsed = Self::parser()
+            .parse_str(input)
+            .map_err(|_| SeatNumberError::MalformedValue)?
+            .to_owned();
+
+        Ok(Self(parsed))
+    }
+}
+
+impl Display for SeatNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::seat_number::{SeatNumber, SeatNumberError};
+
+    #[test]
+    fn is_parseable() {
+        let result: Result<SeatNumber, SeatNumberError> = "12A".parse();
+        assert_eq!(result, Ok(SeatNumber::new(12, 'A')));
+    }
+
+    #[test]
+    fn errors_on_malformed_input() {
+        // no row
+        let result: Result<SeatNumber, SeatNumberError> = "A".parse();
+        assert_eq!(result, Err(SeatNumberError::MalformedValue));
+
+        // rows start at 1
+        let result: Result<SeatNumber, SeatNumberError> = "0A".parse();
+        assert_eq!(result, Err(SeatNumberError::MalformedValue));
+
+        // uses lowercase letters
+        let result: Result<SeatNumber, SeatNumberError> = "3b".parse();
+        assert_eq!(result, Err(SeatNumberError::MalformedValue));
+
+        // more than one letter
+        let result: Result<SeatNumber, SeatNumberError> = "3AB".parse();
+        assert_eq!(result, Err(SeatNumberError::MalformedValue));
+    }
+}
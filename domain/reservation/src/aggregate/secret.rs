@@ -0,0 +1,22 @@
+/// not to leak the matching prefix of secrets, e.g. verification challenges and check-in codes
+pub(crate) fn equals_in_constant_time(expected: &str, actual: &str) -> bool {
+    let difference = expected.len() ^ actual.len();
+    let difference = expected.bytes()
+        .zip(actual.bytes())
+        .fold(difference, |acc, (a, b)| acc | (a ^ b) as usize);
+
+    difference == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::secret::equals_in_constant_time;
+
+    #[test]
+    fn equals_only_the_same_secret() {
+        assert!(equals_in_constant_time("4f2a9c", "4f2a9c"));
+        assert!(!equals_in_constant_time("4f2a9c", "4f2a9d"));
+        assert!(!equals_in_constant_time("4f2a9c", "4f2a9"));
+        assert!(!equals_in_constant_time("4f2a9c", ""));
+    }
+}
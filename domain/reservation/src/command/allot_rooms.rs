@@ -0,0 +1,30 @@
+use thiserror::Error;
+use crate::aggregate::AccommodationId;
+use crate::repository::AccommodationAvailabilityRepositoryError;
+
+/// sets the rooms a partner accommodation holds for us each night
+pub struct AllotRooms {
+    pub accommodation: AccommodationId,
+    pub rooms: u8,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AllotRoomsError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+pub type AllotRoomsResult = Result<(), AllotRoomsError>;
+
+// transformers
+impl From<AccommodationAvailabilityRepositoryError> for AllotRoomsError {
+    fn from(value: AccommodationAvailabilityRepositoryError) -> Self {
+        match value {
+            AccommodationAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
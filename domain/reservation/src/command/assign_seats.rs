@@ -0,0 +1,78 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{AssignedSeat, FlightId, PersonFullName, ReservationId, SeatAssignmentError, SeatNumber};
+use crate::policy::ReservationPolicyError;
+use crate::repository::{FlightAvailabilityRepositoryError, ReservationRepositoryError};
+
+/// passengers without a chosen seat are assigned one automatically
+pub struct AssignSeats {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub seats: Vec<(PersonFullName, SeatNumber)>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AssignSeatsError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+
+    #[error("version conflict")]
+    VersionConflict,
+
+    #[error("{0}")]
+    SeatAssignmentError(SeatAssignmentError),
+}
+
+pub type AssignSeatsResult = Result<Vec<AssignedSeat>, AssignSeatsError>;
+
+// transformers
+impl From<ReservationRepositoryError> for AssignSeatsError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        match value {
+            ReservationRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<FlightAvailabilityRepositoryError> for AssignSeatsError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        match value {
+            FlightAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<ReservationPolicyError> for AssignSeatsError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<SeatAssignmentError> for AssignSeatsError {
+    fn from(value: SeatAssignmentError) -> Self {
+        Self::SeatAssignmentError(value)
+    }
+}
+
+impl From<EventPublishError> for AssignSeatsError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for AssignSeatsError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{CheckInError, FlightId, PersonFullName, ReservationId};
+use crate::policy::ReservationPolicyError;
+use crate::repository::ReservationRepositoryError;
+
+/// the code of the boarding pass, as scanned at the gate
+pub struct BoardPassenger {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub code: String,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum BoardPassengerError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("{0}")]
+    CheckInError(CheckInError),
+}
+
+pub type BoardPassengerResult = Result<PersonFullName, BoardPassengerError>;
+
+// transformers
+impl From<ReservationRepositoryError> for BoardPassengerError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for BoardPassengerError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<CheckInError> for BoardPassengerError {
+    fn from(value: CheckInError) -> Self {
+        Self::CheckInError(value)
+    }
+}
+
+impl From<EventPublishError> for BoardPassengerError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for BoardPassengerError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
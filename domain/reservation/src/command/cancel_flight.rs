@@ -0,0 +1,44 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::FlightId;
+use crate::repository::FlightAvailabilityRepositoryError;
+
+pub struct CancelFlight {
+    pub flight: FlightId,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CancelFlightError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+pub type CancelFlightResult = Result<(), CancelFlightError>;
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for CancelFlightError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        match value {
+            FlightAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<EventPublishError> for CancelFlightError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for CancelFlightError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{BoardingPass, CheckInError, FlightId, PersonFullName, ReservationId};
+use crate::policy::ReservationPolicyError;
+use crate::repository::ReservationRepositoryError;
+
+/// all listed passengers are checked in when none are named
+pub struct CheckInPassengers {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub passengers: Vec<PersonFullName>,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CheckInPassengersError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("{0}")]
+    CheckInError(CheckInError),
+}
+
+pub type CheckInPassengersResult = Result<Vec<BoardingPass>, CheckInPassengersError>;
+
+// transformers
+impl From<ReservationRepositoryError> for CheckInPassengersError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for CheckInPassengersError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<CheckInError> for CheckInPassengersError {
+    fn from(value: CheckInError) -> Self {
+        Self::CheckInError(value)
+    }
+}
+
+impl From<EventPublishError> for CheckInPassengersError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for CheckInPassengersError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
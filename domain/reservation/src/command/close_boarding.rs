@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{CheckInError, FlightId, ReservationId};
+use crate::repository::{FlightAvailabilityRepositoryError, ReservationRepositoryError};
+
+/// marks passengers that did not board as no-shows, once the flight departed
+pub struct CloseBoarding {
+    pub flight: FlightId,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CloseBoardingError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+
+    #[error("{0}")]
+    CheckInError(CheckInError),
+}
+
+/// reservations with no-shows
+pub type CloseBoardingResult = Result<Vec<ReservationId>, CloseBoardingError>;
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for CloseBoardingError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationRepositoryError> for CloseBoardingError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<CheckInError> for CloseBoardingError {
+    fn from(value: CheckInError) -> Self {
+        Self::CheckInError(value)
+    }
+}
+
+impl From<EventPublishError> for CloseBoardingError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for CloseBoardingError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
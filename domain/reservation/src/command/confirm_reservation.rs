@@ -26,6 +26,9 @@ pub enum ConfirmReservationError {
     #[error("unknown journey")]
     UnknownJourney,
 
+    #[error("journey is withdrawn")]
+    JourneyWithdrawn,
+
     #[error("other: {0}")]
     OtherError(String),
 }
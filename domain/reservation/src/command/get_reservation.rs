@@ -1,5 +1,6 @@
 use thiserror::Error;
 use crate::aggregate::{Reservation, ReservationId};
+use crate::policy::ReservationPolicyError;
 use crate::repository::ReservationRepositoryError;
 
 pub struct GetReservation {
@@ -13,6 +14,9 @@ pub enum GetReservationError {
 
     #[error("I/O error: {0}")]
     IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
 }
 
 impl From<ReservationRepositoryError> for GetReservationError {
@@ -21,4 +25,10 @@ impl From<ReservationRepositoryError> for GetReservationError {
     }
 }
 
+impl From<ReservationPolicyError> for GetReservationError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
 pub type GetReservationResult = Result<Reservation, GetReservationError>;
\ No newline at end of file
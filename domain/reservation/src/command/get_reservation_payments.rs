@@ -0,0 +1,62 @@
+use thiserror::Error;
+use prelude::data::Money;
+use crate::aggregate::{PaymentError, Payments, ReservationId};
+use crate::policy::ReservationPolicyError;
+use crate::repository::{JourneyRepositoryError, ReservationRepositoryError};
+
+pub struct GetReservationPayments {
+    pub id: ReservationId,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReservationPayments {
+    pub payments: Payments,
+    pub amount_due: Money,
+    /// positive when owed by the contact, negative when to be refunded
+    pub balance: Money,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetReservationPaymentsError {
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("{0}")]
+    PaymentError(PaymentError),
+}
+
+// transformers
+impl From<ReservationRepositoryError> for GetReservationPaymentsError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyRepositoryError> for GetReservationPaymentsError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for GetReservationPaymentsError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<PaymentError> for GetReservationPaymentsError {
+    fn from(value: PaymentError) -> Self {
+        Self::PaymentError(value)
+    }
+}
+
+pub type GetReservationPaymentsResult = Result<ReservationPayments, GetReservationPaymentsError>;
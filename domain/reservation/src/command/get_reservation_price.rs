@@ -0,0 +1,45 @@
+use thiserror::Error;
+use crate::aggregate::ReservationId;
+use crate::policy::ReservationPolicyError;
+use crate::repository::{JourneyRepositoryError, ReservationRepositoryError};
+use crate::services::reservation_pricing_strategy::PriceBreakdown;
+
+pub struct GetReservationPrice {
+    pub id: ReservationId
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetReservationPriceError {
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+}
+
+// transformers
+impl From<ReservationRepositoryError> for GetReservationPriceError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyRepositoryError> for GetReservationPriceError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for GetReservationPriceError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+pub type GetReservationPriceResult = Result<PriceBreakdown, GetReservationPriceError>;
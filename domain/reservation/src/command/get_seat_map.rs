@@ -0,0 +1,26 @@
+use thiserror::Error;
+use crate::aggregate::{FlightId, Seat};
+use crate::repository::FlightAvailabilityRepositoryError;
+
+pub struct GetSeatMap {
+    pub flight: FlightId,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetSeatMapError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+}
+
+/// seats on board, and whether they're taken
+pub type GetSeatMapResult = Result<Vec<(Seat, bool)>, GetSeatMapError>;
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for GetSeatMapError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
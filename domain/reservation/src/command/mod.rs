@@ -4,9 +4,34 @@ mod register_airfield;
 mod confirm_reservation;
 mod reserve_flight;
 mod get_reservation;
+mod get_reservation_price;
+mod quote_reservation;
+mod search_itineraries;
 mod cancel_reservation;
 mod revise_passengers;
 mod revise_itinerary;
+mod revise_contact;
+mod cancel_flight;
+mod reschedule_flight;
+mod request_contact_email_verification;
+mod verify_contact_email;
+mod send_contact_email_verification;
+mod pay_reservation;
+mod refund_reservation;
+mod get_reservation_payments;
+mod hold_reservation;
+mod confirm_held_reservation;
+mod release_expired_holds;
+mod overbook_flight;
+mod reconcile_oversold_flights;
+mod assign_seats;
+mod get_seat_map;
+mod check_in_passengers;
+mod board_passenger;
+mod close_boarding;
+mod revise_journey;
+mod withdraw_journey;
+mod allot_rooms;
 
 use std::collections::LinkedList;
 pub use publish_journey::*;
@@ -15,9 +40,34 @@ pub use register_airfield::*;
 pub use confirm_reservation::*;
 pub use reserve_flight::*;
 pub use get_reservation::*;
+pub use get_reservation_price::*;
+pub use quote_reservation::*;
+pub use search_itineraries::*;
 pub use cancel_reservation::*;
 pub use revise_passengers::*;
 pub use revise_itinerary::*;
+pub use revise_contact::*;
+pub use cancel_flight::*;
+pub use reschedule_flight::*;
+pub use request_contact_email_verification::*;
+pub use verify_contact_email::*;
+pub use send_contact_email_verification::*;
+pub use pay_reservation::*;
+pub use refund_reservation::*;
+pub use get_reservation_payments::*;
+pub use hold_reservation::*;
+pub use confirm_held_reservation::*;
+pub use release_expired_holds::*;
+pub use overbook_flight::*;
+pub use reconcile_oversold_flights::*;
+pub use assign_seats::*;
+pub use get_seat_map::*;
+pub use check_in_passengers::*;
+pub use board_passenger::*;
+pub use close_boarding::*;
+pub use revise_journey::*;
+pub use withdraw_journey::*;
+pub use allot_rooms::*;
 use crate::aggregate::{Accommodation, AccommodationId, Flight, FlightId};
 
 pub type ReferencedItineraryStage = (FlightId, Option<AccommodationId>);
@@ -0,0 +1,56 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::FlightId;
+use crate::policy::OverbookingPolicyError;
+use crate::repository::FlightAvailabilityRepositoryError;
+
+/// overrides the overbooking percentage of the route for a single flight
+pub struct OverbookFlight {
+    pub flight: FlightId,
+    pub percentage: u16,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OverbookFlightError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+
+    #[error("version conflict")]
+    VersionConflict,
+
+    #[error("malformed overbooking: {0}")]
+    OverbookingError(OverbookingPolicyError),
+}
+
+pub type OverbookFlightResult = Result<(), OverbookFlightError>;
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for OverbookFlightError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        match value {
+            FlightAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<OverbookingPolicyError> for OverbookFlightError {
+    fn from(value: OverbookingPolicyError) -> Self {
+        Self::OverbookingError(value)
+    }
+}
+
+impl From<EventPublishError> for OverbookFlightError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for OverbookFlightError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
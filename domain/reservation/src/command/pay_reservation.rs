@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::data::Money;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{PaymentError, PaymentReference, ReservationId};
+use crate::gateway::PaymentGatewayError;
+use crate::policy::ReservationPolicyError;
+use crate::repository::{JourneyRepositoryError, ReservationRepositoryError};
+
+pub struct PayReservation {
+    pub id: ReservationId,
+    pub amount: Money,
+    /// opaque token of the payment gateway
+    pub payment_method: String,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PayReservationError {
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("{0}")]
+    PaymentError(PaymentError),
+
+    #[error("{0}")]
+    PaymentGatewayError(PaymentGatewayError),
+}
+
+// transformers
+impl From<ReservationRepositoryError> for PayReservationError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyRepositoryError> for PayReservationError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for PayReservationError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<PaymentError> for PayReservationError {
+    fn from(value: PaymentError) -> Self {
+        Self::PaymentError(value)
+    }
+}
+
+impl From<PaymentGatewayError> for PayReservationError {
+    fn from(value: PaymentGatewayError) -> Self {
+        Self::PaymentGatewayError(value)
+    }
+}
+
+impl From<EventPublishError> for PayReservationError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for PayReservationError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
+
+pub type PayReservationResult = Result<PaymentReference, PayReservationError>;
@@ -1,12 +1,15 @@
 use thiserror::Error;
 use prelude::collection::SortedSet;
 use prelude::domain::{EventPublishError, EventTryIntoError};
-use crate::aggregate::{AirfieldId, JourneyError, JourneyId, JourneyName, Segment};
+use crate::aggregate::{AirfieldId, JourneyError, JourneyId, JourneyName, JourneyShape, Segment};
+use crate::policy::PricingPolicy;
 use crate::repository::{AirfieldRepositoryError, JourneyRepositoryError};
 
 pub struct PublishJourney {
     pub name: JourneyName,
+    pub shape: JourneyShape,
     pub segments: SortedSet<Segment>,
+    pub pricing_policy: PricingPolicy,
 }
 
 #[derive(Error, Debug, PartialEq)]
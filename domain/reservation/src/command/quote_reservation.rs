@@ -0,0 +1,54 @@
+use thiserror::Error;
+use prelude::data::{Currency, MoneyError};
+use crate::aggregate::{ItineraryError, JourneyId, PassengerArrangement};
+use crate::command::{Itinerary, ReferencedItineraryStage};
+use crate::repository::JourneyRepositoryError;
+use crate::services::reservation_pricing_strategy::PriceBreakdown;
+
+/// prices a reservation without confirming it
+pub struct QuoteReservation {
+    pub journey: JourneyId,
+    pub passengers: PassengerArrangement,
+    pub itinerary: Itinerary<ReferencedItineraryStage>,
+    /// the journey's currency when not given
+    pub currency: Option<Currency>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum QuoteReservationError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    ItineraryError(ItineraryError),
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("journey is withdrawn")]
+    JourneyWithdrawn,
+
+    #[error("{0}")]
+    MoneyError(MoneyError),
+}
+
+// transformers
+impl From<JourneyRepositoryError> for QuoteReservationError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ItineraryError> for QuoteReservationError {
+    fn from(value: ItineraryError) -> Self {
+        Self::ItineraryError(value)
+    }
+}
+
+impl From<MoneyError> for QuoteReservationError {
+    fn from(value: MoneyError) -> Self {
+        Self::MoneyError(value)
+    }
+}
+
+pub type QuoteReservationResult = Result<PriceBreakdown, QuoteReservationError>;
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::FlightId;
+use crate::repository::FlightAvailabilityRepositoryError;
+
+/// the oversold flights
+pub type ReconcileOversoldFlightsResult = Result<Vec<FlightId>, ReconcileOversoldFlightsError>;
+
+pub struct ReconcileOversoldFlights {
+    pub now: DateTime<Utc>
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReconcileOversoldFlightsError {
+    #[error("version conflict")]
+    VersionConflict,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+}
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for ReconcileOversoldFlightsError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        match value {
+            FlightAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<EventPublishError> for ReconcileOversoldFlightsError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for ReconcileOversoldFlightsError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
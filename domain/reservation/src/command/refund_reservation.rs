@@ -0,0 +1,78 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{PaymentError, PaymentReference, ReservationId};
+use crate::gateway::PaymentGatewayError;
+use crate::policy::ReservationPolicyError;
+use crate::repository::{JourneyRepositoryError, ReservationRepositoryError};
+
+/// refunds what's received in excess of the amount due, e.g. after cancellation
+pub struct RefundReservation {
+    pub id: ReservationId,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RefundReservationError {
+    #[error("unknown reservation")]
+    UnknownReservation,
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("{0}")]
+    PaymentError(PaymentError),
+
+    #[error("{0}")]
+    PaymentGatewayError(PaymentGatewayError),
+}
+
+// transformers
+impl From<ReservationRepositoryError> for RefundReservationError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyRepositoryError> for RefundReservationError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for RefundReservationError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<PaymentError> for RefundReservationError {
+    fn from(value: PaymentError) -> Self {
+        Self::PaymentError(value)
+    }
+}
+
+impl From<PaymentGatewayError> for RefundReservationError {
+    fn from(value: PaymentGatewayError) -> Self {
+        Self::PaymentGatewayError(value)
+    }
+}
+
+impl From<EventPublishError> for RefundReservationError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for RefundReservationError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
+
+/// references of the refunds issued
+pub type RefundReservationResult = Result<Vec<PaymentReference>, RefundReservationError>;
@@ -0,0 +1,50 @@
+use std::io::Error;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::ReservationId;
+use crate::repository::ReservationRepositoryError;
+
+/// the released reservations
+pub type ReleaseExpiredHoldsResult = Result<Vec<ReservationId>, ReleaseExpiredHoldsError>;
+
+pub struct ReleaseExpiredHolds {
+    pub now: DateTime<Utc>
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReleaseExpiredHoldsError {
+    #[error("version conflict")]
+    VersionConflict,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("other: {0}")]
+    OtherError(String),
+}
+
+// transformers
+impl From<ReservationRepositoryError> for ReleaseExpiredHoldsError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<std::io::Error> for ReleaseExpiredHoldsError {
+    fn from(value: Error) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventPublishError> for ReleaseExpiredHoldsError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for ReleaseExpiredHoldsError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
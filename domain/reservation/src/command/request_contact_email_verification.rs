@@ -0,0 +1,56 @@
+use std::io::Error;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{EmailVerificationError, ReservationId};
+use crate::repository::ReservationRepositoryError;
+
+pub type RequestContactEmailVerificationResult = Result<(), RequestContactEmailVerificationError>;
+
+pub struct RequestContactEmailVerification {
+    pub reservation: ReservationId,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RequestContactEmailVerificationError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    VerificationError(EmailVerificationError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+}
+
+// transformers
+impl From<ReservationRepositoryError> for RequestContactEmailVerificationError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EmailVerificationError> for RequestContactEmailVerificationError {
+    fn from(value: EmailVerificationError) -> Self {
+        Self::VerificationError(value)
+    }
+}
+
+impl From<std::io::Error> for RequestContactEmailVerificationError {
+    fn from(value: Error) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventPublishError> for RequestContactEmailVerificationError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for RequestContactEmailVerificationError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
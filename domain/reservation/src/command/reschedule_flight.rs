@@ -0,0 +1,56 @@
+use chrono::{DateTime, FixedOffset};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{FlightError, FlightId};
+use crate::repository::FlightAvailabilityRepositoryError;
+
+pub struct RescheduleFlight {
+    pub flight: FlightId,
+    pub departure: DateTime<FixedOffset>,
+    pub arrival: DateTime<FixedOffset>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RescheduleFlightError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight: {0}")]
+    UnknownFlight(FlightId),
+
+    #[error("malformed flight: {0}")]
+    MalformedFlight(FlightError),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+pub type RescheduleFlightResult = Result<(), RescheduleFlightError>;
+
+// transformers
+impl From<FlightAvailabilityRepositoryError> for RescheduleFlightError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        match value {
+            FlightAvailabilityRepositoryError::VersionConflict => Self::VersionConflict,
+            error => Self::IoError(error.to_string()),
+        }
+    }
+}
+
+impl From<FlightError> for RescheduleFlightError {
+    fn from(value: FlightError) -> Self {
+        Self::MalformedFlight(value)
+    }
+}
+
+impl From<EventPublishError> for RescheduleFlightError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for RescheduleFlightError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
@@ -1,12 +1,14 @@
 use thiserror::Error;
 use prelude::domain::{EventPublishError, EventTryIntoError, Versioned};
-use crate::aggregate::{ FlightId, NumberOfSeats, ReservationId};
-use crate::repository::FlightAvailabilityRepositoryError;
+use crate::aggregate::{ FlightId, NumberOfSeats, ReservationId, Stay};
+use crate::repository::{AccommodationAvailabilityRepositoryError, FlightAvailabilityRepositoryError};
 
 pub struct ReserveFlight {
     pub reservation: Versioned<ReservationId>,
     pub flight: FlightId,
-    pub seats: NumberOfSeats
+    pub seats: NumberOfSeats,
+    /// rooms for the seats are reserved first, the stage fails when sold out
+    pub stay: Option<Stay>,
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -30,6 +32,12 @@ impl From<FlightAvailabilityRepositoryError> for ReserveFlightError {
     }
 }
 
+impl From<AccommodationAvailabilityRepositoryError> for ReserveFlightError {
+    fn from(value: AccommodationAvailabilityRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
 impl From<EventPublishError> for ReserveFlightError {
     fn from(value: EventPublishError) -> Self {
         Self::IoError(value.to_string())
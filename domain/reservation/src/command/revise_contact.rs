@@ -0,0 +1,56 @@
+use std::io::Error;
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{Contact, ReservationId};
+use crate::policy::ReservationPolicyError;
+use crate::repository::ReservationRepositoryError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReviseContactError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    PolicyError(ReservationPolicyError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+}
+
+pub type ReviseContactResult = Result<(), ReviseContactError>;
+
+pub struct ReviseContact {
+    pub reservation: ReservationId,
+    pub contact: Contact,
+}
+
+// transformers
+impl From<ReservationRepositoryError> for ReviseContactError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<ReservationPolicyError> for ReviseContactError {
+    fn from(value: ReservationPolicyError) -> Self {
+        Self::PolicyError(value)
+    }
+}
+
+impl From<std::io::Error> for ReviseContactError {
+    fn from(value: Error) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventPublishError> for ReviseContactError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for ReviseContactError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
@@ -0,0 +1,49 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{JourneyError, JourneyId, JourneyRevision};
+use crate::repository::JourneyRepositoryError;
+
+/// renames a journey or changes the accommodations of its segments
+pub struct ReviseJourney {
+    pub journey: JourneyId,
+    pub revisions: Vec<JourneyRevision>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ReviseJourneyError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("{0}")]
+    JourneyError(JourneyError),
+}
+
+pub type ReviseJourneyResult = Result<(), ReviseJourneyError>;
+
+// transformers
+impl From<JourneyRepositoryError> for ReviseJourneyError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyError> for ReviseJourneyError {
+    fn from(value: JourneyError) -> Self {
+        Self::JourneyError(value)
+    }
+}
+
+impl From<EventTryIntoError> for ReviseJourneyError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
+
+impl From<EventPublishError> for ReviseJourneyError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
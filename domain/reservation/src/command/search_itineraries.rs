@@ -0,0 +1,68 @@
+use chrono::NaiveDate;
+use thiserror::Error;
+use prelude::data::{Currency, MoneyError};
+use crate::aggregate::{AirfieldId, JourneyId, PassengerArrangement};
+use crate::repository::{FlightAvailabilityRepositoryError, JourneyRepositoryError};
+use crate::services::itinerary_search::ItineraryCandidate;
+
+/// finds itineraries with seats for the party, to be confirmed as reservation
+pub struct SearchItineraries {
+    pub journey: JourneyId,
+    /// the itineraries run from this airfield, until back at it or the end of a one-way journey
+    pub departure: AirfieldId,
+    pub passengers: PassengerArrangement,
+    pub earliest_departure: NaiveDate,
+    pub latest_departure: NaiveDate,
+    /// preferred number of days, itineraries closest to it come first
+    pub days: i64,
+    /// the journey's currency when not given
+    pub currency: Option<Currency>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SearchItinerariesError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("journey is withdrawn")]
+    JourneyWithdrawn,
+
+    #[error("journey doesn't depart from airfield: {0}")]
+    DepartureNotInJourney(AirfieldId),
+
+    #[error("malformed departure window")]
+    MalformedDepartureWindow,
+
+    #[error("departure window too wide")]
+    DepartureWindowTooWide,
+
+    #[error("number of days not positive")]
+    DaysNotPositive,
+
+    #[error("{0}")]
+    MoneyError(MoneyError),
+}
+
+// transformers
+impl From<JourneyRepositoryError> for SearchItinerariesError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<FlightAvailabilityRepositoryError> for SearchItinerariesError {
+    fn from(value: FlightAvailabilityRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<MoneyError> for SearchItinerariesError {
+    fn from(value: MoneyError) -> Self {
+        Self::MoneyError(value)
+    }
+}
+
+pub type SearchItinerariesResult = Result<Vec<ItineraryCandidate>, SearchItinerariesError>;
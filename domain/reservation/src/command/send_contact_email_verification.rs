@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use crate::aggregate::{EmailAddress, ReservationId};
+use crate::gateway::EmailGatewayError;
+
+pub type SendContactEmailVerificationResult = Result<(), SendContactEmailVerificationError>;
+
+pub struct SendContactEmailVerification {
+    pub reservation: ReservationId,
+    pub email: EmailAddress,
+    pub challenge: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SendContactEmailVerificationError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("email rejected: {0}")]
+    Rejected(String),
+}
+
+// transformers
+impl From<EmailGatewayError> for SendContactEmailVerificationError {
+    fn from(value: EmailGatewayError) -> Self {
+        match value {
+            EmailGatewayError::Rejected(reason) => Self::Rejected(reason),
+            EmailGatewayError::IoError(error) => Self::IoError(error),
+        }
+    }
+}
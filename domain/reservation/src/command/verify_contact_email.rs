@@ -0,0 +1,57 @@
+use std::io::Error;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{EmailVerificationError, ReservationId};
+use crate::repository::ReservationRepositoryError;
+
+pub type VerifyContactEmailResult = Result<(), VerifyContactEmailError>;
+
+pub struct VerifyContactEmail {
+    pub reservation: ReservationId,
+    pub challenge: String,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum VerifyContactEmailError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("{0}")]
+    VerificationError(EmailVerificationError),
+
+    #[error("unknown reservation")]
+    UnknownReservation,
+}
+
+// transformers
+impl From<ReservationRepositoryError> for VerifyContactEmailError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EmailVerificationError> for VerifyContactEmailError {
+    fn from(value: EmailVerificationError) -> Self {
+        Self::VerificationError(value)
+    }
+}
+
+impl From<std::io::Error> for VerifyContactEmailError {
+    fn from(value: Error) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventPublishError> for VerifyContactEmailError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<EventTryIntoError> for VerifyContactEmailError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
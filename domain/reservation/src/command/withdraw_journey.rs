@@ -0,0 +1,48 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{JourneyError, JourneyId};
+use crate::repository::JourneyRepositoryError;
+
+/// stops new reservations of a journey, existing ones stay valid
+pub struct WithdrawJourney {
+    pub journey: JourneyId,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum WithdrawJourneyError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown journey")]
+    UnknownJourney,
+
+    #[error("{0}")]
+    JourneyError(JourneyError),
+}
+
+pub type WithdrawJourneyResult = Result<(), WithdrawJourneyError>;
+
+// transformers
+impl From<JourneyRepositoryError> for WithdrawJourneyError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyError> for WithdrawJourneyError {
+    fn from(value: JourneyError) -> Self {
+        Self::JourneyError(value)
+    }
+}
+
+impl From<EventTryIntoError> for WithdrawJourneyError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to marshal event".to_owned())
+    }
+}
+
+impl From<EventPublishError> for WithdrawJourneyError {
+    fn from(value: EventPublishError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{EmailAddress, ReservationId};
+
+/// challenge is to be sent to the email address (only)
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ContactEmailVerificationRequestedV1 {
+    pub id: ReservationId,
+    pub email: EmailAddress,
+    pub challenge: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TryInto<Event> for ContactEmailVerificationRequestedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
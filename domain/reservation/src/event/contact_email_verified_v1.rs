@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{EmailAddress, ReservationId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ContactEmailVerifiedV1 {
+    pub id: ReservationId,
+    pub email: EmailAddress,
+}
+
+impl TryInto<Event> for ContactEmailVerifiedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
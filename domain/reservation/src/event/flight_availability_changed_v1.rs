@@ -3,13 +3,27 @@ use serde::{Deserialize, Serialize};
 use prelude::domain::{Event, EventTryIntoError};
 use crate::aggregate::{FlightId, FlightRoute, NumberOfSeats};
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct FlightAvailabilityChangedV1 {
     pub flight: FlightId,
     pub route: FlightRoute,
     pub departure: DateTime<FixedOffset>,
     pub arrival: DateTime<FixedOffset>,
+    /// seats on board not yet reserved
     pub seats_available: NumberOfSeats,
+    /// seats on board
+    #[serde(default)]
+    pub seats: NumberOfSeats,
+    /// seats on board, plus overbooking
+    #[serde(default)]
+    pub sellable_seats: NumberOfSeats,
+    /// seats that can still be sold, including overbooking
+    #[serde(default)]
+    pub sellable_seats_available: NumberOfSeats,
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(default)]
+    pub rescheduled_from: Option<DateTime<FixedOffset>>,
 }
 
 impl TryInto<Event> for FlightAvailabilityChangedV1 {
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+use crate::aggregate::FlightId;
+
+#[derive(Serialize, Deserialize)]
+pub struct FlightCancelledV1 {
+    pub id: FlightId,
+}
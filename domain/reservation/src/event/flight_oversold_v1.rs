@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightId, NumberOfSeats, ReservationId};
+
+/// more seats are reserved than there are on board, found when check-in opens
+#[derive(Serialize, Deserialize)]
+pub struct FlightOversoldV1 {
+    pub flight: FlightId,
+    pub seats: NumberOfSeats,
+    pub seats_reserved: NumberOfSeats,
+    /// the most recent seat holders, candidates for being offered compensation
+    pub reservations: Vec<ReservationId>,
+}
+
+impl TryInto<Event> for FlightOversoldV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
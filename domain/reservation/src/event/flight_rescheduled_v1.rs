@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use crate::aggregate::FlightId;
+use crate::event::{FlightArrival, FlightDeparture};
+
+#[derive(Serialize, Deserialize)]
+pub struct FlightRescheduledV1 {
+    pub id: FlightId,
+    pub departure: FlightDeparture,
+    pub arrival: FlightArrival,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightDisruption, FlightId, ReservationId};
+
+#[derive(Serialize, Deserialize)]
+pub struct FlightReservationDisruptedV1 {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub disruption: FlightDisruption,
+}
+
+impl TryInto<Event> for FlightReservationDisruptedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
@@ -1,12 +1,15 @@
 use serde::{Deserialize, Serialize};
 use prelude::domain::{Event, EventTryIntoError, Versioned};
-use crate::aggregate::{FlightId, NumberOfSeats, ReservationId};
+use crate::aggregate::{FlightId, NumberOfSeats, ReservationId, Stay};
 
 #[derive(Serialize, Deserialize)]
 pub struct FlightReservationRequestedV1 {
     pub reservation: Versioned<ReservationId>,
     pub flight: FlightId,
-    pub seats: NumberOfSeats
+    pub seats: NumberOfSeats,
+    /// rooms for the seats are reserved (or released) along
+    #[serde(default)]
+    pub stay: Option<Stay>,
 }
 
 impl TryInto<Event> for FlightReservationRequestedV1 {
@@ -1,6 +1,6 @@
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
-use crate::aggregate::{AirfieldId, Flight, FlightId, FlightRoute, NumberOfSeats};
+use crate::aggregate::{AirfieldId, AirshipModel, Flight, FlightId, FlightRoute, NumberOfSeats};
 
 #[derive(Serialize, Deserialize)]
 pub struct FlightScheduledV1 {
@@ -21,6 +21,7 @@ impl From<FlightScheduledV1> for Flight {
             departure: value.departure.time,
             arrival: value.arrival.time,
             seats: value.airship.number_of_seats,
+            model: value.airship.model,
         }
     }
 }
@@ -39,5 +40,7 @@ pub struct FlightArrival {
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Airship {
-    pub number_of_seats: NumberOfSeats
+    pub number_of_seats: NumberOfSeats,
+    #[serde(default)]
+    pub model: Option<AirshipModel>,
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 use prelude::collection::SortedSet;
 use prelude::domain::{Event, EventTryIntoError};
-use crate::aggregate::{JourneyId, JourneyName, Segment};
+use crate::aggregate::{JourneyId, JourneyName, JourneyShape, Segment};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct JourneyPublishedV1 {
     pub id: JourneyId,
     pub name: JourneyName,
+    #[serde(default)]
+    pub shape: JourneyShape,
     pub segments: SortedSet<Segment>,
 }
 
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use prelude::collection::SortedSet;
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{JourneyId, JourneyName, JourneyShape, Segment};
+
+/// the segments as revised, with all their accommodations
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct JourneyRevisedV1 {
+    pub id: JourneyId,
+    pub name: JourneyName,
+    #[serde(default)]
+    pub shape: JourneyShape,
+    pub segments: SortedSet<Segment>,
+}
+
+impl TryInto<Event> for JourneyRevisedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::JourneyId;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct JourneyWithdrawnV1 {
+    pub id: JourneyId,
+}
+
+impl TryInto<Event> for JourneyWithdrawnV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
\ No newline at end of file
@@ -8,6 +8,25 @@ mod flight_reserved_v1;
 mod flight_reservation_failed_v1;
 mod reservation_cancelled_v1;
 mod reservation_revised_v1;
+mod reservation_contact_revised_v1;
+mod flight_cancelled_v1;
+mod flight_reservation_disrupted_v1;
+mod flight_rescheduled_v1;
+mod contact_email_verification_requested_v1;
+mod contact_email_verified_v1;
+mod payment_recorded_v1;
+mod refund_issued_v1;
+mod reservation_held_v1;
+mod reservation_hold_released_v1;
+mod waitlisted_v1;
+mod waitlist_promoted_v1;
+mod flight_oversold_v1;
+mod seats_assigned_v1;
+mod passengers_checked_in_v1;
+mod passenger_boarded_v1;
+mod passengers_no_show_v1;
+mod journey_revised_v1;
+mod journey_withdrawn_v1;
 
 use serde::Deserialize;
 pub use journey_published_v1::*;
@@ -16,10 +35,29 @@ pub use flight_availability_changed_v1::*;
 pub use airfield_registered_v1::*;
 pub use reservation_confirmed_v1::*;
 pub use reservation_revised_v1::*;
+pub use reservation_contact_revised_v1::*;
 pub use reservation_cancelled_v1::*;
 pub use flight_reservation_requested_v1::*;
 pub use flight_reserved_v1::*;
 pub use flight_reservation_failed_v1::*;
+pub use flight_cancelled_v1::*;
+pub use flight_reservation_disrupted_v1::*;
+pub use flight_rescheduled_v1::*;
+pub use contact_email_verification_requested_v1::*;
+pub use contact_email_verified_v1::*;
+pub use payment_recorded_v1::*;
+pub use refund_issued_v1::*;
+pub use reservation_held_v1::*;
+pub use reservation_hold_released_v1::*;
+pub use waitlisted_v1::*;
+pub use waitlist_promoted_v1::*;
+pub use flight_oversold_v1::*;
+pub use seats_assigned_v1::*;
+pub use passengers_checked_in_v1::*;
+pub use passenger_boarded_v1::*;
+pub use passengers_no_show_v1::*;
+pub use journey_revised_v1::*;
+pub use journey_withdrawn_v1::*;
 
 #[derive(Deserialize)]
 pub struct RawEvent(String);
@@ -38,8 +76,27 @@ pub enum Event {
     FlightAvailabilityChangedV1(FlightAvailabilityChangedV1),
     ReservationConfirmedV1(ReservationConfirmedV1),
     ReservationRevisedV1(ReservationRevisedV1),
+    ReservationContactRevisedV1(ReservationContactRevisedV1),
     ReservationCancelledV1(ReservationCancelledV1),
     FlightReservationRequestedV1(FlightReservationRequestedV1),
     FlightReservedV1(FlightReservedV1),
     FlightReservationFailedV1(FlightReservationFailedV1),
+    FlightCancelledV1(FlightCancelledV1),
+    FlightReservationDisruptedV1(FlightReservationDisruptedV1),
+    FlightRescheduledV1(FlightRescheduledV1),
+    ContactEmailVerificationRequestedV1(ContactEmailVerificationRequestedV1),
+    ContactEmailVerifiedV1(ContactEmailVerifiedV1),
+    PaymentRecordedV1(PaymentRecordedV1),
+    RefundIssuedV1(RefundIssuedV1),
+    ReservationHeldV1(ReservationHeldV1),
+    ReservationHoldReleasedV1(ReservationHoldReleasedV1),
+    WaitlistedV1(WaitlistedV1),
+    WaitlistPromotedV1(WaitlistPromotedV1),
+    FlightOversoldV1(FlightOversoldV1),
+    SeatsAssignedV1(SeatsAssignedV1),
+    PassengersCheckedInV1(PassengersCheckedInV1),
+    PassengerBoardedV1(PassengerBoardedV1),
+    PassengersNoShowV1(PassengersNoShowV1),
+    JourneyRevisedV1(JourneyRevisedV1),
+    JourneyWithdrawnV1(JourneyWithdrawnV1),
 }
\ No newline at end of file
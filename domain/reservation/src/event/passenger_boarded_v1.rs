@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightId, PersonFullName, ReservationId};
+
+#[derive(Serialize, Deserialize)]
+pub struct PassengerBoardedV1 {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub passenger: PersonFullName,
+    pub time: DateTime<Utc>,
+}
+
+impl TryInto<Event> for PassengerBoardedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightId, PersonFullName, ReservationId};
+
+/// passengers of the reservation checked in on the flight, boarding passes are issued to them
+#[derive(Serialize, Deserialize)]
+pub struct PassengersCheckedInV1 {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub passengers: Vec<PersonFullName>,
+}
+
+impl TryInto<Event> for PassengersCheckedInV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightId, PersonFullName, ReservationId};
+
+/// passengers of the reservation that did not board before departure
+#[derive(Serialize, Deserialize)]
+pub struct PassengersNoShowV1 {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub passengers: Vec<PersonFullName>,
+}
+
+impl TryInto<Event> for PassengersNoShowV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
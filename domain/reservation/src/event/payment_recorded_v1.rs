@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::data::Money;
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{JourneyId, PaymentReference, ReservationId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct PaymentRecordedV1 {
+    pub id: ReservationId,
+    pub journey: JourneyId,
+    pub reference: PaymentReference,
+    pub amount: Money,
+    pub time: DateTime<Utc>,
+}
+
+impl TryInto<Event> for PaymentRecordedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
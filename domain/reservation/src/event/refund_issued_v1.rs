@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::data::Money;
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{JourneyId, PaymentReference, ReservationId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct RefundIssuedV1 {
+    pub id: ReservationId,
+    pub journey: JourneyId,
+    pub payment: PaymentReference,
+    pub reference: PaymentReference,
+    pub amount: Money,
+    pub time: DateTime<Utc>,
+}
+
+impl TryInto<Event> for RefundIssuedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
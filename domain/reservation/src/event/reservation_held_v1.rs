@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{Contact, Itinerary, JourneyId, Passengers, ReservationId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ReservationHeldV1 {
+    pub id: ReservationId,
+    pub journey: JourneyId,
+    pub contact: Contact,
+    pub passengers: Passengers,
+    pub itinerary: Itinerary,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TryInto<Event> for ReservationHeldV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
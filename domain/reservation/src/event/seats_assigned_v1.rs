@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{AssignedSeat, FlightId, ReservationId};
+
+/// replaces the seats previously assigned to the passengers of the reservation on the flight
+#[derive(Serialize, Deserialize)]
+pub struct SeatsAssignedV1 {
+    pub reservation: ReservationId,
+    pub flight: FlightId,
+    pub seats: Vec<AssignedSeat>,
+}
+
+impl TryInto<Event> for SeatsAssignedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
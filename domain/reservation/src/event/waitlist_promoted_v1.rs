@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError, Versioned};
+use crate::aggregate::{FlightId, NumberOfSeats, ReservationId};
+
+/// seats freed on the flight are reserved for the waitlisted reservation
+#[derive(Serialize, Deserialize)]
+pub struct WaitlistPromotedV1 {
+    pub reservation: Versioned<ReservationId>,
+    pub flight: FlightId,
+    pub seats: NumberOfSeats,
+}
+
+impl TryInto<Event> for WaitlistPromotedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
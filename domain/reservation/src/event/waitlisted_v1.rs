@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError, Versioned};
+use crate::aggregate::{FlightId, NumberOfSeats, ReservationId};
+
+#[derive(Serialize, Deserialize)]
+pub struct WaitlistedV1 {
+    pub reservation: Versioned<ReservationId>,
+    pub flight: FlightId,
+    pub seats: NumberOfSeats,
+    /// 1 is next in line
+    pub position: usize,
+}
+
+impl TryInto<Event> for WaitlistedV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
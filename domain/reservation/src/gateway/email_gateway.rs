@@ -0,0 +1,19 @@
+use thiserror::Error;
+use prelude::async_trait;
+use crate::aggregate::EmailAddress;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EmailGatewayError {
+    #[error("rejected: {0}")]
+    Rejected(String),
+
+    #[error("I/O error {0}")]
+    IoError(String),
+}
+
+/// port to the email delivery service
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait EmailGateway {
+    async fn send(&self, to: &EmailAddress, subject: &str, body: &str) -> Result<(), EmailGatewayError>;
+}
@@ -0,0 +1,5 @@
+mod payment_gateway;
+mod email_gateway;
+
+pub use payment_gateway::*;
+pub use email_gateway::*;
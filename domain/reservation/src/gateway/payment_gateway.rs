@@ -0,0 +1,24 @@
+use thiserror::Error;
+use prelude::async_trait;
+use prelude::data::Money;
+use crate::aggregate::{PaymentReference, ReservationId};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PaymentGatewayError {
+    #[error("declined: {0}")]
+    Declined(String),
+
+    #[error("I/O error {0}")]
+    IoError(String),
+}
+
+/// port to the payment service provider, which holds the payment details
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait PaymentGateway {
+    /// payment method is an opaque token, obtained by the client from the provider;
+    /// charges with the same idempotency key are charged once, the reference of the first is returned
+    async fn charge(&self, reservation: &ReservationId, amount: Money, payment_method: &str, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError>;
+    /// refunds with the same idempotency key are refunded once, the reference of the first is returned
+    async fn refund(&self, reservation: &ReservationId, payment: &PaymentReference, amount: Money, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError>;
+}
@@ -0,0 +1,31 @@
+use std::sync::Mutex;
+use prelude::async_trait;
+use crate::aggregate::EmailAddress;
+use crate::gateway::{EmailGateway, EmailGatewayError};
+
+/// fake email delivery service for tests and local development, records sent emails
+#[derive(Default)]
+pub struct FakeEmailGateway {
+    sent: Mutex<Vec<(EmailAddress, String, String)>>,
+}
+
+impl FakeEmailGateway {
+    /// recipient, subject and body, in order of sending
+    pub fn sent(&self) -> Vec<(EmailAddress, String, String)> {
+        self.sent.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl EmailGateway for FakeEmailGateway {
+    async fn send(&self, to: &EmailAddress, subject: &str, body: &str) -> Result<(), EmailGatewayError> {
+        self.sent.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push((to.clone(), subject.to_owned(), body.to_owned()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::LinkedList;
+use std::rc::Rc;
+use futures::executor::block_on;
+use serde::Serialize;
+use prelude::collection::SortedSet;
+use prelude::data::{Currency, Decimal, Money, StaticCurrencyConverter};
+use prelude::domain::{Event, EventPublisher, RecordingEventPublisher};
+use crate::aggregate::{Accommodation, AccommodationId, Contact, Flight, FlightAvailability, FlightId, FlightRoute, HeldReservation, ItineraryStage, JourneyId, JourneyShape, Passenger, PassengerArrangement, Passengers, Picture, Place, Reservation, ReservationId, Segment};
+use crate::command::{CancelReservation, ConfirmReservation, GetReservation, HoldReservation, MakeFlightAvailable, PublishJourney, RegisterAirfield, RevisePassengers};
+use crate::in_memory::{FakeEmailGateway, FakePaymentGateway, InMemoryReservationRepository, SagaDriver};
+use crate::policy::{OverbookingPolicy, PricingPolicy, RefundTier, ReservationPolicy};
+use crate::projection::{Manifest, ManifestApplyContext};
+use crate::repository::FlightAvailabilityRepository;
+use crate::usecase::{AvailabilityUseCase, JourneyUseCase, NotificationUseCase, QueueUseCase, ReservationUseCase};
+
+/// a round trip journey between two airfields with a flight each way, 3 seats per flight,
+/// events are delivered by the saga driver
+pub struct TestContext {
+    pub repository: Rc<InMemoryReservationRepository>,
+    pub publisher: Rc<RecordingEventPublisher>,
+    pub payment_gateway: Rc<FakePaymentGateway>,
+    pub email_gateway: Rc<FakeEmailGateway>,
+    pub reservation_usecase: ReservationUseCase,
+    pub driver: SagaDriver,
+    pub journey: JourneyId,
+}
+
+impl TestContext {
+    pub fn build() -> Self {
+        let repository = Rc::new(InMemoryReservationRepository::default());
+        let payment_gateway = Rc::new(FakePaymentGateway::default());
+        let email_gateway = Rc::new(FakeEmailGateway::default());
+        let publisher = Rc::new(RecordingEventPublisher::default());
+
+        let journey_usecase = JourneyUseCase::new(repository.clone(), repository.clone(), publisher.clone());
+        let availability_usecase = AvailabilityUseCase::new(repository.clone(), repository.clone(), publisher.clone(), OverbookingPolicy::none());
+
+        for (id, name) in [("EHAM", "Schiphol Airport"), ("ENLI", "Farsund Airport")] {
+            block_on(journey_usecase.register_airfield(RegisterAirfield {
+                id: id.parse().unwrap(),
+                name: name.to_owned(),
+                location: "sytpz3".parse().unwrap(),
+            })).unwrap();
+        }
+
+        for flight in [flight_eham_enli(), flight_enli_eham()] {
+            block_on(availability_usecase.make_flight_available(MakeFlightAvailable { flight })).unwrap();
+        }
+
+        let journey = block_on(journey_usecase.publish(PublishJourney {
+            name: "Journey Around North Atlantic".parse().unwrap(),
+            shape: JourneyShape::RoundTrip,
+            segments: SortedSet::empty()
+                .insert(Segment::build(route_eham_enli(), SortedSet::singleton(accommodation())).unwrap())
+                .insert(Segment::build(route_enli_eham(), SortedSet::empty()).unwrap()),
+            // half is refunded on cancellation, whenever, as the test flights departed already
+            pricing_policy: PricingPolicy::build(
+                Money::usd(12000),
+                Money::usd(12000),
+                vec![],
+                vec![],
+                vec![RefundTier { days_before_departure: i64::MIN, refund_percentage: 50 }],
+            ).unwrap().with_window_seat_price(Money::usd(2500)).unwrap(),
+        })).unwrap();
+
+        let driver = SagaDriver::new(
+            QueueUseCase::new(
+                journey_usecase,
+                availability_usecase,
+                ReservationUseCase::new(repository.clone(), repository.clone(), repository.clone(), publisher.clone(), payment_gateway.clone()),
+                NotificationUseCase::new(email_gateway.clone()),
+            ),
+            publisher.clone(),
+        );
+
+        // drain setup events
+        block_on(driver.run()).unwrap();
+
+        Self {
+            reservation_usecase: ReservationUseCase::new(repository.clone(), repository.clone(), repository.clone(), publisher.clone(), payment_gateway.clone()),
+            payment_gateway,
+            email_gateway,
+            repository,
+            publisher,
+            driver,
+            journey,
+        }
+    }
+
+    /// a use case on the same repository and publisher, for the tests of its own commands
+    pub fn availability_usecase(&self) -> AvailabilityUseCase {
+        AvailabilityUseCase::new(self.repository.clone(), self.repository.clone(), self.publisher.clone(), OverbookingPolicy::none())
+    }
+
+    pub fn journey_usecase(&self) -> JourneyUseCase {
+        JourneyUseCase::new(self.repository.clone(), self.repository.clone(), self.publisher.clone())
+    }
+
+    /// delivers all events sent so far, and those sent in turn
+    pub fn deliver(&self) -> usize {
+        block_on(self.driver.run()).unwrap()
+    }
+
+    pub fn publish<T: Serialize>(&self, event: T) {
+        block_on(self.publisher.send(Event::try_into(event).unwrap())).unwrap();
+    }
+
+    /// both flights, without accommodation
+    pub fn confirm(&self, adults: u8) -> ReservationId {
+        block_on(self.reservation_usecase.confirm(ConfirmReservation {
+            journey: self.journey,
+            contact: contact(),
+            passengers: PassengerArrangement::build(adults, 0).unwrap(),
+            itinerary: round_trip(),
+        }, &ReservationPolicy::test())).unwrap()
+    }
+
+    /// confirmed, with its flights reserved as far as seats allow
+    pub fn reserve(&self, adults: u8) -> ReservationId {
+        let id = self.confirm(adults);
+        self.deliver();
+        id
+    }
+
+    pub fn hold(&self, adults: u8) -> ReservationId {
+        block_on(self.reservation_usecase.hold(HoldReservation {
+            journey: self.journey,
+            contact: contact(),
+            passengers: PassengerArrangement::build(adults, 0).unwrap(),
+            itinerary: round_trip(),
+        }, &ReservationPolicy::test())).unwrap()
+    }
+
+    pub fn list_passengers<const N: usize>(&self, id: ReservationId, names: [&str; N]) {
+        block_on(self.reservation_usecase.revise_passengers(RevisePassengers {
+            reservation: id,
+            passengers: passengers(names),
+        }, &ReservationPolicy::test())).unwrap();
+        self.deliver();
+    }
+
+    pub fn cancel(&self, id: ReservationId) {
+        block_on(self.reservation_usecase.cancel(CancelReservation { id }, &ReservationPolicy::test())).unwrap();
+        self.deliver();
+    }
+
+    pub fn get(&self, id: ReservationId) -> Reservation {
+        block_on(self.reservation_usecase.get(GetReservation { id }, &ReservationPolicy::test())).unwrap()
+    }
+
+    pub fn flight_availability(&self, flight: &FlightId) -> FlightAvailability {
+        block_on(FlightAvailabilityRepository::get(self.repository.as_ref(), flight)).unwrap().unwrap()
+    }
+
+    /// all events sent so far, in order
+    pub fn events(&self) -> Vec<crate::event::Event> {
+        self.publisher.sent().into_iter()
+            .map(|event| serde_json::from_value(event.data()).unwrap())
+            .collect()
+    }
+
+    /// projected from all events sent so far
+    pub fn manifest(&self, flight: &FlightId) -> Manifest {
+        let apply_context = ManifestApplyContext::new(self.repository.clone());
+
+        self.events().into_iter()
+            .fold(Manifest::from_flight(flight), |manifest, event| {
+                if block_on(apply_context.affected_flights(&event)).unwrap().contains(flight) {
+                    block_on(manifest.apply(&apply_context, &event)).unwrap()
+                }
+                else {
+                    manifest
+                }
+            })
+    }
+}
+
+pub fn round_trip() -> LinkedList<(FlightId, Option<AccommodationId>)> {
+    LinkedList::from([
+        (flight_eham_enli().id, None),
+        (flight_enli_eham().id, None),
+    ])
+}
+
+pub fn contact() -> Contact {
+    Contact::new(
+        "Niels Bergsma".parse().unwrap(),
+        "n.bergsma@internet.com".parse().unwrap(),
+        None,
+    )
+}
+
+pub fn passengers<const N: usize>(names: [&str; N]) -> Passengers {
+    Passengers::List(SortedSet::from_vec(names.into_iter()
+        .map(|name| Passenger::new(name.parse().unwrap(), "1980-01-01".parse().unwrap()))
+        .collect()))
+}
+
+pub fn converter() -> StaticCurrencyConverter {
+    StaticCurrencyConverter::new(Currency::USD, [
+        (Currency::EUR, Decimal::new(92, 2)),
+    ])
+}
+
+pub fn stages(reservation: &Reservation) -> Vec<ItineraryStage> {
+    match reservation {
+        Reservation::Held(HeldReservation { reservation: confirmed, .. })
+        | Reservation::Confirmed(confirmed) => confirmed.itinerary.stages().cloned().collect(),
+        Reservation::Cancelled(_) => Vec::new(),
+    }
+}
+
+pub fn all_stages(reservation: &Reservation, predicate: impl Fn(&ItineraryStage) -> bool) -> bool {
+    match reservation {
+        Reservation::Held(HeldReservation { reservation: confirmed, .. })
+        | Reservation::Confirmed(confirmed) => confirmed.itinerary.stages().all(predicate),
+        Reservation::Cancelled(_) => false,
+    }
+}
+
+/// on the outbound segment
+pub fn accommodation() -> Accommodation {
+    Accommodation::build(
+        "6EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+        "Farsund Fjordhotel".parse().unwrap(),
+        Place::new(
+            "Farsund, Norway".parse().unwrap(),
+            "u4kf6x".parse().unwrap(),
+        ),
+        SortedSet::singleton(Picture::build(
+            "https://www.visitnorway.com/img/farsund.jpg".parse().unwrap(),
+            "Farsund Resort".to_owned(),
+        ).unwrap()),
+    ).unwrap()
+}
+
+pub fn route_eham_enli() -> FlightRoute {
+    FlightRoute::build(
+        "EHAM".parse().unwrap(),
+        "ENLI".parse().unwrap(),
+    ).unwrap()
+}
+
+pub fn route_enli_eham() -> FlightRoute {
+    FlightRoute::build(
+        "ENLI".parse().unwrap(),
+        "EHAM".parse().unwrap(),
+    ).unwrap()
+}
+
+pub fn flight_eham_enli() -> Flight {
+    Flight::build(
+        "9EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+        route_eham_enli(),
+        "2024-05-08T09:00:00+05:00".parse().unwrap(),
+        "2024-05-08T11:00:00+05:00".parse().unwrap(),
+        3u8,
+    ).unwrap()
+}
+
+pub fn flight_enli_eham() -> Flight {
+    Flight::build(
+        "8EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+        route_enli_eham(),
+        "2024-05-14T09:00:00+05:00".parse().unwrap(),
+        "2024-05-14T11:00:00+05:00".parse().unwrap(),
+        3u8,
+    ).unwrap()
+}
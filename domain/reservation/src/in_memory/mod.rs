@@ -0,0 +1,11 @@
+mod repository;
+mod payment_gateway;
+mod email_gateway;
+mod saga_driver;
+#[cfg(test)]
+pub(crate) mod fixture;
+
+pub use repository::*;
+pub use payment_gateway::*;
+pub use email_gateway::*;
+pub use saga_driver::*;
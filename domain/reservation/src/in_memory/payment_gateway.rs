@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+use prelude::async_trait;
+use prelude::data::Money;
+use crate::aggregate::{PaymentReference, ReservationId};
+use crate::gateway::{PaymentGateway, PaymentGatewayError};
+
+/// payment method that the fake gateway declines
+pub const FAKE_DECLINED_PAYMENT_METHOD: &str = "declined";
+
+/// fake payment service provider for tests and local development;
+/// accepts any payment method but FAKE_DECLINED_PAYMENT_METHOD, and records charges and refunds (once per idempotency key)
+#[derive(Default)]
+pub struct FakePaymentGateway {
+    charges: Mutex<Vec<(PaymentReference, ReservationId, Money)>>,
+    idempotency_keys: Mutex<Vec<(String, PaymentReference)>>,
+    refunds: Mutex<Vec<(PaymentReference, PaymentReference, Money)>>,
+    refund_idempotency_keys: Mutex<Vec<(String, PaymentReference)>>,
+}
+
+impl FakePaymentGateway {
+    /// reference, reservation and amount, in order of charging
+    pub fn charges(&self) -> Vec<(PaymentReference, ReservationId, Money)> {
+        self.charges.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    /// reference, payment and amount, in order of refunding
+    pub fn refunds(&self) -> Vec<(PaymentReference, PaymentReference, Money)> {
+        self.refunds.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl PaymentGateway for FakePaymentGateway {
+    async fn charge(&self, reservation: &ReservationId, amount: Money, payment_method: &str, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+        if payment_method == FAKE_DECLINED_PAYMENT_METHOD {
+            return Err(PaymentGatewayError::Declined("insufficient funds".to_owned()));
+        }
+
+        let mut idempotency_keys = self.idempotency_keys.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        if let Some((_, reference)) = idempotency_keys.iter().find(|(key, _)| key == idempotency_key) {
+            return Ok(reference.clone());
+        }
+
+        let mut charges = self.charges.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        let reference: PaymentReference = format!("pay_{}", charges.len() + 1).parse()
+            .expect("reference should not be empty");
+        charges.push((reference.clone(), *reservation, amount));
+        idempotency_keys.push((idempotency_key.to_owned(), reference.clone()));
+
+        Ok(reference)
+    }
+
+    async fn refund(&self, _reservation: &ReservationId, payment: &PaymentReference, amount: Money, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+        let mut idempotency_keys = self.refund_idempotency_keys.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        if let Some((_, reference)) = idempotency_keys.iter().find(|(key, _)| key == idempotency_key) {
+            return Ok(reference.clone());
+        }
+
+        let mut refunds = self.refunds.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        let reference: PaymentReference = format!("re_{}", refunds.len() + 1).parse()
+            .expect("reference should not be empty");
+        refunds.push((reference.clone(), payment.clone(), amount));
+        idempotency_keys.push((idempotency_key.to_owned(), reference.clone()));
+
+        Ok(reference)
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{DateTime, Utc};
+use prelude::async_trait;
+use prelude::domain::Transaction;
+use prelude::runtime::in_memory::{InMemoryStorage, InMemoryStorageError};
+use crate::aggregate::{AccommodationAvailability, AccommodationId, Airfield, AirfieldId, FlightAvailability, FlightId, FlightRoute, Journey, JourneyId, Reservation, ReservationId};
+use crate::repository::{AccommodationAvailabilityRepository, AccommodationAvailabilityRepositoryError, AirfieldRepository, AirfieldRepositoryError, FlightAvailabilityRepository, FlightAvailabilityRepositoryError, JourneyRepository, JourneyRepositoryError, ReservationRepository, ReservationRepositoryError};
+
+/// in-memory counterpart of the durable object repository, for tests and local development;
+/// uses the same keys and versioning rules
+#[derive(Default)]
+pub struct InMemoryReservationRepository {
+    storage: InMemoryStorage
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl JourneyRepository for InMemoryReservationRepository {
+    async fn get(&self, id: &JourneyId) -> Result<Option<Journey>, JourneyRepositoryError> {
+        let key = ["journey:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Journey>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &JourneyId) -> Result<Transaction<JourneyId, Journey>, JourneyRepositoryError> {
+        let key = ["journey:", &id.to_string()].concat();
+
+        match self.storage.get::<Journey>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(*id, value)),
+            None => Ok(Transaction::new(*id))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<JourneyId, Journey>) -> Result<(), JourneyRepositoryError> {
+        let key = ["journey:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AirfieldRepository for InMemoryReservationRepository {
+    async fn get(&self, id: &AirfieldId) -> Result<Option<Airfield>, AirfieldRepositoryError> {
+        let key = ["airfield:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Airfield>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &AirfieldId) -> Result<Transaction<AirfieldId, Airfield>, AirfieldRepositoryError> {
+        let key = ["airfield:", &id.to_string()].concat();
+
+        match self.storage.get::<Airfield>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(id.clone(), value)),
+            None => Ok(Transaction::new(id.clone()))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<AirfieldId, Airfield>) -> Result<(), AirfieldRepositoryError> {
+        let key = ["airfield:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl FlightAvailabilityRepository for InMemoryReservationRepository {
+    async fn get(&self, id: &FlightId) -> Result<Option<FlightAvailability>, FlightAvailabilityRepositoryError> {
+        let key = ["flight_availability:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<FlightAvailability>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &FlightId) -> Result<Transaction<FlightId, FlightAvailability>, FlightAvailabilityRepositoryError> {
+        let key = ["flight_availability:", &id.to_string()].concat();
+
+        match self.storage.get::<FlightAvailability>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(id.clone(), value)),
+            None => Ok(Transaction::new(id.clone()))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<FlightId, FlightAvailability>) -> Result<(), FlightAvailabilityRepositoryError> {
+        let key = ["flight_availability:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn list_due_for_reconciliation(&self, departing_before: DateTime<Utc>) -> Result<Vec<FlightId>, FlightAvailabilityRepositoryError> {
+        Ok(self.storage.list::<FlightAvailability>("flight_availability:")?
+            .into_iter()
+            .map(|value| value.value())
+            .filter(|availability| availability.is_due_for_reconciliation(departing_before))
+            .map(|availability| availability.flight.id)
+            .collect())
+    }
+
+    async fn list_by_route(&self, route: &FlightRoute, departing_after: DateTime<Utc>, departing_before: DateTime<Utc>) -> Result<Vec<FlightAvailability>, FlightAvailabilityRepositoryError> {
+        Ok(self.storage.list::<FlightAvailability>("flight_availability:")?
+            .into_iter()
+            .map(|value| value.value())
+            .filter(|availability| availability.departs_on_route(route, departing_after, departing_before))
+            .collect())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AccommodationAvailabilityRepository for InMemoryReservationRepository {
+    async fn get(&self, id: &AccommodationId) -> Result<Option<AccommodationAvailability>, AccommodationAvailabilityRepositoryError> {
+        let key = ["accommodation_availability:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<AccommodationAvailability>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &AccommodationId) -> Result<Transaction<AccommodationId, AccommodationAvailability>, AccommodationAvailabilityRepositoryError> {
+        let key = ["accommodation_availability:", &id.to_string()].concat();
+
+        match self.storage.get::<AccommodationAvailability>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(*id, value)),
+            None => Ok(Transaction::new(*id))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<AccommodationId, AccommodationAvailability>) -> Result<(), AccommodationAvailabilityRepositoryError> {
+        let key = ["accommodation_availability:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ReservationRepository for InMemoryReservationRepository {
+    async fn get(&self, id: &ReservationId) -> Result<Option<Reservation>, ReservationRepositoryError> {
+        let key = ["reservation:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Reservation>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &ReservationId) -> Result<Transaction<ReservationId, Reservation>, ReservationRepositoryError> {
+        let key = ["reservation:", &id.to_string()].concat();
+
+        match self.storage.get::<Reservation>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(*id, value)),
+            None => Ok(Transaction::new(*id))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<ReservationId, Reservation>) -> Result<(), ReservationRepositoryError> {
+        let key = ["reservation:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn list_expired_holds(&self, now: DateTime<Utc>) -> Result<Vec<ReservationId>, ReservationRepositoryError> {
+        Ok(self.storage.list::<Reservation>("reservation:")?
+            .into_iter()
+            .map(|value| value.value())
+            .filter(|reservation| reservation.is_expired_hold(now))
+            .map(|reservation| reservation.id())
+            .collect())
+    }
+}
+
+// transformers
+impl From<InMemoryStorageError> for JourneyRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => JourneyRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => JourneyRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for AirfieldRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => AirfieldRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => AirfieldRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for FlightAvailabilityRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => FlightAvailabilityRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => FlightAvailabilityRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for AccommodationAvailabilityRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => AccommodationAvailabilityRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => AccommodationAvailabilityRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for ReservationRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => ReservationRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => ReservationRepositoryError::IoError(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use crate::aggregate::{Airfield, AirfieldId};
+    use crate::in_memory::InMemoryReservationRepository;
+    use crate::repository::{AirfieldRepository, AirfieldRepositoryError};
+
+    #[test]
+    fn get_unknown_returns_none() {
+        let repository = InMemoryReservationRepository::default();
+
+        let result = block_on(repository.get(&airfield_id()));
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn commits_and_gets() {
+        let repository = InMemoryReservationRepository::default();
+
+        let transaction = block_on(repository.set_begin(&airfield_id())).unwrap();
+        assert!(transaction.is_empty());
+
+        let result = block_on(repository.set_commit(transaction.with_value(airfield())));
+        assert_eq!(result, Ok(()));
+
+        let result = block_on(repository.get(&airfield_id()));
+        assert_eq!(result, Ok(Some(airfield())));
+    }
+
+    #[test]
+    fn rejects_concurrent_commits() {
+        let repository = InMemoryReservationRepository::default();
+
+        let transaction1 = block_on(repository.set_begin(&airfield_id())).unwrap();
+        let transaction2 = block_on(repository.set_begin(&airfield_id())).unwrap();
+
+        let result = block_on(repository.set_commit(transaction1.with_value(airfield())));
+        assert_eq!(result, Ok(()));
+
+        let result = block_on(repository.set_commit(transaction2.with_value(airfield())));
+        assert_eq!(result, Err(AirfieldRepositoryError::VersionConflict));
+    }
+
+    // test data
+    fn airfield_id() -> AirfieldId {
+        "EHAM".parse().unwrap()
+    }
+
+    fn airfield() -> Airfield {
+        Airfield::build(airfield_id(), "Schiphol Airport".to_owned(), "sytpz3".parse().unwrap())
+    }
+}
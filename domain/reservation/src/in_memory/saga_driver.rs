@@ -0,0 +1,92 @@
+use std::rc::Rc;
+use thiserror::Error;
+use prelude::domain::RecordingEventPublisher;
+use crate::event::Event;
+use crate::usecase::{HandleEventError, QueueUseCase};
+
+/// upper bound on deliveries in a single run, guards against sagas that never converge
+pub const SAGA_DRIVER_MAX_DELIVERIES: usize = 1_000;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SagaDriverError {
+    #[error("malformed event {0}: {1}")]
+    MalformedEvent(String, String),
+
+    #[error("unable to handle event {0}: {1}")]
+    HandleEventError(String, HandleEventError),
+
+    #[error("events keep coming after {0} deliveries")]
+    Exhausted(usize),
+}
+
+/// delivers events recorded by the publisher to the queue use case, one at a time and in order of sending,
+/// until no events are pending; mimics the queue consumer in the service, synchronously and deterministic
+pub struct SagaDriver {
+    queue_usecase: QueueUseCase,
+    event_publisher: Rc<RecordingEventPublisher>,
+}
+
+impl SagaDriver {
+    pub fn new(queue_usecase: QueueUseCase, event_publisher: Rc<RecordingEventPublisher>) -> Self {
+        Self {
+            queue_usecase,
+            event_publisher,
+        }
+    }
+
+    /// returns the number of delivered events
+    pub async fn run(&self) -> Result<usize, SagaDriverError> {
+        let mut deliveries = 0;
+
+        while let Some(event) = self.event_publisher.next_pending() {
+            if deliveries == SAGA_DRIVER_MAX_DELIVERIES {
+                return Err(SagaDriverError::Exhausted(deliveries));
+            }
+
+            let incoming: Event = serde_json::from_value(event.data())
+                .map_err(|error| SagaDriverError::MalformedEvent(event.name(), error.to_string()))?;
+
+            self.queue_usecase.handle(incoming).await
+                .map_err(|error| SagaDriverError::HandleEventError(event.name(), error))?;
+
+            deliveries += 1;
+        }
+
+        Ok(deliveries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{ItineraryStage, ItineraryStageError};
+    use crate::in_memory::fixture::{all_stages, flight_eham_enli, TestContext};
+
+    #[test]
+    fn confirmed_reservation_converges_to_reserved_flights() {
+        let context = TestContext::build();
+
+        let id = context.confirm(2);
+        let deliveries = context.deliver();
+
+        // per flight: requested, availability changed and reserved
+        assert_eq!(deliveries, 7);
+
+        let reservation = context.get(id);
+        assert!(all_stages(&reservation, |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 1);
+    }
+
+    #[test]
+    fn reservation_with_insufficient_seats_converges_to_failed_flights() {
+        let context = TestContext::build();
+
+        let id = context.reserve(4);
+
+        let reservation = context.get(id);
+        assert!(all_stages(&reservation, |stage|
+            matches!(stage, ItineraryStage::ReservedFailed(_, _, ItineraryStageError::InsufficientSeats))
+        ));
+    }
+}
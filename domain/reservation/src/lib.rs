@@ -1,8 +1,12 @@
 pub mod aggregate;
 pub mod event;
 pub mod repository;
+pub mod gateway;
 pub mod command;
 pub mod usecase;
 pub mod projection;
 pub mod policy;
 pub mod services;
+
+#[cfg(any(test, feature = "in_memory"))]
+pub mod in_memory;
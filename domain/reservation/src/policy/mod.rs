@@ -1,3 +1,7 @@
 mod reservation_policy;
+mod pricing_policy;
+mod overbooking_policy;
 
-pub use reservation_policy::*;
\ No newline at end of file
+pub use reservation_policy::*;
+pub use pricing_policy::*;
+pub use overbooking_policy::*;
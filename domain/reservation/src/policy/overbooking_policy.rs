@@ -0,0 +1,131 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::aggregate::FlightRoute;
+
+pub const NO_OVERBOOKING_PERCENTAGE: u16 = 100;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OverbookingPolicyError {
+    #[error("overbooking percentage below 100%")]
+    PercentageBelow100,
+
+    #[error("check-in should open before departure")]
+    CheckInNotBeforeDeparture,
+}
+
+/// overrides the overbooking percentage for the flights on a route
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouteOverbooking {
+    pub route: FlightRoute,
+    pub percentage: u16,
+}
+
+/// how many seats are sold relative to the seats of a flight, to offset no-shows;
+/// e.g. 110 sells 11 seats for every 10 seats on board
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OverbookingPolicy {
+    percentage: u16,
+    routes: Vec<RouteOverbooking>,
+    /// oversold flights are reconciled once check-in opens
+    check_in_hours_before_departure: i64,
+}
+
+impl OverbookingPolicy {
+    pub fn build(percentage: u16, routes: Vec<RouteOverbooking>, check_in_hours_before_departure: i64) -> Result<Self, OverbookingPolicyError> {
+        Self {
+            percentage,
+            routes,
+            check_in_hours_before_departure,
+        }.validate()
+    }
+
+    /// e.g. when deserialized from configuration
+    pub fn validate(self) -> Result<Self, OverbookingPolicyError> {
+        Self::check_percentage(self.percentage)?;
+        for route in self.routes.iter() {
+            Self::check_percentage(route.percentage)?;
+        }
+
+        if self.check_in_hours_before_departure <= 0 {
+            return Err(OverbookingPolicyError::CheckInNotBeforeDeparture);
+        }
+
+        Ok(self)
+    }
+
+    /// sells the seats on board only
+    pub fn none() -> Self {
+        Self {
+            percentage: NO_OVERBOOKING_PERCENTAGE,
+            routes: vec![],
+            check_in_hours_before_departure: 24,
+        }
+    }
+
+    pub fn check_percentage(percentage: u16) -> Result<u16, OverbookingPolicyError> {
+        if percentage < NO_OVERBOOKING_PERCENTAGE {
+            return Err(OverbookingPolicyError::PercentageBelow100);
+        }
+
+        Ok(percentage)
+    }
+
+    pub fn percentage(&self, route: &FlightRoute) -> u16 {
+        self.routes.iter()
+            .find(|overbooking| &overbooking.route == route)
+            .map(|overbooking| overbooking.percentage)
+            .unwrap_or(self.percentage)
+    }
+
+    /// flights departing before this time are due for reconciliation
+    pub fn check_in_horizon(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + Duration::hours(self.check_in_hours_before_departure)
+    }
+}
+
+impl Default for OverbookingPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::FlightRoute;
+    use crate::policy::{OverbookingPolicy, OverbookingPolicyError, RouteOverbooking};
+
+    #[test]
+    fn routes_override_the_percentage() {
+        let policy = OverbookingPolicy::build(105, vec![
+            RouteOverbooking { route: route_eham_enli(), percentage: 120 },
+        ], 24).unwrap();
+
+        assert_eq!(policy.percentage(&route_eham_enli()), 120);
+        assert_eq!(policy.percentage(&route_enli_eham()), 105);
+        assert_eq!(OverbookingPolicy::none().percentage(&route_eham_enli()), 100);
+    }
+
+    #[test]
+    fn errors_on_malformed_policy() {
+        let result = OverbookingPolicy::build(95, vec![], 24);
+        assert_eq!(result, Err(OverbookingPolicyError::PercentageBelow100));
+
+        let result = OverbookingPolicy::build(110, vec![
+            RouteOverbooking { route: route_eham_enli(), percentage: 90 },
+        ], 24);
+        assert_eq!(result, Err(OverbookingPolicyError::PercentageBelow100));
+
+        let result = OverbookingPolicy::build(110, vec![], 0);
+        assert_eq!(result, Err(OverbookingPolicyError::CheckInNotBeforeDeparture));
+    }
+
+    // test data
+    fn route_eham_enli() -> FlightRoute {
+        FlightRoute::build("EHAM".parse().unwrap(), "ENLI".parse().unwrap()).unwrap()
+    }
+
+    fn route_enli_eham() -> FlightRoute {
+        FlightRoute::build("ENLI".parse().unwrap(), "EHAM".parse().unwrap()).unwrap()
+    }
+}
@@ -0,0 +1,344 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::data::{Currency, Money};
+use crate::aggregate::AccommodationId;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PricingPolicyError {
+    #[error("season ends before it starts")]
+    SeasonEndsBeforeItStarts,
+
+    #[error("seasons overlap")]
+    SeasonsOverlap,
+
+    #[error("season multiplier should be positive")]
+    SeasonMultiplierNotPositive,
+
+    #[error("refund percentage exceeds 100%")]
+    RefundPercentageExceeds100,
+
+    #[error("prices should all be in the same currency")]
+    MixedCurrencies,
+}
+
+/// multiplies the daily rates for the days of travel within the season
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Season {
+    pub from: NaiveDate,
+    /// inclusive
+    pub until: NaiveDate,
+    /// e.g. 125 in high season, 80 in low season
+    pub percentage: u16,
+}
+
+impl Season {
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.from <= date && date <= self.until
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccommodationSurcharge {
+    pub accommodation: AccommodationId,
+    pub price_per_night_per_passenger: Money,
+}
+
+/// applies to cancellations at least the number of days before departure
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RefundTier {
+    pub days_before_departure: i64,
+    pub refund_percentage: u8,
+}
+
+/// tariffs of a journey, see reservation_pricing_strategy for how they're applied
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "StoredPricingPolicy")]
+pub struct PricingPolicy {
+    adult_price_per_day: Money,
+    child_price_per_day: Money,
+    seasons: Vec<Season>,
+    accommodation_surcharges: Vec<AccommodationSurcharge>,
+    /// ordered by days before departure, descending
+    refund_tiers: Vec<RefundTier>,
+    /// charged per window seat chosen by the customer, automatically assigned seats are free
+    #[serde(default)]
+    window_seat_price: Option<Money>,
+}
+
+impl PricingPolicy {
+    pub fn build(
+        adult_price_per_day: Money,
+        child_price_per_day: Money,
+        seasons: Vec<Season>,
+        accommodation_surcharges: Vec<AccommodationSurcharge>,
+        mut refund_tiers: Vec<RefundTier>,
+    ) -> Result<Self, PricingPolicyError> {
+        let currency = adult_price_per_day.currency();
+        if child_price_per_day.currency() != currency
+            || accommodation_surcharges.iter().any(|surcharge| surcharge.price_per_night_per_passenger.currency() != currency) {
+            return Err(PricingPolicyError::MixedCurrencies);
+        }
+
+        for (i, season) in seasons.iter().enumerate() {
+            if season.until < season.from {
+                return Err(PricingPolicyError::SeasonEndsBeforeItStarts);
+            }
+            if season.percentage == 0 {
+                return Err(PricingPolicyError::SeasonMultiplierNotPositive);
+            }
+            if seasons[i + 1..].iter().any(|other| other.from <= season.until && season.from <= other.until) {
+                return Err(PricingPolicyError::SeasonsOverlap);
+            }
+        }
+
+        if refund_tiers.iter().any(|tier| tier.refund_percentage > 100) {
+            return Err(PricingPolicyError::RefundPercentageExceeds100);
+        }
+        refund_tiers.sort_by_key(|tier| std::cmp::Reverse(tier.days_before_departure));
+
+        Ok(Self {
+            adult_price_per_day,
+            child_price_per_day,
+            seasons,
+            accommodation_surcharges,
+            refund_tiers,
+            window_seat_price: None,
+        })
+    }
+
+    pub fn with_window_seat_price(self, price: Money) -> Result<Self, PricingPolicyError> {
+        if price.currency() != self.currency() {
+            return Err(PricingPolicyError::MixedCurrencies);
+        }
+
+        Ok(Self {
+            window_seat_price: Some(price),
+            ..self
+        })
+    }
+
+    /// replaces the surcharge of the accommodation, if any
+    pub fn with_accommodation_surcharge(mut self, surcharge: AccommodationSurcharge) -> Result<Self, PricingPolicyError> {
+        if surcharge.price_per_night_per_passenger.currency() != self.currency() {
+            return Err(PricingPolicyError::MixedCurrencies);
+        }
+
+        self.accommodation_surcharges.retain(|existing| existing.accommodation != surcharge.accommodation);
+        self.accommodation_surcharges.push(surcharge);
+
+        Ok(self)
+    }
+
+    /// the tariffs for journeys that aren't priced otherwise
+    pub fn standard() -> Self {
+        Self {
+            adult_price_per_day: Money::usd(12000),
+            child_price_per_day: Money::usd(12000),
+            seasons: vec![],
+            accommodation_surcharges: vec![],
+            refund_tiers: vec![
+                RefundTier { days_before_departure: 15, refund_percentage: 100 },
+                RefundTier { days_before_departure: 10, refund_percentage: 75 },
+                RefundTier { days_before_departure: 5, refund_percentage: 50 },
+            ],
+            window_seat_price: None,
+        }
+    }
+
+    /// all prices of the policy are in this currency
+    pub fn currency(&self) -> Currency {
+        self.adult_price_per_day.currency()
+    }
+
+    pub fn adult_price_per_day(&self) -> Money {
+        self.adult_price_per_day
+    }
+
+    pub fn child_price_per_day(&self) -> Money {
+        self.child_price_per_day
+    }
+
+    pub fn seasons(&self) -> &[Season] {
+        &self.seasons
+    }
+
+    pub fn accommodation_surcharges(&self) -> &[AccommodationSurcharge] {
+        &self.accommodation_surcharges
+    }
+
+    pub fn refund_tiers(&self) -> &[RefundTier] {
+        &self.refund_tiers
+    }
+
+    pub fn window_seat_price(&self) -> Option<Money> {
+        self.window_seat_price
+    }
+
+    /// 100% outside any season
+    pub fn season_percentage(&self, date: NaiveDate) -> u16 {
+        self.seasons.iter()
+            .find(|season| season.contains(date))
+            .map(|season| season.percentage)
+            .unwrap_or(100)
+    }
+
+    pub fn surcharge_per_night_per_passenger(&self, accommodation: &AccommodationId) -> Option<Money> {
+        self.accommodation_surcharges.iter()
+            .find(|surcharge| &surcharge.accommodation == accommodation)
+            .map(|surcharge| surcharge.price_per_night_per_passenger)
+    }
+
+    /// nothing is refunded when cancelled later than the last tier
+    pub fn refund_percentage(&self, days_before_departure: i64) -> u8 {
+        self.refund_tiers.iter()
+            .find(|tier| days_before_departure >= tier.days_before_departure)
+            .map(|tier| tier.refund_percentage)
+            .unwrap_or(0)
+    }
+}
+
+impl Default for PricingPolicy {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// as serialized, such that stored policies are validated like built ones
+#[derive(Deserialize)]
+struct StoredPricingPolicy {
+    adult_price_per_day: Money,
+    child_price_per_day: Money,
+    seasons: Vec<Season>,
+    accommodation_surcharges: Vec<AccommodationSurcharge>,
+    refund_tiers: Vec<RefundTier>,
+    #[serde(default)]
+    window_seat_price: Option<Money>,
+}
+
+impl TryFrom<StoredPricingPolicy> for PricingPolicy {
+    type Error = PricingPolicyError;
+
+    fn try_from(stored: StoredPricingPolicy) -> Result<Self, Self::Error> {
+        let policy = Self::build(
+            stored.adult_price_per_day,
+            stored.child_price_per_day,
+            stored.seasons,
+            stored.accommodation_surcharges,
+            stored.refund_tiers,
+        )?;
+
+        match stored.window_seat_price {
+            Some(price) => policy.with_window_seat_price(price),
+            None => Ok(policy),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use chrono::NaiveDate;
+    use prelude::data::{Currency, Money};
+    use crate::policy::{PricingPolicy, PricingPolicyError, RefundTier, Season};
+
+    #[test]
+    fn refund_tiers_are_ordered() {
+        let policy = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            vec![],
+            vec![],
+            vec![
+                RefundTier { days_before_departure: 7, refund_percentage: 50 },
+                RefundTier { days_before_departure: 30, refund_percentage: 100 },
+            ],
+        ).unwrap();
+
+        assert_eq!(policy.refund_percentage(31), 100);
+        assert_eq!(policy.refund_percentage(30), 100);
+        assert_eq!(policy.refund_percentage(29), 50);
+        assert_eq!(policy.refund_percentage(7), 50);
+        assert_eq!(policy.refund_percentage(6), 0);
+    }
+
+    #[test]
+    fn seasons_apply_within_their_dates() {
+        let policy = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            vec![high_season()],
+            vec![],
+            vec![],
+        ).unwrap();
+
+        assert_eq!(policy.season_percentage(date("2024-06-30")), 100);
+        assert_eq!(policy.season_percentage(date("2024-07-01")), 150);
+        assert_eq!(policy.season_percentage(date("2024-08-31")), 150);
+        assert_eq!(policy.season_percentage(date("2024-09-01")), 100);
+    }
+
+    #[test]
+    fn errors_on_malformed_policy() {
+        let result = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            vec![Season { from: date("2024-09-01"), until: date("2024-08-01"), percentage: 80 }],
+            vec![],
+            vec![],
+        );
+        assert_eq!(result, Err(PricingPolicyError::SeasonEndsBeforeItStarts));
+
+        let result = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            vec![high_season(), Season { from: date("2024-08-31"), until: date("2024-10-31"), percentage: 80 }],
+            vec![],
+            vec![],
+        );
+        assert_eq!(result, Err(PricingPolicyError::SeasonsOverlap));
+
+        let result = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            vec![],
+            vec![],
+            vec![RefundTier { days_before_departure: 7, refund_percentage: 101 }],
+        );
+        assert_eq!(result, Err(PricingPolicyError::RefundPercentageExceeds100));
+
+        let result = PricingPolicy::build(
+            Money::usd(100_00),
+            Money::from_minor_units(50_00, Currency::EUR),
+            vec![],
+            vec![],
+            vec![],
+        );
+        assert_eq!(result, Err(PricingPolicyError::MixedCurrencies));
+
+        let result = PricingPolicy::standard()
+            .with_window_seat_price(Money::from_minor_units(25_00, Currency::EUR));
+        assert_eq!(result, Err(PricingPolicyError::MixedCurrencies));
+    }
+
+    #[test]
+    fn stored_policy_is_validated() {
+        let policy = PricingPolicy::standard().with_window_seat_price(Money::usd(25_00)).unwrap();
+        let stored = serde_json::to_value(&policy).unwrap();
+        assert_eq!(serde_json::from_value::<PricingPolicy>(stored.clone()).unwrap(), policy);
+
+        let mut mixed = stored;
+        mixed["window_seat_price"] = serde_json::to_value(Money::from_minor_units(25_00, Currency::EUR)).unwrap();
+        let result = serde_json::from_value::<PricingPolicy>(mixed);
+        assert_eq!(result.unwrap_err().to_string(), PricingPolicyError::MixedCurrencies.to_string());
+    }
+
+    // test data
+    fn high_season() -> Season {
+        Season { from: date("2024-07-01"), until: date("2024-08-31"), percentage: 150 }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+}
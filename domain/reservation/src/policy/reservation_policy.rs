@@ -1,6 +1,6 @@
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use thiserror::Error;
-use crate::aggregate::{ConfirmedReservation, Itinerary, Passengers, Reservation};
+use crate::aggregate::{ConfirmedReservation, Contact, EmailAddress, Itinerary, Passengers, Reservation};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ReservationPolicyError {
@@ -17,22 +17,57 @@ pub enum ReservationPolicyError {
     NotAllowedToCancelReservationAnymore,
 
     #[error("malformed itinerary")]
-    MalformedItinerary
+    MalformedItinerary,
+
+    #[error("not allowed to access reservation")]
+    NotAllowedToAccessReservation,
+
+    #[error("reservation is held, confirm it first")]
+    ReservationIsHeld,
+
+    #[error("hold has expired")]
+    HoldExpired,
+
+    #[error("not allowed to hold reservation, only its contact or an agent is")]
+    NotAllowedToHoldReservation,
 }
 
 pub type ReservationRevisionResult<T> = Result<T, ReservationPolicyError>;
 
-#[derive(Clone, Copy)]
+/// on whose behalf a reservation is accessed
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReservationAccessor {
+    /// agents act on behalf of any contact
+    Agent,
+    /// proven to own the email address (e.g. by a verified token claim)
+    Contact(EmailAddress),
+    Anonymous,
+}
+
+#[derive(Clone)]
 pub struct ReservationPolicy {
     pub revise_days_before_departure: i64,
     pub cancel_days_before_departure: i64,
+    /// how long seats are held, before the reservation needs to be confirmed
+    pub hold_minutes: i64,
+    pub accessor: ReservationAccessor,
 }
 
 impl ReservationPolicy {
+    /// anonymous passengers can only confirm new reservations
     pub fn passenger() -> Self {
         Self {
             revise_days_before_departure: 7,
             cancel_days_before_departure: 1,
+            hold_minutes: 15,
+            accessor: ReservationAccessor::Anonymous,
+        }
+    }
+
+    pub fn passenger_with_email(email: EmailAddress) -> Self {
+        Self {
+            accessor: ReservationAccessor::Contact(email),
+            ..Self::passenger()
         }
     }
 
@@ -40,6 +75,8 @@ impl ReservationPolicy {
         Self {
             revise_days_before_departure: -365, // 1 year after
             cancel_days_before_departure: -365,
+            hold_minutes: 60,
+            accessor: ReservationAccessor::Agent,
         }
     }
 
@@ -48,6 +85,8 @@ impl ReservationPolicy {
         Self {
             revise_days_before_departure: i64::MIN,
             cancel_days_before_departure: i64::MIN,
+            hold_minutes: 15,
+            accessor: ReservationAccessor::Agent,
         }
     }
 }
@@ -64,9 +103,52 @@ impl ReservationPolicy {
         }
     }
 
+    /// a hold is to be confirmed and paid later on, hence only by those who can access it
+    pub fn new_held<F, R>(&self, reservation: ConfirmedReservation, f: F) -> ReservationRevisionResult<R>
+        where F: FnOnce(ConfirmedReservation) -> R {
+
+        self.authorize_contact(&reservation.contact)
+            .map_err(|_| ReservationPolicyError::NotAllowedToHoldReservation)?;
+
+        self.new_confirmed(reservation, f)
+    }
+
+    pub fn hold_expires_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + Duration::minutes(self.hold_minutes)
+    }
+
+    /// confirming a confirmed reservation has no effect
+    pub fn confirm_hold<F, R>(&self, reservation: Reservation, now: DateTime<Utc>, f: F) -> ReservationRevisionResult<R>
+        where F: FnOnce(Reservation) -> R {
+
+        self.authorize(&reservation)?;
+
+        match reservation {
+            Reservation::Held(held) if held.expires_at <= now => {
+                Err(ReservationPolicyError::HoldExpired)
+            }
+
+            held@Reservation::Held(_) | held@Reservation::Confirmed(_) => {
+                Ok(f(held))
+            }
+
+            Reservation::Cancelled(_) => {
+                Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
+            }
+        }
+    }
+
+    pub fn view(&self, reservation: Reservation) -> ReservationRevisionResult<Reservation> {
+        self.authorize(&reservation)?;
+
+        Ok(reservation)
+    }
+
     pub fn revise_passengers<F, R>(&self, reservation: Reservation, passengers: Passengers, f: F) -> ReservationRevisionResult<R>
         where F: FnOnce(Reservation, Passengers) -> R {
 
+        self.authorize(&reservation)?;
+
         match reservation {
             Reservation::Confirmed(reservation) => {
                 if self.within_revision_period(&reservation.itinerary) {
@@ -78,6 +160,10 @@ impl ReservationPolicy {
                 }
             }
 
+            Reservation::Held(_) => {
+                Err(ReservationPolicyError::ReservationIsHeld)
+            }
+
             Reservation::Cancelled(_) => {
                 Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
             }
@@ -87,6 +173,8 @@ impl ReservationPolicy {
     pub fn revise_itinerary<F, R>(&self, reservation: Reservation, itinerary: Itinerary, f: F) -> ReservationRevisionResult<R>
         where F: FnOnce(Reservation, Itinerary) -> R {
 
+        self.authorize(&reservation)?;
+
         match reservation {
             Reservation::Confirmed(reservation) => {
                 if self.within_revision_period(&reservation.itinerary) && self.within_revision_period(&itinerary) {
@@ -98,6 +186,44 @@ impl ReservationPolicy {
                 }
             }
 
+            Reservation::Held(_) => {
+                Err(ReservationPolicyError::ReservationIsHeld)
+            }
+
+            Reservation::Cancelled(_) => {
+                Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
+            }
+        }
+    }
+
+    /// contact details stay revisable, e.g. to be informed about disruptions
+    pub fn revise_contact<F, R>(&self, reservation: Reservation, contact: Contact, f: F) -> ReservationRevisionResult<R>
+        where F: FnOnce(Reservation, Contact) -> R {
+
+        self.authorize(&reservation)?;
+
+        match reservation {
+            confirmed@Reservation::Confirmed(_) | confirmed@Reservation::Held(_) => {
+                Ok(f(confirmed, contact))
+            }
+
+            Reservation::Cancelled(_) => {
+                Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
+            }
+        }
+    }
+
+    /// seats can be chosen whenever passengers are listed, e.g. up to check-in
+    pub fn assign_seats<F, R>(&self, reservation: Reservation, f: F) -> ReservationRevisionResult<R>
+        where F: FnOnce(Reservation) -> R {
+
+        self.authorize(&reservation)?;
+
+        match reservation {
+            confirmed@Reservation::Confirmed(_) | confirmed@Reservation::Held(_) => {
+                Ok(f(confirmed))
+            }
+
             Reservation::Cancelled(_) => {
                 Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
             }
@@ -107,6 +233,8 @@ impl ReservationPolicy {
     pub fn cancel<F, R>(&self, reservation: Reservation, f: F) -> ReservationRevisionResult<R>
         where F: FnOnce(Reservation) -> R {
 
+        self.authorize(&reservation)?;
+
         match reservation {
             Reservation::Confirmed(reservation) => {
                 if self.within_cancellation_period(&reservation.itinerary) {
@@ -118,12 +246,29 @@ impl ReservationPolicy {
                 }
             }
 
+            // a hold can always be abandoned
+            held@Reservation::Held(_) => {
+                Ok(f(held))
+            }
+
             Reservation::Cancelled(_) => {
                 Err(ReservationPolicyError::ReservationIsAlreadyCancelled)
             }
         }
     }
 
+    fn authorize(&self, reservation: &Reservation) -> ReservationRevisionResult<()> {
+        self.authorize_contact(reservation.contact())
+    }
+
+    fn authorize_contact(&self, contact: &Contact) -> ReservationRevisionResult<()> {
+        match &self.accessor {
+            ReservationAccessor::Agent => Ok(()),
+            ReservationAccessor::Contact(email) if email.address() == contact.email.address() => Ok(()),
+            _ => Err(ReservationPolicyError::NotAllowedToAccessReservation),
+        }
+    }
+
     fn within_revision_period(&self, itinerary: &Itinerary) -> bool {
         let today = Utc::now().date_naive();
         let days_before_departure = (itinerary.departure_date() - today).num_days();
@@ -145,7 +290,7 @@ mod tests {
     use std::collections::{LinkedList};
     use chrono::{Days, NaiveDate, Utc};
     use prelude::collection::SortedSet;
-    use crate::aggregate::{Contact, Flight, FlightRoute, Itinerary, Journey, JourneyId, PassengerArrangement, Passengers, Reservation, ReservationId, Segment};
+    use crate::aggregate::{Contact, EmailAddress, Flight, FlightRoute, Itinerary, Journey, JourneyShape, JourneyId, PassengerArrangement, Passengers, Reservation, ReservationId, Segment};
     use crate::policy::{ReservationPolicy, ReservationPolicyError};
 
     #[test]
@@ -157,7 +302,7 @@ mod tests {
         let reservation = Reservation::new_confirmed(
             &ReservationPolicy::passenger(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
@@ -169,7 +314,7 @@ mod tests {
         let reservation = Reservation::new_confirmed(
             &ReservationPolicy::passenger(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
@@ -186,14 +331,14 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
         ).unwrap();
 
         let revised = reservation.revise_passengers(
-            &ReservationPolicy::passenger(),
+            &ReservationPolicy::passenger_with_email(email()),
             passengers2()
         );
         assert!(revised.is_ok());
@@ -203,14 +348,14 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
         ).unwrap();
 
         let revised = reservation.revise_passengers(
-            &ReservationPolicy::passenger(),
+            &ReservationPolicy::passenger_with_email(email()),
             passengers2()
         );
         assert_eq!(revised, Err(ReservationPolicyError::NotAllowedToReviseReservationAnymore));
@@ -225,13 +370,13 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
         ).unwrap();
 
-        let cancelled = reservation.cancel(&ReservationPolicy::passenger());
+        let cancelled = reservation.cancel(&ReservationPolicy::passenger_with_email(email()));
         assert!(cancelled.is_ok());
 
         // 6 days before is not allowed
@@ -239,16 +384,65 @@ mod tests {
         let (reservation, _) = Reservation::new_confirmed(
             &ReservationPolicy::test(),
             id(),
-            journey_id(),
+            &journey(),
             contact(),
             passengers(),
             itinerary(departure_date)
         ).unwrap();
 
-        let cancelled = reservation.cancel(&ReservationPolicy::passenger());
+        let cancelled = reservation.cancel(&ReservationPolicy::passenger_with_email(email()));
         assert_eq!(cancelled, Err(ReservationPolicyError::NotAllowedToCancelReservationAnymore));
     }
 
+    #[test]
+    fn only_contact_and_agents_can_access_reservation() {
+        let departure_date = Utc::now().date_naive().checked_add_days(Days::new(30)).unwrap();
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::passenger(),
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary(departure_date)
+        ).unwrap();
+
+        // email address is compared case-insensitive
+        let result = ReservationPolicy::passenger_with_email("N.Bergsma@internet.com".parse().unwrap())
+            .view(reservation.clone());
+        assert!(result.is_ok());
+
+        let result = ReservationPolicy::agent().view(reservation.clone());
+        assert!(result.is_ok());
+
+        let result = ReservationPolicy::passenger().view(reservation.clone());
+        assert_eq!(result.err(), Some(ReservationPolicyError::NotAllowedToAccessReservation));
+
+        let other = ReservationPolicy::passenger_with_email("j.doe@internet.com".parse().unwrap());
+        assert_eq!(other.view(reservation.clone()).err(), Some(ReservationPolicyError::NotAllowedToAccessReservation));
+        assert_eq!(reservation.clone().revise_passengers(&other, passengers2()).err(), Some(ReservationPolicyError::NotAllowedToAccessReservation));
+        assert_eq!(reservation.cancel(&other).err(), Some(ReservationPolicyError::NotAllowedToAccessReservation));
+    }
+
+    #[test]
+    fn only_contact_and_agents_can_hold_reservation() {
+        let departure_date = Utc::now().date_naive().checked_add_days(Days::new(30)).unwrap();
+        let hold = |policy: &ReservationPolicy| Reservation::new_held(
+            policy,
+            id(),
+            &journey(),
+            contact(),
+            passengers(),
+            itinerary(departure_date)
+        ).map(|_| ());
+
+        assert_eq!(hold(&ReservationPolicy::passenger_with_email(email())), Ok(()));
+        assert_eq!(hold(&ReservationPolicy::agent()), Ok(()));
+
+        // would never be able to pay or confirm it
+        assert_eq!(hold(&ReservationPolicy::passenger()), Err(ReservationPolicyError::NotAllowedToHoldReservation));
+        assert_eq!(hold(&ReservationPolicy::passenger_with_email("j.doe@internet.com".parse().unwrap())), Err(ReservationPolicyError::NotAllowedToHoldReservation));
+    }
+
     fn id() -> ReservationId {
         "5FFFciXgSxB70tAE8iERl6".parse().unwrap()
     }
@@ -265,11 +459,15 @@ mod tests {
     fn contact() -> Contact {
         Contact::new(
             "Niels Bergsma".parse().unwrap(),
-            "n.bergsma@internet.com".parse().unwrap(),
+            email(),
             None,
         )
     }
 
+    fn email() -> EmailAddress {
+        "n.bergsma@internet.com".parse().unwrap()
+    }
+
     fn passengers() -> PassengerArrangement {
         PassengerArrangement::build(2, 0).unwrap()
     }
@@ -286,6 +484,7 @@ mod tests {
         let (journey, _) = Journey::build(
             journey_id(),
             "Journey Around North Atlantic".parse().unwrap(),
+            JourneyShape::RoundTrip,
             SortedSet::empty()
                 .insert(Segment::build(route_eham_enli(), SortedSet::empty()).unwrap())
                 .insert(Segment::build(route_enli_eham(), SortedSet::empty()).unwrap())
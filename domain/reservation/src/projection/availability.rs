@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::Formatter;
 use std::hash::{Hash, Hasher};
-use chrono::{Datelike, DateTime, FixedOffset, Month, NaiveDate};
+use chrono::{Datelike, DateTime, FixedOffset, Month, NaiveDate, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{Error, SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use crate::event::Event;
 
@@ -32,8 +34,19 @@ impl Availability {
                     departure: flight.departure,
                     arrival: flight.arrival,
                     seats_available: flight.seats_available,
+                    seats: flight.seats,
+                    sellable_seats: flight.sellable_seats,
+                    sellable_seats_available: flight.sellable_seats_available,
                 };
 
+                if flight.cancelled || YearMonth::from_datetime(flight.departure) != self.period {
+                    // cancelled flights are no longer offered, rescheduled flights may have moved to another period
+                    if let Some(route_availability) = self.flights.get_mut(&route) {
+                        route_availability.remove(&availability);
+                    }
+                    return self;
+                }
+
                 self.flights.entry(route)
                     .and_modify(|a| { a.replace(availability.clone()); })
                     .or_insert(HashSet::from([availability]));
@@ -46,9 +59,16 @@ impl Availability {
 
         self
     }
+
+    /// whether a flight on the route, departing after the given time, has the number of seats for sale
+    pub fn offers(&self, departure: &str, arrival: &str, seats: u8, after: DateTime<Utc>) -> bool {
+        self.flights.get(&FlightRoute::from_airfields(departure, arrival))
+            .map(|flights| flights.iter().any(|flight| flight.departure > after && flight.sellable_seats_available >= seats))
+            .unwrap_or(false)
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
 pub struct YearMonth(i32, Month);
 
 impl YearMonth {
@@ -78,18 +98,26 @@ struct FlightRoute(String);
 
 impl FlightRoute {
     fn from_aggregate(value: crate::aggregate::FlightRoute) -> Self {
-        Self([value.departure.to_string(), "-".to_owned(), value.arrival.to_string()].concat())
+        Self::from_airfields(&value.departure.to_string(), &value.arrival.to_string())
+    }
+
+    fn from_airfields(departure: &str, arrival: &str) -> Self {
+        Self([departure, "-", arrival].concat())
     }
 }
 
 type FlightRouteAvailability = HashSet<FlightAvailability>;
 
+/// physical capacity (seats on board) and sellable capacity (including overbooking) are shown separately
 #[derive(Eq, Clone)]
 struct FlightAvailability {
     id: String,
     departure: DateTime<FixedOffset>,
     arrival: DateTime<FixedOffset>,
-    seats_available: u8
+    seats_available: u8,
+    seats: u8,
+    sellable_seats: u8,
+    sellable_seats_available: u8,
 }
 
 impl PartialEq for FlightAvailability{
@@ -106,24 +134,52 @@ impl Hash for FlightAvailability {
 
 impl Serialize for FlightAvailability {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        let mut seq = serializer.serialize_seq(Some(4))?;
+        let mut seq = serializer.serialize_seq(Some(7))?;
         seq.serialize_element(&self.id)?;
         seq.serialize_element(&self.departure)?;
         seq.serialize_element(&self.arrival)?;
         seq.serialize_element(&self.seats_available)?;
+        seq.serialize_element(&self.seats)?;
+        seq.serialize_element(&self.sellable_seats)?;
+        seq.serialize_element(&self.sellable_seats_available)?;
         seq.end()
     }
 }
 
 impl <'de> Deserialize<'de> for FlightAvailability {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
-        let fields: (String, DateTime<FixedOffset>, DateTime<FixedOffset>, u8) = Deserialize::deserialize(deserializer)?;
+        deserializer.deserialize_seq(FlightAvailabilityVisitor)
+    }
+}
+
+/// capacities are trailing elements, absent in availability stored before overbooking
+struct FlightAvailabilityVisitor;
+
+impl <'de> Visitor<'de> for FlightAvailabilityVisitor {
+    type Value = FlightAvailability;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of id, departure, arrival and seats available, followed by capacities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+        let id = seq.next_element()?.ok_or_else(|| Error::invalid_length(0, &self))?;
+        let departure = seq.next_element()?.ok_or_else(|| Error::invalid_length(1, &self))?;
+        let arrival = seq.next_element()?.ok_or_else(|| Error::invalid_length(2, &self))?;
+        let seats_available = seq.next_element()?.ok_or_else(|| Error::invalid_length(3, &self))?;
+
+        let seats = seq.next_element()?.unwrap_or(seats_available);
+        let sellable_seats = seq.next_element()?.unwrap_or(seats);
+        let sellable_seats_available = seq.next_element()?.unwrap_or(seats_available);
 
         Ok(FlightAvailability {
-            id: fields.0,
-            departure: fields.1,
-            arrival: fields.2,
-            seats_available: fields.3,
+            id,
+            departure,
+            arrival,
+            seats_available,
+            seats,
+            sellable_seats,
+            sellable_seats_available,
         })
     }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use std::rc::Rc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use crate::event::{Event, JourneyPublishedV1};
+use prelude::collection::SortedSet;
+use crate::event::Event;
+use crate::projection::Availability;
 use crate::repository::AirfieldRepository;
 use crate::aggregate;
 
@@ -17,6 +20,14 @@ pub enum JourneysError {
 
 pub type JourneysApplyResult<T> = Result<T, JourneysError>;
 
+/// criteria to search the catalogue by, next to the travel month of the availability searched
+pub struct JourneysFilter {
+    /// id of the airfield the journey departs from, any when absent
+    pub departure: Option<String>,
+    pub seats: u8,
+    pub now: DateTime<Utc>,
+}
+
 #[derive(Serialize, Deserialize, Default)]
 pub struct Journeys(Vec<Journey>);
 
@@ -24,12 +35,27 @@ impl Journeys {
     pub async fn apply(mut self, context: &JourneysApplyContext, event: Event) -> JourneysApplyResult<Journeys> {
         match event {
             Event::JourneyPublishedV1(event) => {
-                let journey = Journey::from(context, event).await?;
+                let journey = Journey::from(context, event.id, event.name, event.shape, event.segments).await?;
 
                 self.0.retain(|j| j.id != journey.id);
                 self.0.push(journey);
             }
 
+            Event::JourneyRevisedV1(event) => {
+                let journey = Journey::from(context, event.id, event.name, event.shape, event.segments).await?;
+
+                match self.0.iter_mut().find(|j| j.id == journey.id) {
+                    Some(existing) => *existing = journey,
+                    None => self.0.push(journey),
+                }
+            }
+
+            Event::JourneyWithdrawnV1(event) => {
+                let id = event.id.to_string();
+
+                self.0.retain(|j| j.id != id);
+            }
+
             _ => {
                 // ignore unknown event types
             }
@@ -37,21 +63,40 @@ impl Journeys {
 
         Ok(self)
     }
+
+    pub fn find(self, id: &str) -> Option<Journey> {
+        self.0.into_iter().find(|journey| journey.id == id)
+    }
+
+    /// journeys that can actually be booked in the period of the availability
+    pub fn filter(self, filter: &JourneysFilter, availability: &Availability) -> Self {
+        Self(self.0.into_iter()
+            .filter(|journey| journey.is_bookable(filter, availability))
+            .collect())
+    }
 }
 
 #[derive(Serialize, Deserialize)]
-struct Journey {
+pub struct Journey {
     id: String,
     name: String,
+    #[serde(default)]
+    shape: aggregate::JourneyShape,
     segments: Vec<Segment>,
 }
 
 impl Journey {
-    async fn from(context: &JourneysApplyContext, value: JourneyPublishedV1) -> JourneysApplyResult<Self> {
-        let id = value.id.to_string();
-        let name = value.name.to_string();
+    async fn from(
+        context: &JourneysApplyContext,
+        id: aggregate::JourneyId,
+        name: aggregate::JourneyName,
+        shape: aggregate::JourneyShape,
+        value: SortedSet<aggregate::Segment>) -> JourneysApplyResult<Self> {
+
+        let id = id.to_string();
+        let name = name.to_string();
         let mut segments = Vec::new();
-        for segment in value.segments.into_iter() {
+        for segment in value.into_iter() {
             segments.push(
                 Segment::from(context, segment).await?
             );
@@ -60,9 +105,17 @@ impl Journey {
         Ok(Self {
             id,
             name,
+            shape,
             segments
         })
     }
+
+    /// bookable when the first flight, from the airfield departed from, has the seats for sale
+    fn is_bookable(&self, filter: &JourneysFilter, availability: &Availability) -> bool {
+        self.segments.iter()
+            .filter(|segment| filter.departure.as_ref().is_none_or(|departure| &segment.flight.departure.id == departure))
+            .any(|segment| availability.offers(&segment.flight.departure.id, &segment.flight.arrival.id, filter.seats, filter.now))
+    }
 }
 
 #[derive(Serialize, Deserialize)]
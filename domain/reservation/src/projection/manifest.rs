@@ -0,0 +1,359 @@
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use prelude::domain::Version;
+use crate::aggregate;
+use crate::event::Event;
+use crate::repository::ReservationRepository;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ManifestError {
+    #[error("unable to resolve reservation: {0}")]
+    UnableToResolveReservation(aggregate::ReservationId),
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+pub type ManifestApplyResult<T> = Result<T, ManifestError>;
+
+/// passengers on board of a flight, e.g. for ground operations and border control
+#[derive(Serialize, Deserialize)]
+pub struct Manifest {
+    flight: String,
+    passengers: Vec<Passenger>,
+}
+
+impl Manifest {
+    pub fn from_flight(flight: &aggregate::FlightId) -> Self {
+        Self {
+            flight: flight.to_string(),
+            passengers: Vec::new(),
+        }
+    }
+
+    pub fn flight(&self) -> &str {
+        &self.flight
+    }
+
+    /// the passengers of a reservation are (re)listed whenever its seats are reserved, e.g. after a revision
+    pub async fn apply(mut self, context: &ManifestApplyContext, event: &Event) -> ManifestApplyResult<Self> {
+        match event {
+            Event::FlightReservedV1(event) if event.flight == self.flight => {
+                let id = event.reservation.value_ref().to_string();
+
+                // redelivered events of an earlier version of the reservation
+                if self.passengers.iter().any(|passenger| passenger.reservation == id && passenger.version > event.reservation.version()) {
+                    return Ok(self);
+                }
+
+                self.passengers.retain(|passenger| passenger.reservation != id);
+
+                if !event.annulled() {
+                    let reservation = context.resolve_reservation(event.reservation.value_ref()).await?;
+                    self.passengers.extend(Passenger::from_reservation(&reservation, &event.flight));
+                }
+            }
+
+            Event::SeatsAssignedV1(event) if event.flight == self.flight => {
+                let id = event.reservation.to_string();
+
+                for passenger in self.passengers.iter_mut().filter(|passenger| passenger.reservation == id) {
+                    passenger.seat = event.seats.iter()
+                        .find(|seat| passenger.name.as_ref() == Some(&seat.passenger.to_string()))
+                        .map(|seat| seat.seat.to_string());
+                }
+            }
+
+            Event::PassengersCheckedInV1(event) if event.flight == self.flight => {
+                self.set_status(&event.reservation.to_string(), &event.passengers, Status::CheckedIn);
+            }
+
+            Event::PassengerBoardedV1(event) if event.flight == self.flight => {
+                self.set_status(&event.reservation.to_string(), std::slice::from_ref(&event.passenger), Status::Boarded);
+            }
+
+            Event::PassengersNoShowV1(event) if event.flight == self.flight => {
+                self.set_status(&event.reservation.to_string(), &event.passengers, Status::NoShow);
+            }
+
+            Event::ReservationContactRevisedV1(event) => {
+                let id = event.id.to_string();
+
+                for passenger in self.passengers.iter_mut().filter(|passenger| passenger.reservation == id) {
+                    passenger.contact = event.contact.clone().into();
+                }
+            }
+
+            _ => {
+                // ignore unknown event types
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn set_status(&mut self, reservation: &str, names: &[aggregate::PersonFullName], status: Status) {
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+
+        for passenger in self.passengers.iter_mut().filter(|passenger| passenger.reservation == reservation) {
+            if passenger.name.as_ref().is_some_and(|name| names.contains(name)) {
+                passenger.status = status;
+            }
+        }
+    }
+
+    /// one line per passenger, preceded by a header
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("reservation,name,date_of_birth,seat,status,contact_name,contact_email,contact_phone\n");
+
+        for passenger in self.passengers.iter() {
+            let fields = [
+                passenger.reservation.clone(),
+                passenger.name.clone().unwrap_or_default(),
+                passenger.date_of_birth.map(|date| date.to_string()).unwrap_or_default(),
+                passenger.seat.clone().unwrap_or_default(),
+                passenger.status.to_string(),
+                passenger.contact.name.clone(),
+                passenger.contact.email.clone(),
+                passenger.contact.phone.clone().unwrap_or_default(),
+            ];
+
+            let line: Vec<String> = fields.iter()
+                .map(|field| csv_field(field))
+                .collect();
+
+            csv.push_str(&line.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+/// quoted when it contains a separator, quote or line break; a value a spreadsheet would take for a formula,
+/// e.g. a phone number or a name supplied by the customer, is prefixed with an apostrophe to keep it text
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        ["'", value].concat()
+    }
+    else {
+        value.to_owned()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        ["\"", &value.replace('"', "\"\""), "\""].concat()
+    }
+    else {
+        value
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Passenger {
+    reservation: String,
+    version: Version,
+    /// absent as long as the reservation only arranges the number of passengers
+    name: Option<String>,
+    date_of_birth: Option<NaiveDate>,
+    seat: Option<String>,
+    #[serde(default)]
+    status: Status,
+    contact: Contact,
+}
+
+impl Passenger {
+    fn from_reservation(reservation: &aggregate::Reservation, flight: &aggregate::FlightId) -> Vec<Self> {
+        let (passengers, seats, boarding) = match reservation {
+            aggregate::Reservation::Held(aggregate::HeldReservation { reservation, .. }) | aggregate::Reservation::Confirmed(reservation) => {
+                (&reservation.passengers, &reservation.seats, &reservation.boarding)
+            }
+
+            aggregate::Reservation::Cancelled(_) => {
+                return Vec::new();
+            }
+        };
+
+        let unnamed = Self {
+            reservation: reservation.id().to_string(),
+            version: reservation.version(),
+            name: None,
+            date_of_birth: None,
+            seat: None,
+            status: Status::Reserved,
+            contact: reservation.contact().clone().into(),
+        };
+
+        match passengers {
+            aggregate::Passengers::Arrangement(arrangement) => {
+                vec![unnamed; arrangement.count() as usize]
+            }
+
+            aggregate::Passengers::List(list) => {
+                list.iter()
+                    .map(|passenger| Self {
+                        name: Some(passenger.name().to_string()),
+                        date_of_birth: Some(passenger.date_of_birth()),
+                        seat: seats.iter()
+                            .find(|seat| &seat.flight == flight && &seat.passenger == passenger.name())
+                            .map(|seat| seat.seat.to_string()),
+                        status: boarding.iter()
+                            .find(|boarding| &boarding.flight == flight && &boarding.passenger == passenger.name())
+                            .map(|boarding| Status::from(&boarding.status))
+                            .unwrap_or(Status::Reserved),
+                        ..unnamed.clone()
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    #[default]
+    Reserved,
+    CheckedIn,
+    Boarded,
+    NoShow,
+}
+
+impl From<&aggregate::BoardingStatus> for Status {
+    fn from(value: &aggregate::BoardingStatus) -> Self {
+        match value {
+            aggregate::BoardingStatus::CheckedIn(_) => Self::CheckedIn,
+            aggregate::BoardingStatus::Boarded(_, _) => Self::Boarded,
+            aggregate::BoardingStatus::NoShow => Self::NoShow,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reserved => write!(f, "reserved"),
+            Self::CheckedIn => write!(f, "checked_in"),
+            Self::Boarded => write!(f, "boarded"),
+            Self::NoShow => write!(f, "no_show"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Contact {
+    name: String,
+    email: String,
+    phone: Option<String>,
+}
+
+impl From<aggregate::Contact> for Contact {
+    fn from(value: aggregate::Contact) -> Self {
+        Self {
+            name: value.name.to_string(),
+            email: value.email.to_string(),
+            phone: value.phone.map(|phone| phone.to_string()),
+        }
+    }
+}
+
+// apply context
+pub struct ManifestApplyContext {
+    reservation_repository: Rc<dyn ReservationRepository>
+}
+
+impl ManifestApplyContext {
+    pub fn new(reservation_repository: Rc<dyn ReservationRepository>) -> Self {
+        Self {
+            reservation_repository
+        }
+    }
+
+    /// manifests of the flights the event applies to
+    pub async fn affected_flights(&self, event: &Event) -> ManifestApplyResult<Vec<aggregate::FlightId>> {
+        match event {
+            Event::FlightReservedV1(event) => Ok(vec![event.flight.clone()]),
+            Event::SeatsAssignedV1(event) => Ok(vec![event.flight.clone()]),
+            Event::PassengersCheckedInV1(event) => Ok(vec![event.flight.clone()]),
+            Event::PassengerBoardedV1(event) => Ok(vec![event.flight.clone()]),
+            Event::PassengersNoShowV1(event) => Ok(vec![event.flight.clone()]),
+            Event::ReservationContactRevisedV1(event) => {
+                let reservation = self.resolve_reservation(&event.id).await?;
+                Ok(reserved_flights(&reservation))
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn resolve_reservation(&self, id: &aggregate::ReservationId) -> ManifestApplyResult<aggregate::Reservation> {
+        self.reservation_repository.get(id).await
+            .map_err(|error| ManifestError::IoError(error.to_string()))?
+            .ok_or(ManifestError::UnableToResolveReservation(*id))
+    }
+}
+
+fn reserved_flights(reservation: &aggregate::Reservation) -> Vec<aggregate::FlightId> {
+    match reservation {
+        aggregate::Reservation::Held(aggregate::HeldReservation { reservation, .. }) | aggregate::Reservation::Confirmed(reservation) => {
+            reservation.itinerary.stages()
+                .filter(|stage| matches!(stage, aggregate::ItineraryStage::Reserved(_, _)))
+                .map(|stage| stage.flight())
+                .collect()
+        }
+
+        aggregate::Reservation::Cancelled(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use crate::command::AssignSeats;
+    use crate::in_memory::fixture::{flight_eham_enli, flight_enli_eham, TestContext};
+    use crate::policy::ReservationPolicy;
+    use crate::projection::manifest::csv_field;
+
+    #[test]
+    fn manifest_lists_passengers_of_reserved_flights() {
+        let context = TestContext::build();
+        let id = context.reserve(2);
+        let other = context.reserve(1);
+        context.list_passengers(id, ["Jane Doe", "John Doe"]);
+
+        block_on(context.reservation_usecase.assign_seats(AssignSeats {
+            reservation: id,
+            flight: flight_eham_enli().id,
+            seats: vec![("Jane Doe".parse().unwrap(), "1A".parse().unwrap())],
+        }, &ReservationPolicy::test())).unwrap();
+        context.deliver();
+
+        // passengers arranged by number only are listed without name
+        assert_eq!(context.manifest(&flight_eham_enli().id).to_csv(), [
+            "reservation,name,date_of_birth,seat,status,contact_name,contact_email,contact_phone\n",
+            &format!("{},,,,reserved,Niels Bergsma,n.bergsma@internet.com,\n", other),
+            &format!("{},Jane Doe,1980-01-01,1A,reserved,Niels Bergsma,n.bergsma@internet.com,\n", id),
+            &format!("{},John Doe,1980-01-01,1B,reserved,Niels Bergsma,n.bergsma@internet.com,\n", id),
+        ].concat());
+
+        context.cancel(other);
+
+        let csv = context.manifest(&flight_enli_eham().id).to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(!csv.contains(&other.to_string()));
+    }
+
+    #[test]
+    fn csv_fields_are_escaped() {
+        assert_eq!(csv_field("Jane Doe"), "Jane Doe");
+        assert_eq!(csv_field("Doe, Jane"), "\"Doe, Jane\"");
+        assert_eq!(csv_field("Jane \"JD\" Doe"), "\"Jane \"\"JD\"\" Doe\"");
+
+        // taken for a formula by spreadsheets otherwise
+        assert_eq!(csv_field("=HYPERLINK(\"https://example.com\")"), "\"'=HYPERLINK(\"\"https://example.com\"\")\"");
+        assert_eq!(csv_field("+31 20 123 4567"), "'+31 20 123 4567");
+        assert_eq!(csv_field("-1+1"), "'-1+1");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+    }
+}
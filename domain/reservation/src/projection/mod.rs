@@ -1,5 +1,7 @@
 mod journeys;
 mod availability;
+mod manifest;
 
 pub use journeys::*;
 pub use availability::*;
+pub use manifest::*;
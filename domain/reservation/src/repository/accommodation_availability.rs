@@ -0,0 +1,24 @@
+use thiserror::Error;
+use prelude::async_trait;
+use prelude::domain::{Transaction};
+use crate::aggregate::{AccommodationAvailability, AccommodationId};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AccommodationAvailabilityRepositoryError {
+    #[error("I/O error {0}")]
+    IoError(String),
+
+    #[error("not found")]
+    NotFound,
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AccommodationAvailabilityRepository {
+    async fn get(&self, id: &AccommodationId) -> Result<Option<AccommodationAvailability>, AccommodationAvailabilityRepositoryError>;
+    async fn set_begin(&self, id: &AccommodationId) -> Result<Transaction<AccommodationId, AccommodationAvailability>, AccommodationAvailabilityRepositoryError>;
+    async fn set_commit(&self, transaction: Transaction<AccommodationId, AccommodationAvailability>) -> Result<(), AccommodationAvailabilityRepositoryError>;
+}
@@ -1,7 +1,8 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 use prelude::async_trait;
 use prelude::domain::{Transaction};
-use crate::aggregate::{FlightAvailability, FlightId};
+use crate::aggregate::{FlightAvailability, FlightId, FlightRoute};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum FlightAvailabilityRepositoryError {
@@ -21,4 +22,6 @@ pub trait FlightAvailabilityRepository {
     async fn get(&self, id: &FlightId) -> Result<Option<FlightAvailability>, FlightAvailabilityRepositoryError>;
     async fn set_begin(&self, id: &FlightId) -> Result<Transaction<FlightId, FlightAvailability>, FlightAvailabilityRepositoryError>;
     async fn set_commit(&self, transaction: Transaction<FlightId, FlightAvailability>) -> Result<(), FlightAvailabilityRepositoryError>;
+    async fn list_due_for_reconciliation(&self, departing_before: DateTime<Utc>) -> Result<Vec<FlightId>, FlightAvailabilityRepositoryError>;
+    async fn list_by_route(&self, route: &FlightRoute, departing_after: DateTime<Utc>, departing_before: DateTime<Utc>) -> Result<Vec<FlightAvailability>, FlightAvailabilityRepositoryError>;
 }
@@ -1,9 +1,11 @@
 mod journey;
 mod flight_availability;
+mod accommodation_availability;
 mod reservation;
 mod airfield;
 
 pub use journey::*;
 pub use flight_availability::*;
+pub use accommodation_availability::*;
 pub use reservation::*;
 pub use airfield::*;
\ No newline at end of file
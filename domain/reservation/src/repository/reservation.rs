@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 use prelude::async_trait;
 use prelude::domain::{Transaction};
@@ -21,4 +22,6 @@ pub trait ReservationRepository {
     async fn get(&self, id: &ReservationId) -> Result<Option<Reservation>, ReservationRepositoryError>;
     async fn set_begin(&self, id: &ReservationId) -> Result<Transaction<ReservationId, Reservation>, ReservationRepositoryError>;
     async fn set_commit(&self, transaction: Transaction<ReservationId, Reservation>) -> Result<(), ReservationRepositoryError>;
+    /// held reservations that expired at the given time
+    async fn list_expired_holds(&self, now: DateTime<Utc>) -> Result<Vec<ReservationId>, ReservationRepositoryError>;
 }
@@ -1,23 +1,25 @@
 use std::slice::Iter;
 use prelude::domain::{Version, Versioned};
-use crate::aggregate::{Itinerary, ItineraryStage, Passengers, Reservation, ReservationId, Revision};
+use crate::aggregate::{HeldReservation, Itinerary, ItineraryStage, Passengers, Reservation, ReservationId, Revision};
 use crate::event::FlightReservationRequestedV1;
 
 /// next request inspects the current state of a reservation against
 /// the target state, and returns a single action towards that.
 ///
-/// for a confirmed reservation that means:
-/// - stages in latest itinerary = reserved
+/// for a confirmed, or held, reservation that means:
+/// - stages in latest itinerary = reserved (unless disrupted, which awaits rebooking)
 /// - stages in older revisions = annulled
 ///
 /// for a cancelled reservation that means:
 /// - all stages from older revisions = annulled
+///
+/// the rooms of a stage's stay are part of the stage, and reserved or annulled along
 pub fn next_request(reservation: &Reservation) -> Option<FlightReservationRequestedV1> {
     let id = reservation.id();
     let version = reservation.version();
 
     match reservation {
-        Reservation::Confirmed(reservation) => {
+        Reservation::Held(HeldReservation { reservation, .. }) | Reservation::Confirmed(reservation) => {
             next_reserved(&id, &version, &reservation.itinerary, &reservation.passengers)
                 .or_else(|| next_annulment(&id, reservation.revisions.iter()))
         }
@@ -30,17 +32,21 @@ pub fn next_request(reservation: &Reservation) -> Option<FlightReservationReques
 
 fn next_reserved(id: &ReservationId, version: &Version, itinerary: &Itinerary, passengers: &Passengers,) -> Option<FlightReservationRequestedV1> {
     itinerary.stages()
-        .filter_map(|stage| {
+        .zip(itinerary.stays())
+        .filter_map(|(stage, stay)| {
             match stage {
                 ItineraryStage::Planned(flight, _) | ItineraryStage::Annulled(flight, _) => {
                     Some(FlightReservationRequestedV1 {
                         reservation: Versioned::from_version(*id, *version),
                         flight: flight.id.clone(),
                         seats: passengers.count(),
+                        stay,
                     })
                 }
 
-                ItineraryStage::Reserved(_, _) | ItineraryStage::ReservedFailed(_, _, _)=> {
+                ItineraryStage::Reserved(_, _)
+                | ItineraryStage::ReservedFailed(_, _, _)
+                | ItineraryStage::Disrupted(_, _, _) => {
                     None
                 }
             }
@@ -51,17 +57,19 @@ fn next_reserved(id: &ReservationId, version: &Version, itinerary: &Itinerary, p
 fn next_annulment(id: &ReservationId, revisions: Iter<Revision>) -> Option<FlightReservationRequestedV1> {
     revisions
         .flat_map(|(version, _, itinerary)| {
-            itinerary.stages().map(move |stage| (version, stage))
+            itinerary.stages().zip(itinerary.stays()).map(move |(stage, stay)| (version, stage, stay))
         })
-        .filter_map(|(version, stage)| {
+        .filter_map(|(version, stage, stay)| {
             match stage {
                 ItineraryStage::Planned(flight, _)
                 | ItineraryStage::Reserved(flight, _)
-                | ItineraryStage::ReservedFailed(flight, _, _) => {
+                | ItineraryStage::ReservedFailed(flight, _, _)
+                | ItineraryStage::Disrupted(flight, _, _) => {
                     Some(FlightReservationRequestedV1 {
                         reservation: Versioned::from_version(*id, *version),
                         flight: flight.clone().id,
                         seats: 0,
+                        stay,
                     })
                 }
 
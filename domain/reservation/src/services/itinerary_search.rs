@@ -0,0 +1,269 @@
+use std::cmp::Ordering;
+use std::collections::LinkedList;
+use chrono::NaiveDate;
+use prelude::data::Money;
+use crate::aggregate::{Accommodation, Flight, FlightAvailability, Itinerary, Journey, PassengerArrangement, Segment, JOURNEY_MAX_DAYS_IN_ACCOMMODATION, JOURNEY_MIN_DAYS_IN_ACCOMMODATION};
+use crate::policy::PricingPolicy;
+use crate::services::reservation_pricing_strategy::{self, PriceBreakdown};
+
+/// itineraries may deviate this many days from the preferred length
+pub const ITINERARY_SEARCH_DAYS_TOLERANCE: i64 = 7;
+pub const ITINERARY_SEARCH_MAX_WINDOW_DAYS: i64 = 31;
+pub const ITINERARY_SEARCH_MAX_RESULTS: usize = 10;
+
+/// bounds the enumeration on journeys with many segments and flights
+const MAX_CANDIDATES: usize = 1000;
+
+#[derive(Debug, PartialEq)]
+pub struct ItineraryCandidate {
+    pub itinerary: Itinerary,
+    pub price: PriceBreakdown,
+}
+
+/// bookable itineraries along the route, departing within the window;
+/// ranked by how close they are to the preferred number of days, then by price
+pub fn search(
+    journey: &Journey,
+    route: &LinkedList<&Segment>,
+    flights: &[FlightAvailability],
+    passengers: &PassengerArrangement,
+    window: (NaiveDate, NaiveDate),
+    days: i64) -> Vec<ItineraryCandidate> {
+
+    let stages: Vec<(Vec<&Flight>, Option<Accommodation>)> = route.iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let mut candidates: Vec<&Flight> = flights.iter()
+                .filter(|availability| availability.flight.route == segment.flight)
+                .filter(|availability| availability.sellable_seats_available() >= passengers.count())
+                .map(|availability| &availability.flight)
+                .collect();
+            candidates.sort_by_key(|flight| flight.departure);
+
+            // no stay after the last flight
+            let stay = if i + 1 < route.len() {
+                cheapest_accommodation(segment, journey.pricing_policy())
+            }
+            else {
+                None
+            };
+
+            (candidates, stay)
+        })
+        .collect();
+
+    let search = Search {
+        stages: &stages,
+        window,
+        max_days: days + ITINERARY_SEARCH_DAYS_TOLERANCE,
+    };
+
+    let mut combinations = Vec::new();
+    search.extend(&mut Vec::new(), &mut combinations);
+
+    let mut candidates: Vec<ItineraryCandidate> = combinations.into_iter()
+        .filter_map(|stages| journey.parse_itinerary(stages).ok())
+        .map(|itinerary| ItineraryCandidate {
+            price: reservation_pricing_strategy::quote(&itinerary, passengers, journey.pricing_policy()),
+            itinerary,
+        })
+        .filter(|candidate| (candidate.price.days - days).abs() <= ITINERARY_SEARCH_DAYS_TOLERANCE)
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        (a.price.days - days).abs().cmp(&(b.price.days - days).abs())
+            .then(a.price.price.partial_cmp(&b.price.price).unwrap_or(Ordering::Equal))
+            .then(a.itinerary.departure_date().cmp(&b.itinerary.departure_date()))
+    });
+    candidates.truncate(ITINERARY_SEARCH_MAX_RESULTS);
+
+    candidates
+}
+
+/// accommodations without surcharge are the cheapest
+fn cheapest_accommodation(segment: &Segment, policy: &PricingPolicy) -> Option<Accommodation> {
+    segment.accommodations.iter()
+        .min_by(|a, b| {
+            let surcharge = |accommodation: &Accommodation| policy.surcharge_per_night_per_passenger(&accommodation.id)
+                .unwrap_or(Money::zero(policy.currency()));
+
+            surcharge(a).partial_cmp(&surcharge(b)).unwrap_or(Ordering::Equal)
+        })
+        .cloned()
+}
+
+struct Search<'a> {
+    stages: &'a [(Vec<&'a Flight>, Option<Accommodation>)],
+    window: (NaiveDate, NaiveDate),
+    max_days: i64,
+}
+
+impl<'a> Search<'a> {
+    /// depth first, in order of departure, so later flights are skipped once out of bounds
+    fn extend(&self, chosen: &mut Vec<&'a Flight>, result: &mut Vec<LinkedList<(Flight, Option<Accommodation>)>>) {
+        if result.len() >= MAX_CANDIDATES {
+            return;
+        }
+
+        let Some((candidates, _)) = self.stages.get(chosen.len()) else {
+            result.push(chosen.iter()
+                .zip(self.stages.iter())
+                .map(|(flight, (_, stay))| ((*flight).clone(), stay.clone()))
+                .collect());
+            return;
+        };
+
+        for flight in candidates.iter() {
+            let departure_date = flight.departure.date_naive();
+
+            match chosen.last() {
+                None => {
+                    if departure_date < self.window.0 {
+                        continue;
+                    }
+                    if departure_date > self.window.1 {
+                        break;
+                    }
+                }
+
+                Some(previous) => {
+                    if flight.departure < previous.arrival {
+                        continue;
+                    }
+
+                    let days_between_flights = (departure_date - previous.arrival.date_naive()).num_days();
+                    let stay = &self.stages[chosen.len() - 1].1;
+                    if stay.is_some() && days_between_flights < JOURNEY_MIN_DAYS_IN_ACCOMMODATION {
+                        continue;
+                    }
+                    if days_between_flights > JOURNEY_MAX_DAYS_IN_ACCOMMODATION {
+                        break;
+                    }
+
+                    let first_departure_date = chosen[0].departure.date_naive();
+                    if (departure_date - first_departure_date).num_days() >= self.max_days {
+                        break;
+                    }
+                }
+            }
+
+            chosen.push(flight);
+            self.extend(chosen, result);
+            chosen.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use prelude::collection::SortedSet;
+    use crate::aggregate::{Accommodation, Flight, FlightAvailability, FlightRoute, ItineraryStage, Journey, JourneyShape, PassengerArrangement, Picture, Place, Segment};
+    use crate::services::itinerary_search::search;
+
+    #[test]
+    fn finds_itineraries_closest_to_preferred_length() {
+        let journey = journey();
+        let route = journey.route_from(&"EHAM".parse().unwrap()).unwrap();
+        let flights = vec![
+            availability("9EPFciXgSxB70tAE8iERl1", route_eham_enli(), "2024-05-08", 10),
+            availability("9EPFciXgSxB70tAE8iERl2", route_eham_enli(), "2024-05-09", 10),
+            availability("9EPFciXgSxB70tAE8iERl3", route_enli_eham(), "2024-05-10", 10),
+            availability("9EPFciXgSxB70tAE8iERl4", route_enli_eham(), "2024-05-14", 10),
+            availability("9EPFciXgSxB70tAE8iERl5", route_enli_eham(), "2024-05-16", 10),
+        ];
+
+        let result = search(&journey, &route, &flights, &adults(2), (date("2024-05-01"), date("2024-05-08")), 7);
+        let flights: Vec<Vec<String>> = result.iter()
+            .map(|candidate| candidate.itinerary.stages().map(ItineraryStage::flight).collect())
+            .collect();
+
+        // the return on the 10th is too soon after arriving at the accommodation,
+        // departing the 9th is out of the window
+        assert_eq!(flights, vec![
+            vec!["9EPFciXgSxB70tAE8iERl1".to_owned(), "9EPFciXgSxB70tAE8iERl4".to_owned()],
+            vec!["9EPFciXgSxB70tAE8iERl1".to_owned(), "9EPFciXgSxB70tAE8iERl5".to_owned()],
+        ]);
+        assert_eq!(result[0].price.days, 7);
+        assert_eq!(result[0].price.accommodation_nights, 6);
+    }
+
+    #[test]
+    fn skips_flights_without_seats_for_the_party() {
+        let journey = journey();
+        let route = journey.route_from(&"EHAM".parse().unwrap()).unwrap();
+        let flights = vec![
+            availability("9EPFciXgSxB70tAE8iERl1", route_eham_enli(), "2024-05-08", 10),
+            availability("9EPFciXgSxB70tAE8iERl4", route_enli_eham(), "2024-05-14", 2),
+        ];
+
+        let result = search(&journey, &route, &flights, &adults(2), (date("2024-05-01"), date("2024-05-08")), 7);
+        assert_eq!(result.len(), 1);
+
+        let result = search(&journey, &route, &flights, &adults(3), (date("2024-05-01"), date("2024-05-08")), 7);
+        assert!(result.is_empty());
+    }
+
+    // test data
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn adults(count: u8) -> PassengerArrangement {
+        PassengerArrangement::build(count, 0).unwrap()
+    }
+
+    fn availability(id: &str, route: FlightRoute, date: &str, seats: u8) -> FlightAvailability {
+        let (availability, _) = FlightAvailability::from_flight(Flight::build(
+            id.parse().unwrap(),
+            route,
+            [date, "T09:00:00+05:00"].concat().parse().unwrap(),
+            [date, "T11:00:00+05:00"].concat().parse().unwrap(),
+            seats,
+        ).unwrap());
+
+        availability
+    }
+
+    fn journey() -> Journey {
+        let (journey, _) = Journey::build(
+            "5EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            "Journey Around North Atlantic".parse().unwrap(),
+            JourneyShape::RoundTrip,
+            SortedSet::empty()
+                .insert(Segment::build(route_eham_enli(), SortedSet::singleton(accommodation())).unwrap())
+                .insert(Segment::build(route_enli_eham(), SortedSet::empty()).unwrap())
+        ).unwrap();
+
+        journey
+    }
+
+    fn accommodation() -> Accommodation {
+        Accommodation::build(
+            "6EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            "Farsund Fjordhotel".parse().unwrap(),
+            Place::new(
+                "Farsund, Norway".parse().unwrap(),
+                "u4kf6x".parse().unwrap(),
+            ),
+            SortedSet::singleton(Picture::build(
+                "https://www.visitnorway.com/img/farsund.jpg".parse().unwrap(),
+                "Farsund Resort".to_owned(),
+            ).unwrap()),
+        ).unwrap()
+    }
+
+    fn route_eham_enli() -> FlightRoute {
+        FlightRoute::build(
+            "EHAM".parse().unwrap(),
+            "ENLI".parse().unwrap(),
+        ).unwrap()
+    }
+
+    fn route_enli_eham() -> FlightRoute {
+        FlightRoute::build(
+            "ENLI".parse().unwrap(),
+            "EHAM".parse().unwrap(),
+        ).unwrap()
+    }
+}
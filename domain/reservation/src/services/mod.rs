@@ -1,2 +1,3 @@
 pub mod flight_reservation_strategy;
 pub mod reservation_pricing_strategy;
+pub mod itinerary_search;
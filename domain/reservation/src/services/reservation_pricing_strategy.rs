@@ -1,97 +1,378 @@
-use chrono::{NaiveDate};
-use prelude::data::Money;
-use crate::aggregate::{Itinerary, Reservation};
+use chrono::{Duration, NaiveDate};
+use prelude::data::{Currency, CurrencyConverter, Money, MoneyError};
+use crate::aggregate::{AssignedSeat, HeldReservation, Itinerary, PassengerArrangement, Reservation};
+use crate::policy::PricingPolicy;
 
-fn price_per_day_per_passenger() -> Money {
-    Money::usd(12000)
+/// guaranteed by PricingPolicy::build, which deserialized policies pass as well
+const SAME_CURRENCY: &str = "prices of a pricing policy should share their currency";
+
+/// itemized price of a (proposed) reservation
+#[derive(Debug, PartialEq, Clone)]
+pub struct PriceBreakdown {
+    pub adult_price_per_day: Money,
+    pub child_price_per_day: Money,
+    pub days: i64,
+    pub adults: u8,
+    pub children: u8,
+    /// daily rates of all passengers, adjusted for the season of each day
+    pub travel: Money,
+    pub accommodation_nights: i64,
+    pub accommodation: Money,
+    /// chosen by the customer
+    pub window_seats: u8,
+    pub seats: Money,
+    pub price: Money,
+    /// only cancelled reservations are refunded
+    pub refund_percentage: u8,
+    pub amount_due: Money,
 }
 
-/// pricing a reservation is based on the number of days of travel
-///   the formula is: amount = price per day * days of travel * passengers
+impl PriceBreakdown {
+    /// e.g. to quote in the customer's currency
+    pub fn convert(self, converter: &dyn CurrencyConverter, currency: Currency) -> Result<Self, MoneyError> {
+        Ok(Self {
+            adult_price_per_day: converter.convert(self.adult_price_per_day, currency)?,
+            child_price_per_day: converter.convert(self.child_price_per_day, currency)?,
+            travel: converter.convert(self.travel, currency)?,
+            accommodation: converter.convert(self.accommodation, currency)?,
+            seats: converter.convert(self.seats, currency)?,
+            price: converter.convert(self.price, currency)?,
+            amount_due: converter.convert(self.amount_due, currency)?,
+            ..self
+        })
+    }
+}
+
+/// pricing a reservation is based on the number of days of travel, and the journey's pricing policy
+///   the formula is: amount = sum over days of (adult rate * adults + child rate * children) * season
+///                          + sum over stays of surcharge per night * nights * passengers
+///                          + window seat price * window seats chosen
+///
+/// cancellation fee always apply (but to released holds), and depend on the number of days before travel,
+/// according to the refund tiers of the policy
 ///
-/// cancellation fee always apply, and depend on the number of days before travel,
-/// raging from 0% (full amount is returned) - 100% (nothing is returned)
-pub fn price(reservation: &Reservation) -> Money {
+/// the policy snapshotted on the reservation takes precedence, the given one (the journey's current)
+/// only prices reservations made before it was kept
+pub fn price(reservation: &Reservation, policy: &PricingPolicy) -> Money {
+    price_breakdown(reservation, policy).amount_due
+}
+
+pub fn price_breakdown(reservation: &Reservation, policy: &PricingPolicy) -> PriceBreakdown {
+    let policy = reservation.pricing_policy().unwrap_or(policy);
+
     match reservation {
-        Reservation::Confirmed(confirmed) => {
-            price_itinerary(&confirmed.itinerary, confirmed.passengers.count(), None)
+        Reservation::Held(HeldReservation { reservation: confirmed, .. }) | Reservation::Confirmed(confirmed) => {
+            let passengers = confirmed.passengers.arrangement(confirmed.itinerary.departure_date());
+            price_itinerary(&confirmed.itinerary, &passengers, &confirmed.seats, policy, None)
         }
 
         Reservation::Cancelled(cancelled) => {
             if let Some((_, passengers, itinerary)) = cancelled.revisions.first() {
-                price_itinerary(itinerary, passengers.count(), Some(cancelled.time.date_naive()))
+                let passengers = passengers.arrangement(itinerary.departure_date());
+                let breakdown = price_itinerary(itinerary, &passengers, &cancelled.seats, policy, Some(cancelled.time.date_naive()));
+
+                // no cancellation fee applies to a hold that was never confirmed
+                if cancelled.released {
+                    PriceBreakdown {
+                        refund_percentage: 100,
+                        amount_due: Money::zero(policy.currency()),
+                        ..breakdown
+                    }
+                }
+                else {
+                    breakdown
+                }
             }
             else {
-                Money::usd(0)
+                PriceBreakdown {
+                    adult_price_per_day: policy.adult_price_per_day(),
+                    child_price_per_day: policy.child_price_per_day(),
+                    days: 0,
+                    adults: 0,
+                    children: 0,
+                    travel: Money::zero(policy.currency()),
+                    accommodation_nights: 0,
+                    accommodation: Money::zero(policy.currency()),
+                    window_seats: 0,
+                    seats: Money::zero(policy.currency()),
+                    price: Money::zero(policy.currency()),
+                    refund_percentage: 100,
+                    amount_due: Money::zero(policy.currency()),
+                }
             }
         }
     }
 }
 
-fn price_itinerary(itinerary: &Itinerary, passengers: u8, cancelled: Option<NaiveDate>) -> Money {
+/// price of an itinerary before it is confirmed, i.e. without seats
+pub fn quote(itinerary: &Itinerary, passengers: &PassengerArrangement, policy: &PricingPolicy) -> PriceBreakdown {
+    price_itinerary(itinerary, passengers, &[], policy, None)
+}
+
+fn price_itinerary(itinerary: &Itinerary, passengers: &PassengerArrangement, seats: &[AssignedSeat], policy: &PricingPolicy, cancelled: Option<NaiveDate>) -> PriceBreakdown {
+    let departure_date = itinerary.departure_date();
     let number_of_days = 1 + itinerary.duration().num_days();
 
-    let price = price_per_day_per_passenger()
-        .mul(number_of_days)
-        .mul(passengers as i64);
-
-    if let Some(date) = cancelled {
-        let number_of_days_before_departure = (itinerary.departure_date() - date).num_days();
-        let return_percentage = match number_of_days_before_departure {
-            15.. => 100,
-            10..=14 => 75,
-            5..=9 => 50,
-            _ => 0
-        };
+    let price_per_day = policy.adult_price_per_day()
+        .mul(passengers.adults as i64)
+        .checked_add(policy.child_price_per_day().mul(passengers.children as i64))
+        .expect(SAME_CURRENCY);
+
+    let travel = (0..number_of_days)
+        .map(|day| policy.season_percentage(departure_date + Duration::days(day)))
+        .fold(Money::zero(policy.currency()), |total, percentage| {
+            total.checked_add(price_per_day.percentage(percentage)).expect(SAME_CURRENCY)
+        })
+        .round();
+
+    let stages: Vec<_> = itinerary.stages()
+        .map(|stage| stage.clone().flight_and_accommodation())
+        .collect();
+
+    let mut accommodation_nights = 0;
+    let mut accommodation = Money::zero(policy.currency());
+    for ((arrive, stay), (depart, _)) in stages.iter().zip(stages.iter().skip(1)) {
+        if let Some(stay) = stay {
+            let nights = (depart.departure.date_naive() - arrive.arrival.date_naive()).num_days();
+            accommodation_nights += nights;
+
+            if let Some(surcharge) = policy.surcharge_per_night_per_passenger(&stay.id) {
+                accommodation = accommodation
+                    .checked_add(surcharge.mul(nights).mul(passengers.count() as i64))
+                    .expect(SAME_CURRENCY);
+            }
+        }
+    }
 
-        price.percentage(100 - return_percentage)
+    let window_seats = seats.iter()
+        .filter(|seat| seat.window && seat.chosen)
+        .count() as u8;
+    let seats = policy.window_seat_price()
+        .map(|price| price.mul(window_seats as i64))
+        .unwrap_or(Money::zero(policy.currency()));
+
+    let price = travel
+        .checked_add(accommodation).expect(SAME_CURRENCY)
+        .checked_add(seats).expect(SAME_CURRENCY);
+
+    let refund_percentage = if let Some(date) = cancelled {
+        let number_of_days_before_departure = (departure_date - date).num_days();
+        policy.refund_percentage(number_of_days_before_departure)
     }
     else {
-        price
+        0
+    };
+
+    PriceBreakdown {
+        adult_price_per_day: policy.adult_price_per_day(),
+        child_price_per_day: policy.child_price_per_day(),
+        days: number_of_days,
+        adults: passengers.adults,
+        children: passengers.children,
+        travel,
+        accommodation_nights,
+        accommodation,
+        window_seats,
+        seats,
+        price,
+        refund_percentage,
+        amount_due: price.percentage((100 - refund_percentage) as u16).round(),
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
 mod tests {
     use std::collections::{LinkedList};
     use chrono::NaiveDate;
     use prelude::collection::SortedSet;
     use prelude::data::Money;
-    use crate::aggregate::{Flight, FlightRoute, Itinerary, Journey, Segment};
-    use crate::services::reservation_pricing_strategy::price_itinerary;
+    use crate::aggregate::{Accommodation, AssignedSeat, ConfirmedReservation, Contact, Flight, FlightRoute, Itinerary, Journey, JourneyShape, PassengerArrangement, Picture, Place, Reservation, Segment};
+    use crate::policy::{AccommodationSurcharge, PricingPolicy, RefundTier, ReservationPolicy, Season};
+    use crate::services::reservation_pricing_strategy::{price, price_itinerary, PriceBreakdown};
 
     #[test]
     fn price_reservation_is_based_on_length_and_passengers() {
         // 7 days itinerary, 2 persons
-        let result = price_itinerary(&itinerary(), 2, None);
-        assert_eq!(result, Money::usd(1680_00));
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), None);
+        assert_eq!(result.amount_due, Money::usd(1680_00));
 
         // 7 days itinerary, 4 persons
-        let result = price_itinerary(&itinerary(), 4, None);
-        assert_eq!(result, Money::usd(3360_00));
+        let result = price_itinerary(&itinerary(), &adults(4), &[], &PricingPolicy::standard(), None);
+        assert_eq!(result.amount_due, Money::usd(3360_00));
+    }
+
+    #[test]
+    fn price_is_itemized() {
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), Some("2024-04-24".parse().unwrap()));
+        assert_eq!(result, PriceBreakdown {
+            adult_price_per_day: Money::usd(120_00),
+            child_price_per_day: Money::usd(120_00),
+            days: 7,
+            adults: 2,
+            children: 0,
+            travel: Money::usd(1680_00),
+            accommodation_nights: 0,
+            accommodation: Money::usd(0),
+            window_seats: 0,
+            seats: Money::usd(0),
+            price: Money::usd(1680_00),
+            refund_percentage: 75,
+            amount_due: Money::usd(420_00),
+        });
     }
 
     #[test]
     fn cancellation_refund_on_date() {
         // cancelled >= 15 days, 100% refunded
         let cancellation_date: NaiveDate = "2024-03-08".parse().unwrap();
-        let result = price_itinerary(&itinerary(), 2, Some(cancellation_date));
-        assert_eq!(result, Money::usd(0_00));
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), Some(cancellation_date));
+        assert_eq!(result.amount_due, Money::usd(0_00));
 
         // cancelled >= 10 days, 75% refunded
         let cancellation_date: NaiveDate = "2024-04-24".parse().unwrap();
-        let result = price_itinerary(&itinerary(), 2, Some(cancellation_date));
-        assert_eq!(result, Money::usd(420_00));
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), Some(cancellation_date));
+        assert_eq!(result.amount_due, Money::usd(420_00));
 
         // cancelled >= 5 days, 50% refunded
         let cancellation_date: NaiveDate = "2024-04-30".parse().unwrap();
-        let result = price_itinerary(&itinerary(), 2, Some(cancellation_date));
-        assert_eq!(result, Money::usd(840_00));
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), Some(cancellation_date));
+        assert_eq!(result.amount_due, Money::usd(840_00));
 
         // cancelled < 5 days
         let cancellation_date: NaiveDate = "2024-05-05".parse().unwrap();
-        let result = price_itinerary(&itinerary(), 2, Some(cancellation_date));
-        assert_eq!(result, Money::usd(1680_00));
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &PricingPolicy::standard(), Some(cancellation_date));
+        assert_eq!(result.amount_due, Money::usd(1680_00));
+    }
+
+    #[test]
+    fn children_are_priced_at_child_rate() {
+        // 7 days itinerary, 1 adult at 100.00 and 2 children at 50.00 per day
+        let arrangement = PassengerArrangement::build(1, 2).unwrap();
+        let result = price_itinerary(&itinerary(), &arrangement, &[], &policy(vec![], vec![]), None);
+        assert_eq!(result.amount_due, Money::usd(1400_00));
+    }
+
+    #[test]
+    fn seasons_multiply_daily_rates() {
+        // 3 of 7 days (12-14 May) in high season, 2 adults at 100.00 per day
+        let high_season = Season {
+            from: "2024-05-12".parse().unwrap(),
+            until: "2024-05-31".parse().unwrap(),
+            percentage: 150,
+        };
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &policy(vec![high_season], vec![]), None);
+        assert_eq!(result.travel, Money::usd(1700_00));
+        assert_eq!(result.amount_due, Money::usd(1700_00));
+    }
+
+    #[test]
+    fn accommodation_is_surcharged_per_night() {
+        // 6 nights in accommodation at 30.00 per night, 2 adults at 100.00 per day
+        let surcharge = AccommodationSurcharge {
+            accommodation: accommodation().id,
+            price_per_night_per_passenger: Money::usd(30_00),
+        };
+        let result = price_itinerary(&itinerary_with_accommodation(), &adults(2), &[], &policy(vec![], vec![surcharge]), None);
+        assert_eq!(result.accommodation_nights, 6);
+        assert_eq!(result.accommodation, Money::usd(360_00));
+        assert_eq!(result.amount_due, Money::usd(1760_00));
+    }
+
+    #[test]
+    fn refund_tiers_are_configurable() {
+        // cancelled 14 days before departure
+        let cancellation_date: NaiveDate = "2024-04-24".parse().unwrap();
+        let result = price_itinerary(&itinerary(), &adults(2), &[], &policy(vec![], vec![]), Some(cancellation_date));
+        assert_eq!(result.refund_percentage, 90);
+        assert_eq!(result.amount_due, Money::usd(140_00));
+    }
+
+    #[test]
+    fn chosen_window_seats_are_charged() {
+        // 2 adults at 100.00 per day for 7 days, one of them chose a window seat at 25.00
+        let seats = vec![seat("1A", true, true), seat("1B", true, false), seat("2B", false, true)];
+        let window_seat_policy = policy(vec![], vec![]).with_window_seat_price(Money::usd(25_00)).unwrap();
+        let result = price_itinerary(&itinerary(), &adults(2), &seats, &window_seat_policy, None);
+        assert_eq!(result.window_seats, 1);
+        assert_eq!(result.seats, Money::usd(25_00));
+        assert_eq!(result.amount_due, Money::usd(1425_00));
+
+        // free without a price
+        let result = price_itinerary(&itinerary(), &adults(2), &seats, &policy(vec![], vec![]), None);
+        assert_eq!(result.seats, Money::usd(0));
+        assert_eq!(result.amount_due, Money::usd(1400_00));
+    }
+
+    #[test]
+    fn reservation_is_priced_by_the_policy_as_of_confirmation() {
+        // confirmed at the standard 120.00 per day, the journey's policy is 100.00 per day since
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            "7EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            &journey(),
+            Contact::new("Niels Bergsma".parse().unwrap(), "n.bergsma@internet.com".parse().unwrap(), None),
+            adults(2),
+            itinerary(),
+        ).unwrap();
+        assert_eq!(price(&reservation, &policy(vec![], vec![])), Money::usd(1680_00));
+
+        // made before the policy was kept on the reservation
+        let reservation = match reservation {
+            Reservation::Confirmed(confirmed) => Reservation::Confirmed(ConfirmedReservation { pricing_policy: None, ..confirmed }),
+            other => other,
+        };
+        assert_eq!(price(&reservation, &policy(vec![], vec![])), Money::usd(1400_00));
+    }
+
+    #[test]
+    fn cancelled_reservation_is_priced_with_its_chosen_seats() {
+        let (reservation, _) = Reservation::new_confirmed(
+            &ReservationPolicy::test(),
+            "7EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            &journey(),
+            Contact::new("Niels Bergsma".parse().unwrap(), "n.bergsma@internet.com".parse().unwrap(), None),
+            adults(2),
+            itinerary(),
+        ).unwrap();
+        let window_seat_policy = policy(vec![], vec![]).with_window_seat_price(Money::usd(25_00)).unwrap();
+        let reservation = match reservation {
+            Reservation::Confirmed(confirmed) => Reservation::Confirmed(ConfirmedReservation {
+                seats: vec![seat("1A", true, true), seat("1B", false, false)],
+                pricing_policy: Some(window_seat_policy),
+                ..confirmed
+            }),
+            other => other,
+        };
+
+        // cancelled after departure, nothing is refunded
+        let (cancelled, _) = reservation.cancel(&ReservationPolicy::test()).unwrap();
+        assert_eq!(price(&cancelled, &PricingPolicy::standard()), Money::usd(1425_00));
+    }
+
+    // test data
+    fn policy(seasons: Vec<Season>, surcharges: Vec<AccommodationSurcharge>) -> PricingPolicy {
+        PricingPolicy::build(
+            Money::usd(100_00),
+            Money::usd(50_00),
+            seasons,
+            surcharges,
+            vec![RefundTier { days_before_departure: 14, refund_percentage: 90 }],
+        ).unwrap()
+    }
+
+    fn seat(number: &str, window: bool, chosen: bool) -> AssignedSeat {
+        AssignedSeat {
+            flight: flight_eham_enli_8may().id,
+            passenger: "Jane Doe".parse().unwrap(),
+            seat: number.parse().unwrap(),
+            window,
+            chosen,
+        }
+    }
+
+    fn adults(adults: u8) -> PassengerArrangement {
+        PassengerArrangement::build(adults, 0).unwrap()
     }
 
     fn itinerary() -> Itinerary {
@@ -104,18 +385,44 @@ mod tests {
         journey.parse_itinerary(stages).unwrap()
     }
 
+    fn itinerary_with_accommodation() -> Itinerary {
+        let stages = LinkedList::from([
+            (flight_eham_enli_8may(), Some(accommodation())),
+            (flight_enli_eham_14may(), None),
+        ]);
+
+        let journey = journey();
+        journey.parse_itinerary(stages).unwrap()
+    }
+
     fn journey() -> Journey {
         let (journey, _) = Journey::build(
             "5EPFciXgSxB70tAE8iERl6".parse().unwrap(),
             "Journey Around North Atlantic".parse().unwrap(),
+            JourneyShape::RoundTrip,
             SortedSet::empty()
-                .insert(Segment::build(route_eham_enli(), SortedSet::empty()).unwrap())
+                .insert(Segment::build(route_eham_enli(), SortedSet::singleton(accommodation())).unwrap())
                 .insert(Segment::build(route_enli_eham(), SortedSet::empty()).unwrap())
         ).unwrap();
 
         journey
     }
 
+    fn accommodation() -> Accommodation {
+        Accommodation::build(
+            "6EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            "Farsund Fjordhotel".parse().unwrap(),
+            Place::new(
+                "Farsund, Norway".parse().unwrap(),
+                "u4kf6x".parse().unwrap(),
+            ),
+            SortedSet::singleton(Picture::build(
+                "https://www.visitnorway.com/img/farsund.jpg".parse().unwrap(),
+                "Farsund Resort".to_owned(),
+            ).unwrap()),
+        ).unwrap()
+    }
+
     fn route_eham_enli() -> FlightRoute {
         FlightRoute::build(
             "EHAM".parse().unwrap(),
@@ -1,20 +1,26 @@
 use std::rc::Rc;
+use chrono::Utc;
 use prelude::domain::{Event, EventPublisher};
-use crate::aggregate::{FlightAvailability};
-use crate::command::{MakeFlightAvailableError, MakeFlightAvailable, MakeFlightAvailableResult, ReserveFlight, ReserveFlightResult, ReserveFlightError};
-use crate::event::{FlightReservationFailedV1, FlightReservedV1};
-use crate::repository::{FlightAvailabilityRepository};
+use crate::aggregate::{rooms_for, AccommodationAvailability, AvailabilityFlightError, FlightAvailability, FlightDisruption};
+use crate::command::{CancelFlight, CancelFlightError, CancelFlightResult, RescheduleFlight, RescheduleFlightError, RescheduleFlightResult, MakeFlightAvailableError, MakeFlightAvailable, MakeFlightAvailableResult, ReserveFlight, ReserveFlightResult, ReserveFlightError, OverbookFlight, OverbookFlightError, OverbookFlightResult, ReconcileOversoldFlights, ReconcileOversoldFlightsError, ReconcileOversoldFlightsResult, GetSeatMap, GetSeatMapError, GetSeatMapResult, AllotRooms, AllotRoomsResult};
+use crate::event::{FlightReservationDisruptedV1, FlightReservationFailedV1, FlightReservedV1};
+use crate::policy::OverbookingPolicy;
+use crate::repository::{AccommodationAvailabilityRepository, FlightAvailabilityRepository};
 
 pub struct AvailabilityUseCase {
     flight_availability_repository: Rc<dyn FlightAvailabilityRepository>,
+    accommodation_availability_repository: Rc<dyn AccommodationAvailabilityRepository>,
     event_publisher: Rc<dyn EventPublisher>,
+    overbooking_policy: OverbookingPolicy,
 }
 
 impl AvailabilityUseCase {
-    pub fn new(flight_availability_repository: Rc<dyn FlightAvailabilityRepository>,event_publisher: Rc<dyn EventPublisher>) -> Self {
+    pub fn new(flight_availability_repository: Rc<dyn FlightAvailabilityRepository>, accommodation_availability_repository: Rc<dyn AccommodationAvailabilityRepository>, event_publisher: Rc<dyn EventPublisher>, overbooking_policy: OverbookingPolicy) -> Self {
         Self {
             flight_availability_repository,
+            accommodation_availability_repository,
             event_publisher,
+            overbooking_policy,
         }
     }
 
@@ -22,7 +28,9 @@ impl AvailabilityUseCase {
         let transaction = self.flight_availability_repository.set_begin(&command.flight.id).await?
             .expect_empty(MakeFlightAvailableError::IdConflict)?;
 
-        let (flight_availability, availability_changed) = FlightAvailability::from_flight(command.flight);
+        let percentage = self.overbooking_policy.percentage(&command.flight.route);
+        let (flight_availability, _) = FlightAvailability::from_flight(command.flight);
+        let (flight_availability, availability_changed, _) = flight_availability.overbook(percentage);
         let event: Event = availability_changed.try_into()?;
 
         self.flight_availability_repository.set_commit(transaction.with_value(flight_availability)).await?;
@@ -39,11 +47,36 @@ impl AvailabilityUseCase {
         let availability = transaction.value.clone()
             .ok_or(ReserveFlightError::UnknownFlight(command.flight.clone()))?;
 
-        match availability.reserve(command.reservation.value_ref(), command.seats) {
-            Ok((availability, availability_changed)) => {
-                self.flight_availability_repository.set_commit(
-                    transaction.with_value(availability)
-                ).await?;
+        // rooms are reserved only once the flight takes the stage, reserved or waitlisted
+        let reserved = availability.clone().reserve(command.reservation.value_ref(), command.seats);
+        let takes_stage = matches!(reserved, Ok(_) | Err(AvailabilityFlightError::InsufficientSeats));
+
+        if takes_stage && !self.reserve_rooms(&command).await? {
+            let reserved_flight_failed = FlightReservationFailedV1 {
+                reservation: command.reservation,
+                flight: command.flight,
+                reason: AvailabilityFlightError::InsufficientRooms,
+            };
+
+            let event: Event = reserved_flight_failed.try_into()?;
+            self.event_publisher.send(event).await?;
+
+            return Ok(());
+        }
+
+        // rooms stay reserved while waitlisted, until the stage is annulled
+        match reserved {
+            Ok((availability, _)) => {
+                // an annulment may free seats for the waitlist
+                let (availability, availability_changed, promoted) = availability
+                    .leave_waitlist(&command.reservation)
+                    .unassign_seats(&command.reservation)
+                    .promote_waitlisted();
+
+                if let Err(error) = self.flight_availability_repository.set_commit(transaction.with_value(availability)).await {
+                    self.release_rooms(&command).await?;
+                    return Err(error.into());
+                }
 
                 let event: Event = availability_changed.try_into()?;
                 self.event_publisher.send(event).await?;
@@ -56,6 +89,35 @@ impl AvailabilityUseCase {
                 let event: Event = reserved_flight.try_into()?;
                 self.event_publisher.send(event).await?;
 
+                for waitlist_promoted in promoted {
+                    let event: Event = waitlist_promoted.try_into()?;
+                    self.event_publisher.send(event).await?;
+                }
+
+                Ok(())
+            }
+
+            Err(AvailabilityFlightError::InsufficientSeats) => {
+                let (availability, waitlisted) = availability
+                    .join_waitlist(&command.reservation, command.seats, Utc::now());
+
+                if let Err(error) = self.flight_availability_repository.set_commit(transaction.with_value(availability)).await {
+                    self.release_rooms(&command).await?;
+                    return Err(error.into());
+                }
+
+                let reserved_flight_failed = FlightReservationFailedV1 {
+                    reservation: command.reservation,
+                    flight: command.flight,
+                    reason: AvailabilityFlightError::InsufficientSeats,
+                };
+
+                let event: Event = reserved_flight_failed.try_into()?;
+                self.event_publisher.send(event).await?;
+
+                let event: Event = waitlisted.try_into()?;
+                self.event_publisher.send(event).await?;
+
                 Ok(())
             }
 
@@ -73,4 +135,409 @@ impl AvailabilityUseCase {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// false when the accommodation is sold out on any night of the stay;
+    /// accommodations without an allotment aren't limited
+    async fn reserve_rooms(&self, command: &ReserveFlight) -> Result<bool, ReserveFlightError> {
+        let Some(stay) = &command.stay else {
+            return Ok(true);
+        };
+
+        let transaction = self.accommodation_availability_repository
+            .set_begin(&stay.accommodation).await?;
+
+        let Some(availability) = transaction.value.clone() else {
+            return Ok(true);
+        };
+
+        match availability.reserve(&command.reservation, stay, rooms_for(command.seats)) {
+            Ok(availability) => {
+                self.accommodation_availability_repository.set_commit(
+                    transaction.with_value(availability)
+                ).await?;
+
+                Ok(true)
+            }
+
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// undoes reserve_rooms, when the flight could not be committed after all
+    async fn release_rooms(&self, command: &ReserveFlight) -> Result<(), ReserveFlightError> {
+        let Some(stay) = &command.stay else {
+            return Ok(());
+        };
+
+        let transaction = self.accommodation_availability_repository
+            .set_begin(&stay.accommodation).await?;
+
+        let Some(availability) = transaction.value.clone() else {
+            return Ok(());
+        };
+
+        let availability = availability.reserve(&command.reservation, stay, 0)
+            .expect("releasing rooms always succeeds");
+
+        self.accommodation_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        Ok(())
+    }
+
+    pub async fn allot_rooms(&self, command: AllotRooms) -> AllotRoomsResult {
+        let transaction = self.accommodation_availability_repository
+            .set_begin(&command.accommodation).await?;
+
+        let availability = match transaction.value.clone() {
+            Some(availability) => availability.allot(command.rooms),
+            None => AccommodationAvailability::from_allotment(command.accommodation, command.rooms),
+        };
+
+        self.accommodation_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        Ok(())
+    }
+
+    pub async fn overbook_flight(&self, command: OverbookFlight) -> OverbookFlightResult {
+        let percentage = OverbookingPolicy::check_percentage(command.percentage)?;
+
+        let transaction = self.flight_availability_repository
+            .set_begin(&command.flight).await?;
+
+        let (availability, availability_changed, promoted) = transaction
+            .value_or(OverbookFlightError::UnknownFlight(command.flight.clone()))?
+            .overbook(percentage);
+
+        self.flight_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        let event: Event = availability_changed.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        for waitlist_promoted in promoted {
+            let event: Event = waitlist_promoted.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+
+    /// compares the seats sold with the seats on board, for flights of which check-in opened
+    pub async fn reconcile_oversold_flights(&self, command: ReconcileOversoldFlights) -> ReconcileOversoldFlightsResult {
+        let departing_before = self.overbooking_policy.check_in_horizon(command.now);
+        let mut oversold_flights = Vec::new();
+
+        for flight in self.flight_availability_repository.list_due_for_reconciliation(departing_before).await? {
+            let transaction = self.flight_availability_repository
+                .set_begin(&flight).await?;
+
+            let (availability, flight_oversold) = transaction
+                .value_or(ReconcileOversoldFlightsError::UnknownFlight(flight.clone()))?
+                .reconcile();
+
+            self.flight_availability_repository.set_commit(
+                transaction.with_value(availability)
+            ).await?;
+
+            if let Some(flight_oversold) = flight_oversold {
+                let event: Event = flight_oversold.try_into()?;
+                self.event_publisher.send(event).await?;
+
+                oversold_flights.push(flight);
+            }
+        }
+
+        Ok(oversold_flights)
+    }
+
+    pub async fn seat_map(&self, command: GetSeatMap) -> GetSeatMapResult {
+        let availability = self.flight_availability_repository.get(&command.flight).await?
+            .ok_or(GetSeatMapError::UnknownFlight(command.flight))?;
+
+        Ok(availability.seat_map().seats().iter()
+            .map(|seat| (seat.clone(), availability.seat_taken(&seat.number)))
+            .collect())
+    }
+
+    pub async fn cancel_flight(&self, command: CancelFlight) -> CancelFlightResult {
+        let transaction = self.flight_availability_repository
+            .set_begin(&command.flight).await?;
+
+        let (availability, availability_changed, disrupted_reservations) = transaction
+            .value_or(CancelFlightError::UnknownFlight(command.flight.clone()))?
+            .cancel();
+
+        self.flight_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        let event: Event = availability_changed.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        for reservation in disrupted_reservations {
+            let flight_disrupted = FlightReservationDisruptedV1 {
+                reservation,
+                flight: command.flight.clone(),
+                disruption: FlightDisruption::Cancelled,
+            };
+
+            let event: Event = flight_disrupted.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn reschedule_flight(&self, command: RescheduleFlight) -> RescheduleFlightResult {
+        let transaction = self.flight_availability_repository
+            .set_begin(&command.flight).await?;
+
+        let (availability, availability_changed, disrupted_reservations) = transaction
+            .value_or(RescheduleFlightError::UnknownFlight(command.flight.clone()))?
+            .reschedule(command.departure, command.arrival)?;
+        let flight = availability.flight.clone();
+
+        self.flight_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        let event: Event = availability_changed.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        for reservation in disrupted_reservations {
+            let flight_disrupted = FlightReservationDisruptedV1 {
+                reservation,
+                flight: command.flight.clone(),
+                disruption: FlightDisruption::Rescheduled(flight.clone()),
+            };
+
+            let event: Event = flight_disrupted.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::LinkedList;
+    use chrono::Utc;
+    use futures::executor::block_on;
+    use crate::aggregate::{AccommodationAvailability, ItineraryStage, ItineraryStageDisruption, ItineraryStageError, PassengerArrangement, ReservationId};
+    use crate::command::{AllotRooms, ConfirmReservation, OverbookFlight, ReconcileOversoldFlights};
+    use crate::event::{FlightArrival, FlightCancelledV1, FlightDeparture, FlightRescheduledV1};
+    use crate::in_memory::fixture::{accommodation, all_stages, contact, flight_eham_enli, flight_enli_eham, stages, TestContext};
+    use crate::policy::ReservationPolicy;
+    use crate::repository::AccommodationAvailabilityRepository;
+
+    #[test]
+    fn waitlisted_reservation_is_promoted_when_seats_are_freed() {
+        let context = TestContext::build();
+
+        let first = context.reserve(2);
+        let second = context.reserve(2);
+
+        let reservation = context.get(second);
+        assert!(all_stages(&reservation, |stage| matches!(stage, ItineraryStage::ReservedFailed(_, _, _))));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.waitlist.len(), 1);
+
+        context.cancel(first);
+
+        let reservation = context.get(second);
+        assert!(all_stages(&reservation, |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 1);
+        assert!(availability.waitlist.is_empty());
+    }
+
+    #[test]
+    fn overbooked_flight_sells_beyond_its_seats_and_is_reconciled() {
+        let context = TestContext::build();
+        let availability_usecase = context.availability_usecase();
+
+        block_on(availability_usecase.overbook_flight(OverbookFlight { flight: flight_eham_enli().id, percentage: 200 })).unwrap();
+        context.deliver();
+
+        context.reserve(3);
+        let id = context.reserve(2);
+
+        let reservation = context.get(id);
+        let reserved = stages(&reservation);
+        assert!(matches!(reserved[0], ItineraryStage::Reserved(_, _)));
+        assert!(matches!(reserved[1], ItineraryStage::ReservedFailed(_, _, ItineraryStageError::InsufficientSeats)));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 0);
+        assert_eq!(availability.sellable_seats_available(), 1);
+        assert_eq!(availability.seats_oversold(), 2);
+
+        // the test flights departed already, hence check-in opened
+        let oversold = block_on(availability_usecase.reconcile_oversold_flights(ReconcileOversoldFlights { now: Utc::now() })).unwrap();
+        assert_eq!(oversold, vec![flight_eham_enli().id]);
+
+        let oversold = block_on(availability_usecase.reconcile_oversold_flights(ReconcileOversoldFlights { now: Utc::now() })).unwrap();
+        assert!(oversold.is_empty());
+    }
+
+    #[test]
+    fn sold_out_accommodation_fails_its_stage_until_rooms_are_released() {
+        let context = TestContext::build();
+        let availability_usecase = context.availability_usecase();
+        block_on(availability_usecase.allot_rooms(AllotRooms { accommodation: accommodation().id, rooms: 1 })).unwrap();
+
+        let first = reserve_with_accommodation(&context, 2);
+        assert!(all_stages(&context.get(first), |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let availability = rooms(&context);
+        assert_eq!(availability.rooms_available("2024-05-13".parse().unwrap()), 0);
+        assert_eq!(availability.rooms_available("2024-05-14".parse().unwrap()), 1);
+
+        let second = reserve_with_accommodation(&context, 1);
+
+        let reserved = stages(&context.get(second));
+        assert!(matches!(reserved[0], ItineraryStage::ReservedFailed(_, _, ItineraryStageError::InsufficientRooms)));
+
+        // cancelling releases the rooms along with the seats
+        context.cancel(first);
+
+        let availability = rooms(&context);
+        assert_eq!(availability.rooms_available("2024-05-08".parse().unwrap()), 1);
+    }
+
+    #[test]
+    fn cancelled_flight_disrupts_reserved_stages() {
+        let context = TestContext::build();
+
+        let id = context.reserve(2);
+
+        context.publish(FlightCancelledV1 { id: flight_eham_enli().id });
+        context.deliver();
+
+        let reservation = context.get(id);
+        let stages = stages(&reservation);
+        assert!(matches!(stages[0], ItineraryStage::Disrupted(_, _, ItineraryStageDisruption::FlightCancelled)));
+        assert!(matches!(stages[1], ItineraryStage::Reserved(_, _)));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert!(availability.cancelled);
+    }
+
+    #[test]
+    fn reservation_on_cancelled_flight_is_disrupted() {
+        let context = TestContext::build();
+
+        context.publish(FlightCancelledV1 { id: flight_eham_enli().id });
+        context.deliver();
+
+        let id = context.reserve(2);
+
+        let reservation = context.get(id);
+        let stages = stages(&reservation);
+        assert!(matches!(stages[0], ItineraryStage::Disrupted(_, _, ItineraryStageDisruption::FlightCancelled)));
+        assert!(matches!(stages[1], ItineraryStage::Reserved(_, _)));
+    }
+
+    #[test]
+    fn reservation_on_cancelled_flight_reserves_no_rooms() {
+        let context = TestContext::build();
+        block_on(context.availability_usecase().allot_rooms(AllotRooms { accommodation: accommodation().id, rooms: 1 })).unwrap();
+
+        context.publish(FlightCancelledV1 { id: flight_eham_enli().id });
+        context.deliver();
+
+        let id = reserve_with_accommodation(&context, 2);
+
+        let reservation = context.get(id);
+        assert!(matches!(stages(&reservation)[0], ItineraryStage::Disrupted(_, _, ItineraryStageDisruption::FlightCancelled)));
+
+        let availability = rooms(&context);
+        assert_eq!(availability.rooms_available("2024-05-08".parse().unwrap()), 1);
+        assert!(availability.room_reservations.is_empty());
+    }
+
+    #[test]
+    fn rescheduled_flight_revalidates_reserved_stages() {
+        let context = TestContext::build();
+
+        let id = context.reserve(2);
+
+        // a day later, itinerary remains valid
+        context.publish(rescheduled_eham_enli("2024-05-09T09:00:00+05:00", "2024-05-09T11:00:00+05:00"));
+        context.deliver();
+
+        let reservation = context.get(id);
+        let rescheduled = stages(&reservation);
+        assert!(matches!(&rescheduled[0], ItineraryStage::Reserved(flight, _) if flight.departure.to_rfc3339() == "2024-05-09T09:00:00+05:00"));
+        assert!(matches!(rescheduled[1], ItineraryStage::Reserved(_, _)));
+
+        // after the return flight, itinerary is no longer valid
+        context.publish(rescheduled_eham_enli("2024-05-15T09:00:00+05:00", "2024-05-15T11:00:00+05:00"));
+        context.deliver();
+
+        let reservation = context.get(id);
+        let disrupted = stages(&reservation);
+        assert!(matches!(disrupted[0], ItineraryStage::Disrupted(_, _, ItineraryStageDisruption::FlightRescheduled)));
+        assert!(matches!(disrupted[1], ItineraryStage::Reserved(_, _)));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.flight.departure.to_rfc3339(), "2024-05-15T09:00:00+05:00");
+        assert_eq!(availability.seats_available(), 1);
+    }
+
+    #[test]
+    fn rescheduled_flight_reaches_waitlisted_stages() {
+        let context = TestContext::build();
+
+        context.reserve(2);
+        let waitlisted = context.reserve(2);
+
+        context.publish(rescheduled_eham_enli("2024-05-09T09:00:00+05:00", "2024-05-09T11:00:00+05:00"));
+        context.deliver();
+
+        let reservation = context.get(waitlisted);
+        let rescheduled = stages(&reservation);
+        assert!(matches!(&rescheduled[0], ItineraryStage::ReservedFailed(flight, _, ItineraryStageError::InsufficientSeats) if flight.departure.to_rfc3339() == "2024-05-09T09:00:00+05:00"));
+    }
+
+    // test data
+    fn reserve_with_accommodation(context: &TestContext, adults: u8) -> ReservationId {
+        let id = block_on(context.reservation_usecase.confirm(ConfirmReservation {
+            journey: context.journey,
+            contact: contact(),
+            passengers: PassengerArrangement::build(adults, 0).unwrap(),
+            itinerary: LinkedList::from([
+                (flight_eham_enli().id, Some(accommodation().id)),
+                (flight_enli_eham().id, None),
+            ]),
+        }, &ReservationPolicy::test())).unwrap();
+        context.deliver();
+        id
+    }
+
+    fn rooms(context: &TestContext) -> AccommodationAvailability {
+        block_on(AccommodationAvailabilityRepository::get(context.repository.as_ref(), &accommodation().id)).unwrap().unwrap()
+    }
+
+    fn rescheduled_eham_enli(departure: &str, arrival: &str) -> FlightRescheduledV1 {
+        FlightRescheduledV1 {
+            id: flight_eham_enli().id,
+            departure: FlightDeparture {
+                location: "EHAM".parse().unwrap(),
+                time: departure.parse().unwrap(),
+            },
+            arrival: FlightArrival {
+                location: "ENLI".parse().unwrap(),
+                time: arrival.parse().unwrap(),
+            },
+        }
+    }
+}
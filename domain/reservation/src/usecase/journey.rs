@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use prelude::domain::{Event, EventPublisher};
 use crate::aggregate::{Airfield, Journey, JourneyId};
-use crate::command::{PublishJourney, PublishJourneyError, PublishJourneyResult, RegisterAirfield, RegisterAirfieldResult};
+use crate::command::{PublishJourney, PublishJourneyError, PublishJourneyResult, RegisterAirfield, RegisterAirfieldResult, ReviseJourney, ReviseJourneyError, ReviseJourneyResult, WithdrawJourney, WithdrawJourneyError, WithdrawJourneyResult};
 use crate::repository::{AirfieldRepository, JourneyRepository};
 
 pub struct JourneyUseCase {
@@ -47,8 +47,10 @@ impl JourneyUseCase {
         let (journey, journey_published) = Journey::build(
             id,
             command.name,
+            command.shape,
             command.segments
         )?;
+        let journey = journey.with_pricing_policy(command.pricing_policy);
         let event: Event = journey_published.try_into()?;
 
         self.journey_repository.set_commit(transaction.with_value(journey)).await?;
@@ -56,4 +58,63 @@ impl JourneyUseCase {
 
         Ok(id)
     }
-}
\ No newline at end of file
+    pub async fn revise(&self, command: ReviseJourney) -> ReviseJourneyResult {
+        let transaction = self.journey_repository
+            .set_begin(&command.journey).await?;
+
+        let (journey, journey_revised) = transaction
+            .value_or(ReviseJourneyError::UnknownJourney)?
+            .revise(command.revisions)?;
+        let event: Event = journey_revised.try_into()?;
+
+        self.journey_repository.set_commit(transaction.with_value(journey)).await?;
+        self.event_publisher.send(event).await?;
+
+        Ok(())
+    }
+
+    pub async fn withdraw(&self, command: WithdrawJourney) -> WithdrawJourneyResult {
+        let transaction = self.journey_repository
+            .set_begin(&command.journey).await?;
+
+        let (journey, journey_withdrawn) = transaction
+            .value_or(WithdrawJourneyError::UnknownJourney)?
+            .withdraw()?;
+        let event: Event = journey_withdrawn.try_into()?;
+
+        self.journey_repository.set_commit(transaction.with_value(journey)).await?;
+        self.event_publisher.send(event).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use crate::aggregate::{PassengerArrangement, Reservation};
+    use crate::command::{ConfirmReservation, ConfirmReservationError, GetReservationPrice, WithdrawJourney};
+    use crate::in_memory::fixture::{contact, round_trip, TestContext};
+    use crate::policy::ReservationPolicy;
+
+    #[test]
+    fn withdrawn_journey_takes_no_new_reservations() {
+        let context = TestContext::build();
+        let id = context.reserve(1);
+
+        let journey_usecase = context.journey_usecase();
+        block_on(journey_usecase.withdraw(WithdrawJourney { journey: context.journey })).unwrap();
+
+        let result = block_on(context.reservation_usecase.confirm(ConfirmReservation {
+            journey: context.journey,
+            contact: contact(),
+            passengers: PassengerArrangement::build(1, 0).unwrap(),
+            itinerary: round_trip(),
+        }, &ReservationPolicy::test()));
+        assert_eq!(result, Err(ConfirmReservationError::JourneyWithdrawn));
+
+        // reservations made before stay valid
+        assert!(matches!(context.get(id), Reservation::Confirmed(_)));
+        block_on(context.reservation_usecase.price(GetReservationPrice { id }, &ReservationPolicy::test())).unwrap();
+    }
+}
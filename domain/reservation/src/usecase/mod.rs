@@ -1,8 +1,13 @@
 mod journey;
 mod availability;
 mod reservation;
+mod notification;
+mod queue;
 mod result;
 
 pub use journey::*;
 pub use availability::*;
-pub use reservation::*;
\ No newline at end of file
+pub use reservation::*;
+pub use notification::*;
+pub use queue::*;
+pub use result::{HandleEventError, HandleEventResult};
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::rc::Rc;
+use crate::command::{SendContactEmailVerification, SendContactEmailVerificationResult};
+use crate::gateway::EmailGateway;
+
+/// sends what is only to be known by the recipient, e.g. verification challenges
+pub struct NotificationUseCase {
+    email_gateway: Rc<dyn EmailGateway>,
+}
+
+impl NotificationUseCase {
+    pub fn new(email_gateway: Rc<dyn EmailGateway>) -> Self {
+        Self {
+            email_gateway,
+        }
+    }
+
+    pub async fn send_contact_email_verification(&self, command: SendContactEmailVerification) -> SendContactEmailVerificationResult {
+        let body = format!(
+            "Your verification code for reservation {} is {}, it expires at {}.",
+            command.reservation,
+            command.challenge,
+            command.expires_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+
+        self.email_gateway.send(&command.email, "Verify your email address", &body).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use futures::executor::block_on;
+    use crate::command::{RequestContactEmailVerification, VerifyContactEmail};
+    use crate::event::Event;
+    use crate::in_memory::fixture::{contact, TestContext};
+
+    #[test]
+    fn sends_contact_email_verification_challenge() {
+        let context = TestContext::build();
+        let id = context.confirm(1);
+
+        block_on(context.reservation_usecase.request_contact_email_verification(RequestContactEmailVerification {
+            reservation: id,
+            now: now(),
+        })).unwrap();
+        let requested = context.events().into_iter()
+            .find_map(|event| match event {
+                Event::ContactEmailVerificationRequestedV1(requested) => Some(requested),
+                _ => None,
+            })
+            .unwrap();
+        context.deliver();
+
+        let sent = context.email_gateway.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, contact().email);
+        assert!(sent[0].2.contains(&requested.challenge));
+
+        block_on(context.reservation_usecase.verify_contact_email(VerifyContactEmail {
+            reservation: id,
+            challenge: requested.challenge,
+            now: now(),
+        })).unwrap();
+        assert!(context.get(id).contact().email_is_verified());
+    }
+
+    // test data
+    fn now() -> DateTime<Utc> {
+        "2024-01-08T09:00:00Z".parse().unwrap()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::command::{CancelFlight, MakeFlightAvailable, RefundReservation, RegisterAirfield, RescheduleFlight, ReserveFlight, SendContactEmailVerification};
+use crate::event::{Event, ReservationCancelledV1, ReservationHoldReleasedV1};
+use crate::policy::ReservationPolicy;
+use crate::usecase::{AvailabilityUseCase, JourneyUseCase, NotificationUseCase, ReservationUseCase};
+use crate::usecase::result::{HandleEventError, HandleEventResult};
+
+/// dispatches events (delivered by a queue) to the use cases;
+/// events that only feed projections are ignored
+pub struct QueueUseCase {
+    journey_usecase: JourneyUseCase,
+    availability_usecase: AvailabilityUseCase,
+    reservation_usecase: ReservationUseCase,
+    notification_usecase: NotificationUseCase,
+}
+
+impl QueueUseCase {
+    pub fn new(journey_usecase: JourneyUseCase, availability_usecase: AvailabilityUseCase, reservation_usecase: ReservationUseCase, notification_usecase: NotificationUseCase) -> Self {
+        Self {
+            journey_usecase,
+            availability_usecase,
+            reservation_usecase,
+            notification_usecase,
+        }
+    }
+
+    pub async fn handle(&self, event: Event) -> HandleEventResult {
+        match event {
+            Event::FlightScheduledV1(event) => {
+                let command = MakeFlightAvailable {
+                    flight: event.into(),
+                };
+
+                self.availability_usecase.make_flight_available(command).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::AirfieldRegisteredV1(airfield) => {
+                let command = RegisterAirfield {
+                    id: airfield.id,
+                    name: airfield.name,
+                    location: airfield.location,
+                };
+
+                self.journey_usecase.register_airfield(command).await
+                    .map(|_| ())
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightReservationRequestedV1(request) => {
+                let command = ReserveFlight {
+                    reservation: request.reservation,
+                    flight: request.flight,
+                    seats: request.seats,
+                    stay: request.stay,
+                };
+
+                self.availability_usecase.reserve_flight(command).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightReservedV1(event) => {
+                self.reservation_usecase.handle_flight_reserved(&event).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::WaitlistPromotedV1(event) => {
+                self.reservation_usecase.handle_waitlist_promoted(&event).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightReservationFailedV1(event) => {
+                self.reservation_usecase.handle_flight_reservation_failed(&event).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightCancelledV1(event) => {
+                let command = CancelFlight {
+                    flight: event.id,
+                };
+
+                self.availability_usecase.cancel_flight(command).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightRescheduledV1(event) => {
+                let command = RescheduleFlight {
+                    flight: event.id,
+                    departure: event.departure.time,
+                    arrival: event.arrival.time,
+                };
+
+                self.availability_usecase.reschedule_flight(command).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::FlightReservationDisruptedV1(event) => {
+                self.reservation_usecase.handle_flight_disrupted(&event).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::ReservationCancelledV1(ReservationCancelledV1 { id, .. })
+            | Event::ReservationHoldReleasedV1(ReservationHoldReleasedV1 { id, .. }) => {
+                // refunds already issued are recorded, hence redelivery doesn't refund twice
+                self.reservation_usecase.refund(RefundReservation { id }, &ReservationPolicy::agent()).await
+                    .map(|_| ())
+                    .map_err(HandleEventError::from_error)
+            }
+
+            Event::ContactEmailVerificationRequestedV1(event) => {
+                let command = SendContactEmailVerification {
+                    reservation: event.id,
+                    email: event.email,
+                    challenge: event.challenge,
+                    expires_at: event.expires_at,
+                };
+
+                self.notification_usecase.send_contact_email_verification(command).await
+                    .map_err(HandleEventError::from_error)
+            }
+
+            _ => {
+                // ignore other events
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,20 +1,24 @@
 use std::collections::LinkedList;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::rc::Rc;
-use prelude::domain::{Event, EventPublisher};
-use crate::aggregate::{AccommodationId, AvailabilityFlightError, FlightId, ItineraryError, ItineraryStageError, Journey, Reservation, ReservationId};
-use crate::command::{CancelReservation, CancelReservationError, CancelReservationResult, ConfirmReservation, ConfirmReservationError, ConfirmReservationResult, GetReservation, GetReservationError, GetReservationResult, ReferencedItineraryStage, ResolvedItineraryStage, ReviseItinerary, ReviseItineraryError, ReviseItineraryResult, RevisePassengers, RevisePassengersError, RevisePassengersResult};
-use crate::event::{FlightReservationFailedV1, FlightReservedV1};
+use chrono::{Duration, NaiveTime, Utc};
+use prelude::data::{CurrencyConverter, Money};
+use prelude::domain::{Event, EventPublisher, Versioned};
+use crate::aggregate::{AccommodationId, AvailabilityFlightError, EmailVerificationError, FlightDisruption, FlightId, ItineraryError, ItineraryStageDisruption, ItineraryStageError, Journey, Payment, PaymentReference, Refund, Reservation, ReservationId, SeatAssignment};
+use crate::command::{AssignSeats, AssignSeatsError, AssignSeatsResult, BoardPassenger, BoardPassengerError, BoardPassengerResult, CheckInPassengers, CheckInPassengersError, CheckInPassengersResult, CloseBoarding, CloseBoardingError, CloseBoardingResult, CancelReservation, CancelReservationError, CancelReservationResult, ConfirmHeldReservation, ConfirmHeldReservationError, ConfirmHeldReservationResult, ConfirmReservation, ConfirmReservationError, ConfirmReservationResult, GetReservation, GetReservationError, GetReservationPayments, GetReservationPaymentsError, GetReservationPaymentsResult, GetReservationPrice, GetReservationPriceError, GetReservationPriceResult, GetReservationResult, HoldReservation, HoldReservationError, HoldReservationResult, PayReservation, PayReservationError, PayReservationResult, QuoteReservation, QuoteReservationError, QuoteReservationResult, SearchItineraries, SearchItinerariesError, SearchItinerariesResult, ReferencedItineraryStage, RefundReservation, RefundReservationError, RefundReservationResult, ReleaseExpiredHolds, ReleaseExpiredHoldsError, ReleaseExpiredHoldsResult, RequestContactEmailVerification, RequestContactEmailVerificationError, RequestContactEmailVerificationResult, ReservationPayments, ResolvedItineraryStage, ReviseContact, ReviseContactError, ReviseContactResult, ReviseItinerary, ReviseItineraryError, ReviseItineraryResult, RevisePassengers, RevisePassengersError, RevisePassengersResult, VerifyContactEmail, VerifyContactEmailError, VerifyContactEmailResult};
+use crate::event::{FlightReservationDisruptedV1, FlightReservationFailedV1, FlightReservedV1, PaymentRecordedV1, RefundIssuedV1, WaitlistPromotedV1};
+use crate::gateway::PaymentGateway;
 use crate::policy::ReservationPolicy;
-use crate::repository::{FlightAvailabilityRepository, JourneyRepository, ReservationRepository};
-use crate::services::flight_reservation_strategy;
-use crate::usecase::result::{HandleFlightReservationFailedError, HandleFlightReservationFailedResult, HandleFlightReservedError, HandleFlightReservedResult};
+use crate::repository::{FlightAvailabilityRepository, JourneyRepository, ReservationRepository, ReservationRepositoryError};
+use crate::services::{flight_reservation_strategy, itinerary_search, reservation_pricing_strategy};
+use crate::usecase::result::{HandleFlightDisruptedError, HandleFlightDisruptedResult, HandleFlightReservationFailedError, HandleFlightReservationFailedResult, HandleFlightReservedError, HandleFlightReservedResult};
 
 pub struct ReservationUseCase {
     reservation_repository: Rc<dyn ReservationRepository>,
     flight_availability_repository: Rc<dyn FlightAvailabilityRepository>,
     journey_repository: Rc<dyn JourneyRepository>,
-    event_publisher: Rc<dyn EventPublisher>
+    event_publisher: Rc<dyn EventPublisher>,
+    payment_gateway: Rc<dyn PaymentGateway>,
 }
 
 impl ReservationUseCase {
@@ -22,29 +26,120 @@ impl ReservationUseCase {
         reservation_repository: Rc<dyn ReservationRepository>,
         flight_availability_repository: Rc<dyn FlightAvailabilityRepository>,
         journey_repository: Rc<dyn JourneyRepository>,
-        event_publisher: Rc<dyn EventPublisher>
+        event_publisher: Rc<dyn EventPublisher>,
+        payment_gateway: Rc<dyn PaymentGateway>,
     ) -> Self {
         Self {
             reservation_repository,
             flight_availability_repository,
             journey_repository,
             event_publisher,
+            payment_gateway,
         }
     }
 
-    pub async fn get(&self, command: GetReservation) -> GetReservationResult {
+    pub async fn get(&self, command: GetReservation, policy: &ReservationPolicy) -> GetReservationResult {
         match self.reservation_repository.get(&command.id).await? {
-            Some(reservation) => Ok(reservation),
+            Some(reservation) => Ok(policy.view(reservation)?),
             None => Err(GetReservationError::UnknownReservation),
         }
     }
 
+    pub async fn price(&self, command: GetReservationPrice, policy: &ReservationPolicy) -> GetReservationPriceResult {
+        let reservation = self.reservation_repository.get(&command.id).await?
+            .ok_or(GetReservationPriceError::UnknownReservation)?;
+
+        let reservation = policy.view(reservation)?;
+        let journey = self.journey_repository.get(&reservation.journey()).await?
+            .ok_or(GetReservationPriceError::UnknownJourney)?;
+
+        Ok(reservation_pricing_strategy::price_breakdown(&reservation, journey.pricing_policy()))
+    }
+
+    /// flights are not checked for availability, only the itinerary
+    pub async fn quote(&self, command: QuoteReservation, converter: &dyn CurrencyConverter) -> QuoteReservationResult {
+        let journey = self.journey_repository.get(&command.journey).await?
+            .ok_or(QuoteReservationError::UnknownJourney)?;
+
+        if journey.is_withdrawn() {
+            return Err(QuoteReservationError::JourneyWithdrawn);
+        }
+
+        let itinerary = journey.parse_itinerary(
+            self.resolve_itinerary_stages(&journey, command.itinerary).await?
+        )?;
+
+        let quote = reservation_pricing_strategy::quote(&itinerary, &command.passengers, journey.pricing_policy());
+
+        match command.currency {
+            Some(currency) => Ok(quote.convert(converter, currency)?),
+            None => Ok(quote),
+        }
+    }
+
+    /// flights are searched by the seats available now, which are not held until confirmed
+    pub async fn search(&self, command: SearchItineraries, converter: &dyn CurrencyConverter) -> SearchItinerariesResult {
+        if command.latest_departure < command.earliest_departure {
+            return Err(SearchItinerariesError::MalformedDepartureWindow);
+        }
+        if (command.latest_departure - command.earliest_departure).num_days() > itinerary_search::ITINERARY_SEARCH_MAX_WINDOW_DAYS {
+            return Err(SearchItinerariesError::DepartureWindowTooWide);
+        }
+        if command.days < 1 {
+            return Err(SearchItinerariesError::DaysNotPositive);
+        }
+
+        let journey = self.journey_repository.get(&command.journey).await?
+            .ok_or(SearchItinerariesError::UnknownJourney)?;
+
+        if journey.is_withdrawn() {
+            return Err(SearchItinerariesError::JourneyWithdrawn);
+        }
+
+        let route = journey.route_from(&command.departure)
+            .ok_or(SearchItinerariesError::DepartureNotInJourney(command.departure.clone()))?;
+
+        // a day of margin for the time zones of the airfields
+        let departing_after = (command.earliest_departure - Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+        let departing_before = (command.latest_departure + Duration::days(command.days + itinerary_search::ITINERARY_SEARCH_DAYS_TOLERANCE + 1))
+            .and_time(NaiveTime::MIN).and_utc();
+
+        let mut flights = Vec::new();
+        for segment in route.iter() {
+            flights.extend(self.flight_availability_repository
+                .list_by_route(&segment.flight, departing_after, departing_before).await?);
+        }
+
+        let candidates = itinerary_search::search(
+            &journey,
+            &route,
+            &flights,
+            &command.passengers,
+            (command.earliest_departure, command.latest_departure),
+            command.days,
+        );
+
+        match command.currency {
+            Some(currency) => candidates.into_iter()
+                .map(|candidate| Ok(itinerary_search::ItineraryCandidate {
+                    price: candidate.price.convert(converter, currency)?,
+                    ..candidate
+                }))
+                .collect(),
+            None => Ok(candidates),
+        }
+    }
+
     pub async fn confirm(&self, command: ConfirmReservation, policy: &ReservationPolicy) -> ConfirmReservationResult {
         let id = ReservationId::new_random();
 
         let journey = self.journey_repository.get(&command.journey).await?
             .ok_or(ConfirmReservationError::UnknownJourney)?;
 
+        if journey.is_withdrawn() {
+            return Err(ConfirmReservationError::JourneyWithdrawn);
+        }
+
         let itinerary = journey.parse_itinerary(
             self.resolve_itinerary_stages(&journey, command.itinerary).await?
         )?;
@@ -55,7 +150,7 @@ impl ReservationUseCase {
         let (reservation, reservation_confirmed) = Reservation::new_confirmed(
             policy,
             id,
-            command.journey,
+            &journey,
             command.contact,
             command.passengers,
             itinerary,
@@ -71,6 +166,93 @@ impl ReservationUseCase {
             .map(|_| id)
     }
 
+    /// seats are reserved as when confirming, but released by release_expired_holds unless confirmed in time
+    pub async fn hold(&self, command: HoldReservation, policy: &ReservationPolicy) -> HoldReservationResult {
+        let id = ReservationId::new_random();
+
+        let journey = self.journey_repository.get(&command.journey).await?
+            .ok_or(HoldReservationError::UnknownJourney)?;
+
+        if journey.is_withdrawn() {
+            return Err(HoldReservationError::JourneyWithdrawn);
+        }
+
+        let itinerary = journey.parse_itinerary(
+            self.resolve_itinerary_stages(&journey, command.itinerary).await?
+        )?;
+
+        let transaction = self.reservation_repository.set_begin(&id).await?
+            .expect_empty(HoldReservationError::IdConflict)?;
+
+        let (reservation, reservation_held) = Reservation::new_held(
+            policy,
+            id,
+            &journey,
+            command.contact,
+            command.passengers,
+            itinerary,
+        )?;
+        let event: Event = reservation_held.try_into()?;
+
+        self.reservation_repository.set_commit(transaction.with_value(reservation.clone())).await?;
+        self.event_publisher.send(event).await?;
+
+        self.resolve_flights(&reservation).await
+            .map_err(|error| error.into())
+            .map(|_| id)
+    }
+
+    pub async fn confirm_hold(&self, command: ConfirmHeldReservation, policy: &ReservationPolicy) -> ConfirmHeldReservationResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.id).await?;
+
+        let (reservation, reservation_confirmed) = transaction
+            .value_or(ConfirmHeldReservationError::UnknownReservation)?
+            .confirm_hold(policy)?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        if let Some(event) = reservation_confirmed {
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+
+    /// sweeps expired holds, their seats are annulled (saga) and payments refunded
+    pub async fn release_expired_holds(&self, command: ReleaseExpiredHolds) -> ReleaseExpiredHoldsResult {
+        let mut released = Vec::new();
+
+        for id in self.reservation_repository.list_expired_holds(command.now).await? {
+            let transaction = self.reservation_repository
+                .set_begin(&id).await?;
+
+            let (reservation, hold_released) = transaction
+                .value_or(ReleaseExpiredHoldsError::OtherError(format!("unknown reservation {}", id)))?
+                .release_hold(command.now);
+
+            // confirmed, or released, since listed
+            let Some(event) = hold_released else { continue };
+
+            self.reservation_repository.set_commit(
+                transaction.with_value(reservation.clone())
+            ).await?;
+
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+
+            // refunded on the event, as cancelled reservations are
+            self.resolve_flights(&reservation).await?;
+
+            released.push(id);
+        }
+
+        Ok(released)
+    }
+
     pub async fn revise_passengers(&self, command: RevisePassengers, policy: &ReservationPolicy) -> RevisePassengersResult {
         let transaction = self.reservation_repository.set_begin(&command.reservation).await?;
 
@@ -105,7 +287,7 @@ impl ReservationUseCase {
             self.resolve_itinerary_stages(&journey, command.itinerary).await?
         )?;
 
-        let (reservation, reservation_revised) = reservation.revise_itinerary(policy, itinerary)?;
+        let (reservation, reservation_revised) = reservation.revise_itinerary(policy, &journey, itinerary)?;
 
         self.reservation_repository.set_commit(
             transaction.with_value(reservation.clone())
@@ -120,6 +302,153 @@ impl ReservationUseCase {
             .map_err(|error| error.into())
     }
 
+    pub async fn revise_contact(&self, command: ReviseContact, policy: &ReservationPolicy) -> ReviseContactResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let (reservation, contact_revised) = transaction
+            .value_or(ReviseContactError::UnknownReservation)?
+            .revise_contact(policy, command.contact)?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        if let Some(event) = contact_revised {
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+
+    /// seats are assigned by the flight's availability first, then recorded by the reservation
+    /// both aggregates are revised before either is committed; when the reservation fails to commit after the
+    /// flight did, the seats of the flight are restored, hence retrying is safe
+    pub async fn assign_seats(&self, command: AssignSeats, policy: &ReservationPolicy) -> AssignSeatsResult {
+        let reservation_transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let reservation = policy.view(reservation_transaction.value_or(AssignSeatsError::UnknownReservation)?)?;
+        let requests = reservation.seat_requests(&command.flight, command.seats)?;
+
+        let transaction = self.flight_availability_repository
+            .set_begin(&command.flight).await?;
+
+        let availability = transaction
+            .value_or(AssignSeatsError::UnknownFlight(command.flight.clone()))?;
+        let assigned_before: Vec<SeatAssignment> = availability.seat_assignments.iter()
+            .filter(|assignment| assignment.reservation == reservation.id())
+            .cloned()
+            .collect();
+
+        let (availability, seats) = availability
+            .assign_seats(&Versioned::from_version(reservation.id(), reservation.version()), requests)?;
+
+        let (reservation, seats_assigned) = reservation
+            .assign_seats(policy, &command.flight, seats.clone())?;
+
+        self.flight_availability_repository.set_commit(
+            transaction.with_value(availability)
+        ).await?;
+
+        if let Err(error) = self.reservation_repository.set_commit(reservation_transaction.with_value(reservation)).await {
+            let transaction = self.flight_availability_repository
+                .set_begin(&command.flight).await?;
+
+            let availability = transaction
+                .value_or(AssignSeatsError::UnknownFlight(command.flight.clone()))?
+                .restore_seats(&command.reservation, assigned_before);
+
+            self.flight_availability_repository.set_commit(
+                transaction.with_value(availability)
+            ).await?;
+
+            return Err(error.into());
+        }
+
+        if let Some(event) = seats_assigned {
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(seats)
+    }
+
+    pub async fn check_in(&self, command: CheckInPassengers, policy: &ReservationPolicy) -> CheckInPassengersResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let reservation = transaction
+            .value_or(CheckInPassengersError::UnknownReservation)?;
+
+        let (reservation, boarding_passes, checked_in) = policy.view(reservation)?
+            .check_in(&command.flight, command.passengers, command.now)?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        if let Some(event) = checked_in {
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(boarding_passes)
+    }
+
+    pub async fn board(&self, command: BoardPassenger, policy: &ReservationPolicy) -> BoardPassengerResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let reservation = transaction
+            .value_or(BoardPassengerError::UnknownReservation)?;
+
+        let (reservation, boarded) = policy.view(reservation)?
+            .board(&command.flight, &command.code, command.now)?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        let passenger = boarded.passenger.clone();
+        let event = boarded.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        Ok(passenger)
+    }
+
+    /// all reservations holding seats on the flight are closed for boarding
+    pub async fn close_boarding(&self, command: CloseBoarding) -> CloseBoardingResult {
+        let availability = self.flight_availability_repository.get(&command.flight).await?
+            .ok_or(CloseBoardingError::UnknownFlight(command.flight.clone()))?;
+
+        let mut reservations = Vec::new();
+        for id in availability.seat_holders() {
+            let transaction = self.reservation_repository
+                .set_begin(&id).await?;
+
+            let Some(reservation) = transaction.value.clone() else {
+                continue;
+            };
+
+            let (reservation, no_shows) = reservation.close_boarding(&command.flight, command.now)?;
+
+            // unchanged otherwise, hence not committed so as not to conflict with revisions
+            if let Some(event) = no_shows {
+                self.reservation_repository.set_commit(
+                    transaction.with_value(reservation)
+                ).await?;
+
+                let event = event.try_into()?;
+                self.event_publisher.send(event).await?;
+                reservations.push(id);
+            }
+        }
+
+        Ok(reservations)
+    }
+
     pub async fn cancel(&self, command: CancelReservation, policy: &ReservationPolicy) -> CancelReservationResult {
         let transaction = self.reservation_repository
             .set_begin(&command.id).await?;
@@ -132,6 +461,8 @@ impl ReservationUseCase {
             transaction.with_value(reservation.clone())
         ).await?;
 
+        // cancellation lowers the amount due, by the refund percentage, which is refunded
+        // when the event is handled, such that a failing refund is retried rather than the cancellation
         if let Some(event) = reservation_cancelled {
             let event = event.try_into()?;
             self.event_publisher.send(event).await?;
@@ -141,6 +472,226 @@ impl ReservationUseCase {
             .map_err(|error| error.into())
     }
 
+    pub async fn payments(&self, command: GetReservationPayments, policy: &ReservationPolicy) -> GetReservationPaymentsResult {
+        let reservation = self.reservation_repository.get(&command.id).await?
+            .ok_or(GetReservationPaymentsError::UnknownReservation)?;
+
+        let reservation = policy.view(reservation)?;
+        let journey = self.journey_repository.get(&reservation.journey()).await?
+            .ok_or(GetReservationPaymentsError::UnknownJourney)?;
+
+        let amount_due = reservation_pricing_strategy::price(&reservation, journey.pricing_policy());
+        Ok(ReservationPayments {
+            balance: reservation.balance(amount_due)?,
+            payments: reservation.payments().clone(),
+            amount_due,
+        })
+    }
+
+    /// the payment method is charged before the payment is recorded, once per version of the reservation;
+    /// a charge that conflicts with a concurrent commit is recorded on top of it, or refunded
+    pub async fn pay(&self, command: PayReservation, policy: &ReservationPolicy) -> PayReservationResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.id).await?;
+
+        let reservation = policy.view(
+            transaction.value_or(PayReservationError::UnknownReservation)?
+        )?;
+        let journey = self.journey_repository.get(&reservation.journey()).await?
+            .ok_or(PayReservationError::UnknownJourney)?;
+
+        let amount_due = reservation_pricing_strategy::price(&reservation, journey.pricing_policy());
+        reservation.validate_payment(amount_due, command.amount, command.now)?;
+
+        let idempotency_key = [command.id.to_string(), transaction.version.to_string()].join(":");
+        let reference = self.payment_gateway
+            .charge(&command.id, command.amount, &command.payment_method, &idempotency_key).await?;
+
+        let payment = Payment {
+            reference: reference.clone(),
+            amount: command.amount,
+            time: command.now,
+        };
+        let (reservation, payment_recorded) = reservation.record_payment(amount_due, payment.clone())?;
+
+        let payment_recorded = match self.reservation_repository.set_commit(transaction.with_value(reservation)).await {
+            Ok(()) => payment_recorded,
+            Err(ReservationRepositoryError::VersionConflict) => self.record_charged_payment(&command.id, payment).await?,
+            Err(error) => return Err(error.into()),
+        };
+
+        let event = payment_recorded.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        Ok(reference)
+    }
+
+    /// records a payment that is charged already, the charge is refunded if it can't be recorded
+    async fn record_charged_payment(&self, id: &ReservationId, payment: Payment) -> Result<PaymentRecordedV1, PayReservationError> {
+        let recorded = async {
+            let transaction = self.reservation_repository
+                .set_begin(id).await?;
+
+            let reservation = transaction.value_or(PayReservationError::UnknownReservation)?;
+            let journey = self.journey_repository.get(&reservation.journey()).await?
+                .ok_or(PayReservationError::UnknownJourney)?;
+
+            let amount_due = reservation_pricing_strategy::price(&reservation, journey.pricing_policy());
+            let (reservation, payment_recorded) = reservation.record_payment(amount_due, payment.clone())?;
+
+            self.reservation_repository.set_commit(
+                transaction.with_value(reservation)
+            ).await?;
+
+            Ok(payment_recorded)
+        }.await;
+
+        if recorded.is_err() {
+            let idempotency_key = refund_idempotency_key(id, &payment.reference, Money::zero(payment.amount.currency()));
+            self.payment_gateway.refund(id, &payment.reference, payment.amount, &idempotency_key).await?;
+        }
+
+        recorded
+    }
+
+    /// refunds issued before a failing one are still recorded, and refunds are idempotent,
+    /// hence retrying doesn't refund twice
+    pub async fn refund(&self, command: RefundReservation, policy: &ReservationPolicy) -> RefundReservationResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.id).await?;
+
+        let mut reservation = policy.view(
+            transaction.value_or(RefundReservationError::UnknownReservation)?
+        )?;
+        let journey = self.journey_repository.get(&reservation.journey()).await?
+            .ok_or(RefundReservationError::UnknownJourney)?;
+
+        let amount_due = reservation_pricing_strategy::price(&reservation, journey.pricing_policy());
+
+        let mut refunds = Vec::new();
+        let mut events = Vec::new();
+        let mut failure = None;
+        for (payment, amount) in reservation.refunds_due(amount_due)? {
+            let idempotency_key = refund_idempotency_key(&command.id, &payment, reservation.payments().refunded(&payment)?);
+            let reference = match self.payment_gateway.refund(&command.id, &payment, amount, &idempotency_key).await {
+                Ok(reference) => reference,
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            };
+
+            let refund = Refund {
+                payment,
+                reference,
+                amount,
+                time: Utc::now(),
+            };
+            let (refunded, refund_issued) = reservation.record_refund(refund.clone())?;
+
+            reservation = refunded;
+            refunds.push(refund);
+            events.push(refund_issued);
+        }
+
+        if !events.is_empty() {
+            let events = match self.reservation_repository.set_commit(transaction.with_value(reservation)).await {
+                Ok(()) => events,
+                Err(ReservationRepositoryError::VersionConflict) => self.record_issued_refunds(&command.id, &refunds).await?,
+                Err(error) => return Err(error.into()),
+            };
+
+            for event in events {
+                let event = event.try_into()?;
+                self.event_publisher.send(event).await?;
+            }
+        }
+
+        match failure {
+            Some(error) => Err(error.into()),
+            None => Ok(refunds.into_iter().map(|refund| refund.reference).collect()),
+        }
+    }
+
+    /// records refunds that are issued already, but those recorded concurrently
+    async fn record_issued_refunds(&self, id: &ReservationId, refunds: &[Refund]) -> Result<Vec<RefundIssuedV1>, RefundReservationError> {
+        let transaction = self.reservation_repository
+            .set_begin(id).await?;
+
+        let mut reservation = transaction.value_or(RefundReservationError::UnknownReservation)?;
+
+        let mut events = Vec::new();
+        for refund in refunds {
+            // e.g. by a redelivery, which the gateway returned the same refund to
+            if reservation.payments().refunds().iter().any(|recorded| recorded.reference == refund.reference) {
+                continue;
+            }
+
+            let (refunded, refund_issued) = reservation.record_refund(refund.clone())?;
+            reservation = refunded;
+            events.push(refund_issued);
+        }
+
+        if !events.is_empty() {
+            self.reservation_repository.set_commit(
+                transaction.with_value(reservation)
+            ).await?;
+        }
+
+        Ok(events)
+    }
+
+    /// challenge is only published (to be mailed), not returned
+    pub async fn request_contact_email_verification(&self, command: RequestContactEmailVerification) -> RequestContactEmailVerificationResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let (reservation, verification_requested) = transaction
+            .value_or(RequestContactEmailVerificationError::UnknownReservation)?
+            .request_contact_email_verification(command.now)?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        let event = verification_requested.try_into()?;
+        self.event_publisher.send(event).await?;
+
+        Ok(())
+    }
+
+    pub async fn verify_contact_email(&self, command: VerifyContactEmail) -> VerifyContactEmailResult {
+        let transaction = self.reservation_repository
+            .set_begin(&command.reservation).await?;
+
+        let reservation = transaction
+            .value_or(VerifyContactEmailError::UnknownReservation)?;
+
+        let (reservation, email_verified) = match reservation.clone().verify_contact_email(&command.challenge, command.now) {
+            Err(EmailVerificationError::ChallengeDontMatch) => {
+                // the failed attempt is counted before the error is returned
+                self.reservation_repository.set_commit(
+                    transaction.with_value(reservation.fail_contact_email_verification())
+                ).await?;
+
+                return Err(EmailVerificationError::ChallengeDontMatch.into());
+            }
+
+            result => result?,
+        };
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation)
+        ).await?;
+
+        if let Some(event) = email_verified {
+            let event = event.try_into()?;
+            self.event_publisher.send(event).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn handle_flight_reserved(&self, event: &FlightReservedV1) -> HandleFlightReservedResult {
         let id = event.reservation.value_ref();
         let version = event.reservation.version();
@@ -156,6 +707,26 @@ impl ReservationUseCase {
                 .value_or(HandleFlightReservedError::UnknownReservation(*id))?
                 .mark_flight_as_reserved(&event.flight, version)
         };
+        let reservation = self.refresh_flight(reservation, &event.flight).await?;
+
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation.clone())
+        ).await?;
+
+        self.resolve_flights(&reservation).await
+            .map_err(|error| error.into())
+    }
+
+    /// seats freed on a fully booked flight were given to the (waitlisted) reservation
+    pub async fn handle_waitlist_promoted(&self, event: &WaitlistPromotedV1) -> HandleFlightReservedResult {
+        let id = event.reservation.value_ref();
+        let version = event.reservation.version();
+        let transaction = self.reservation_repository.set_begin(id).await?;
+
+        let reservation = transaction
+            .value_or(HandleFlightReservedError::UnknownReservation(*id))?
+            .mark_flight_as_reserved(&event.flight, version);
+        let reservation = self.refresh_flight(reservation, &event.flight).await?;
 
         self.reservation_repository.set_commit(
             transaction.with_value(reservation.clone())
@@ -168,14 +739,51 @@ impl ReservationUseCase {
     pub async fn handle_flight_reservation_failed(&self, event: &FlightReservationFailedV1) -> HandleFlightReservationFailedResult {
         let id = event.reservation.value_ref();
         let version = event.reservation.version();
-        let reason = match event.reason {
-            AvailabilityFlightError::InsufficientSeats => ItineraryStageError::InsufficientSeats,
-        };
         let transaction = self.reservation_repository.set_begin(id).await?;
+        let reservation = transaction
+            .value_or(HandleFlightReservationFailedError::UnknownReservation(*id))?;
+
+        let reservation = match event.reason {
+            AvailabilityFlightError::InsufficientSeats => {
+                reservation.mark_flight_as_reserved_failed(&event.flight, &ItineraryStageError::InsufficientSeats, version)
+            }
+
+            AvailabilityFlightError::InsufficientRooms => {
+                reservation.mark_flight_as_reserved_failed(&event.flight, &ItineraryStageError::InsufficientRooms, version)
+            }
+
+            AvailabilityFlightError::FlightCancelled => {
+                reservation.mark_flight_as_cancelled(&event.flight, version)
+            }
+        };
+        let reservation = self.refresh_flight(reservation, &event.flight).await?;
 
+        self.reservation_repository.set_commit(
+            transaction.with_value(reservation.clone())
+        ).await?;
+
+        self.resolve_flights(&reservation).await
+            .map_err(|error| error.into())
+    }
+
+    pub async fn handle_flight_disrupted(&self, event: &FlightReservationDisruptedV1) -> HandleFlightDisruptedResult {
+        let id = &event.reservation;
+        let transaction = self.reservation_repository.set_begin(id).await?;
         let reservation = transaction
-            .value_or(HandleFlightReservationFailedError::UnknownReservation(*id))?
-            .mark_flight_as_reserved_failed(&event.flight, &reason, version);
+            .value_or(HandleFlightDisruptedError::UnknownReservation(*id))?;
+
+        let reservation = match &event.disruption {
+            FlightDisruption::Cancelled => {
+                reservation.mark_flight_as_disrupted(&event.flight, &ItineraryStageDisruption::FlightCancelled)
+            }
+
+            FlightDisruption::Rescheduled(flight) => {
+                let journey = self.journey_repository.get(&reservation.journey()).await?
+                    .ok_or(HandleFlightDisruptedError::UnknownJourney(reservation.journey()))?;
+
+                reservation.reschedule_flight(flight, &journey)
+            }
+        };
 
         self.reservation_repository.set_commit(
             transaction.with_value(reservation.clone())
@@ -218,16 +826,576 @@ impl ReservationUseCase {
     }
 
     /// resolve flights of a reservation (reserve, and/or annul);
+    /// stages resolved after their flight was rescheduled still hold the copy of before,
+    /// as only the reservations holding or waiting for seats are told about it
+    async fn refresh_flight(&self, reservation: Reservation, flight: &FlightId) -> Result<Reservation, Error> {
+        let flight = match self.flight_availability_repository.get(flight).await.map_err(Error::other)? {
+            Some(availability) if reservation.holds_outdated_flight(&availability.flight) => availability.flight,
+            _ => return Ok(reservation),
+        };
+
+        let journey = self.journey_repository.get(&reservation.journey()).await
+            .map_err(Error::other)?
+            .ok_or(Error::other("unknown journey"))?;
+
+        Ok(reservation.reschedule_flight(&flight, &journey))
+    }
+
     /// works similar to an orchestration-based saga, meaning 1 aggregate at a time is changed in a single transaction (event driven)
     /// converses reservation to the correct state, 1 event at a time
     async fn resolve_flights(&self, reservation: &Reservation) -> Result<(), Error> {
         if let Some(request) = flight_reservation_strategy::next_request(reservation) {
             let event: Event = request.try_into()
-                .map_err(|error| Error::new(ErrorKind::Other, error))?;
+                .map_err(Error::other)?;
 
             self.event_publisher.send(event).await
-                .map_err(|error| Error::new(ErrorKind::Other, error))?;
+                .map_err(Error::other)?;
         }
         Ok(())
     }
 }
+
+/// a refund is identified by the payment and the amount of it refunded before,
+/// such that a retry is refunded once by the gateway
+fn refund_idempotency_key(reservation: &ReservationId, payment: &PaymentReference, refunded: Money) -> String {
+    [reservation.to_string(), payment.to_string(), refunded.minor_units().to_string()].join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use chrono::{DateTime, Duration, Utc};
+    use futures::executor::block_on;
+    use futures::FutureExt;
+    use prelude::async_trait;
+    use prelude::domain::Transaction;
+    use prelude::data::{Currency, Money, MoneyError};
+    use crate::aggregate::{CheckInError, EmailVerificationError, EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS, FlightAvailability, FlightId, FlightRoute, ItineraryStage, PassengerArrangement, Payment, PaymentError, PaymentReference, Reservation, ReservationId, SeatAssignmentError};
+    use crate::command::{AssignSeats, AssignSeatsError, BoardPassenger, BoardPassengerError, CancelReservation, CheckInPassengers, CloseBoarding, CloseBoardingError, ConfirmHeldReservation, GetReservationPayments, GetReservationPrice, PayReservation, PayReservationError, QuoteReservation, QuoteReservationError, QuoteReservationResult, RefundReservation, ReleaseExpiredHolds, RequestContactEmailVerification, ReservationPayments, SearchItineraries, VerifyContactEmail, VerifyContactEmailError};
+    use crate::event::{Event, ReservationCancelledV1};
+    use crate::gateway::{PaymentGateway, PaymentGatewayError};
+    use crate::in_memory::{FakePaymentGateway, InMemoryReservationRepository, FAKE_DECLINED_PAYMENT_METHOD};
+    use crate::in_memory::fixture::{all_stages, contact, converter, flight_eham_enli, flight_enli_eham, round_trip, TestContext};
+    use crate::policy::ReservationPolicy;
+    use crate::repository::{FlightAvailabilityRepository, FlightAvailabilityRepositoryError, ReservationRepository};
+    use crate::usecase::ReservationUseCase;
+    use crate::usecase::reservation::refund_idempotency_key;
+
+    #[test]
+    fn quote_equals_price_of_confirmed_reservation() {
+        let context = TestContext::build();
+
+        let quote = get_quote(&context, None).unwrap();
+
+        let id = context.confirm(2);
+        let price = block_on(context.reservation_usecase.price(GetReservationPrice { id }, &ReservationPolicy::test())).unwrap();
+
+        assert_eq!(quote, price);
+        assert_eq!(quote.adults, 2);
+    }
+
+    #[test]
+    fn quote_in_customers_currency() {
+        let context = TestContext::build();
+
+        let quote = get_quote(&context, Some(Currency::EUR)).unwrap();
+
+        // 2 passengers, 7 days at 120.00 USD = 1680.00 USD
+        assert_eq!(quote.amount_due, Money::from_minor_units(154560, Currency::EUR));
+
+        assert_eq!(get_quote(&context, Some(Currency::GBP)), Err(QuoteReservationError::MoneyError(MoneyError::NoExchangeRate(Currency::USD, Currency::GBP))));
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn payments_settle_the_balance() {
+        let context = TestContext::build();
+        let id = context.confirm(2);
+
+        let result = pay(&context, id, Money::usd(1000_00), FAKE_DECLINED_PAYMENT_METHOD);
+        assert!(matches!(result, Err(PayReservationError::PaymentGatewayError(_))));
+
+        pay(&context, id, Money::usd(1000_00), "tok_visa").unwrap();
+
+        // 2 passengers, 7 days at 120.00 USD = 1680.00 USD
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.amount_due, Money::usd(1680_00));
+        assert_eq!(payments.balance, Money::usd(680_00));
+        assert_eq!(payments.payments.payments().len(), 1);
+
+        let result = pay(&context, id, Money::usd(680_01), "tok_visa");
+        assert_eq!(result, Err(PayReservationError::PaymentError(PaymentError::AmountExceedsBalance)));
+
+        pay(&context, id, Money::usd(680_00), "tok_visa").unwrap();
+
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.balance, Money::usd(0));
+        assert_eq!(context.payment_gateway.charges().len(), 2);
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn payment_conflicting_with_concurrent_commit_is_charged_once() {
+        let context = TestContext::build();
+        let id = context.confirm(2);
+        let payment_gateway = Rc::new(ConflictingPaymentGateway::default());
+        let reservation_usecase = ReservationUseCase::new(context.repository.clone(), context.repository.clone(), context.repository.clone(), context.publisher.clone(), payment_gateway.clone());
+
+        // e.g. a revision, after which the payment is still acceptable
+        CONCURRENT_COMMIT.set(Some((context.repository.clone(), |reservation| reservation)));
+        let reference = block_on(reservation_usecase.pay(PayReservation {
+            id,
+            amount: Money::usd(1000_00),
+            payment_method: "tok_visa".to_owned(),
+            now: Utc::now(),
+        }, &ReservationPolicy::test())).unwrap();
+
+        let payments = block_on(reservation_usecase.payments(GetReservationPayments { id }, &ReservationPolicy::test())).unwrap();
+        assert_eq!(payments.payments.payments().iter().map(|payment| &payment.reference).collect::<Vec<_>>(), vec![&reference]);
+        assert_eq!(payment_gateway.gateway.charges().len(), 1);
+
+        // another payment, after which it isn't
+        CONCURRENT_COMMIT.set(Some((context.repository.clone(), |reservation| {
+            let (reservation, _) = reservation.record_payment(Money::usd(1680_00), Payment {
+                reference: "pay_concurrent".parse().unwrap(),
+                amount: Money::usd(680_00),
+                time: Utc::now(),
+            }).unwrap();
+
+            reservation
+        })));
+        let result = block_on(reservation_usecase.pay(PayReservation {
+            id,
+            amount: Money::usd(680_00),
+            payment_method: "tok_visa".to_owned(),
+            now: Utc::now(),
+        }, &ReservationPolicy::test()));
+        assert_eq!(result, Err(PayReservationError::PaymentError(PaymentError::AmountExceedsBalance)));
+
+        let charges = payment_gateway.gateway.charges();
+        let refunds = payment_gateway.gateway.refunds();
+        assert_eq!(charges.len(), 2);
+        assert_eq!(refunds.len(), 1);
+        assert_eq!((&refunds[0].1, refunds[0].2), (&charges[1].0, Money::usd(680_00)));
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn cancellation_refunds_by_refund_percentage() {
+        let context = TestContext::build();
+        let id = context.confirm(2);
+
+        pay(&context, id, Money::usd(1000_00), "tok_visa").unwrap();
+        pay(&context, id, Money::usd(680_00), "tok_visa").unwrap();
+
+        context.cancel(id);
+
+        // half of 1680.00 USD is refunded, most recent payment first
+        let refunds: Vec<_> = context.payment_gateway.refunds().into_iter()
+            .map(|(_, payment, amount)| (payment.to_string(), amount))
+            .collect();
+        assert_eq!(refunds, vec![
+            ("pay_2".to_owned(), Money::usd(680_00)),
+            ("pay_1".to_owned(), Money::usd(160_00)),
+        ]);
+
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.amount_due, Money::usd(840_00));
+        assert_eq!(payments.balance, Money::usd(0));
+        assert_eq!(payments.payments.refunds().len(), 2);
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn refund_is_retried_after_cancellation() {
+        let context = TestContext::build();
+        let id = context.confirm(2);
+
+        let payment = pay(&context, id, Money::usd(1680_00), "tok_visa").unwrap();
+
+        // issued, but not recorded, e.g. as the handling of the cancellation was interrupted
+        block_on(context.reservation_usecase.cancel(CancelReservation { id }, &ReservationPolicy::test())).unwrap();
+        let key = refund_idempotency_key(&id, &payment, Money::usd(0));
+        let reference = block_on(context.payment_gateway.refund(&id, &payment, Money::usd(840_00), &key)).unwrap();
+
+        context.deliver();
+        assert_eq!(context.payment_gateway.refunds().len(), 1);
+
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.payments.refunds().iter().map(|refund| &refund.reference).collect::<Vec<_>>(), vec![&reference]);
+        assert_eq!(payments.balance, Money::usd(0));
+
+        // redelivered
+        context.publish(ReservationCancelledV1 { id, journey: context.journey, contact: contact() });
+        context.deliver();
+        assert_eq!(context.payment_gateway.refunds().len(), 1);
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn refund_is_recorded_despite_concurrent_commit() {
+        let context = TestContext::build();
+        let id = context.confirm(2);
+
+        pay(&context, id, Money::usd(1680_00), "tok_visa").unwrap();
+        block_on(context.reservation_usecase.cancel(CancelReservation { id }, &ReservationPolicy::test())).unwrap();
+
+        let payment_gateway = Rc::new(ConflictingPaymentGateway::default());
+        let reservation_usecase = ReservationUseCase::new(context.repository.clone(), context.repository.clone(), context.repository.clone(), context.publisher.clone(), payment_gateway.clone());
+
+        // e.g. a revision of the contact
+        CONCURRENT_COMMIT.set(Some((context.repository.clone(), |reservation| reservation)));
+        let references = block_on(reservation_usecase.refund(RefundReservation { id }, &ReservationPolicy::test())).unwrap();
+        assert_eq!(payment_gateway.gateway.refunds().len(), 1);
+
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.payments.refunds().iter().map(|refund| &refund.reference).collect::<Vec<_>>(), references.iter().collect::<Vec<_>>());
+        assert_eq!(payments.balance, Money::usd(0));
+    }
+
+    #[test]
+    fn expired_hold_releases_its_seats() {
+        let context = TestContext::build();
+
+        let id = context.hold(2);
+        context.deliver();
+
+        let reservation = context.get(id);
+        assert!(matches!(reservation, Reservation::Held(_)));
+        assert!(all_stages(&reservation, |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 1);
+
+        // not expired yet
+        let released = block_on(context.reservation_usecase.release_expired_holds(ReleaseExpiredHolds { now: Utc::now() })).unwrap();
+        assert!(released.is_empty());
+
+        // once expired, paying doesn't confirm it, even before it's released
+        let result = block_on(context.reservation_usecase.pay(PayReservation {
+            id,
+            amount: Money::usd(10000),
+            payment_method: "tok_visa".to_owned(),
+            now: Utc::now() + Duration::minutes(16),
+        }, &ReservationPolicy::test()));
+        assert_eq!(result, Err(PayReservationError::PaymentError(PaymentError::HoldExpired)));
+        assert!(context.payment_gateway.charges().is_empty());
+
+        let released = block_on(context.reservation_usecase.release_expired_holds(ReleaseExpiredHolds { now: Utc::now() + Duration::minutes(16) })).unwrap();
+        assert_eq!(released, vec![id]);
+        context.deliver();
+
+        assert!(matches!(context.get(id), Reservation::Cancelled(_)));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 3);
+    }
+
+    #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
+    fn released_hold_is_refunded_in_full() {
+        let context = TestContext::build();
+
+        let id = context.hold(2);
+        context.deliver();
+
+        pay(&context, id, Money::usd(500_00), "tok_visa").unwrap();
+        assert!(matches!(context.get(id), Reservation::Held(_)));
+
+        let released = block_on(context.reservation_usecase.release_expired_holds(ReleaseExpiredHolds { now: Utc::now() + Duration::minutes(16) })).unwrap();
+        assert_eq!(released, vec![id]);
+        context.deliver();
+
+        // rather than by the refund tiers, which would retain half
+        let refunds: Vec<_> = context.payment_gateway.refunds().into_iter()
+            .map(|(_, payment, amount)| (payment.to_string(), amount))
+            .collect();
+        assert_eq!(refunds, vec![("pay_1".to_owned(), Money::usd(500_00))]);
+
+        let payments = get_payments(&context, id);
+        assert_eq!(payments.amount_due, Money::usd(0));
+        assert_eq!(payments.balance, Money::usd(0));
+    }
+
+    #[test]
+    fn confirmed_hold_keeps_its_seats() {
+        let context = TestContext::build();
+
+        let id = context.hold(2);
+        context.deliver();
+
+        block_on(context.reservation_usecase.confirm_hold(ConfirmHeldReservation { id }, &ReservationPolicy::test())).unwrap();
+
+        let released = block_on(context.reservation_usecase.release_expired_holds(ReleaseExpiredHolds { now: Utc::now() + Duration::minutes(16) })).unwrap();
+        assert!(released.is_empty());
+        context.deliver();
+
+        let reservation = context.get(id);
+        assert!(matches!(reservation, Reservation::Confirmed(_)));
+        assert!(all_stages(&reservation, |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert_eq!(availability.seats_available(), 1);
+    }
+
+    #[test]
+    fn listed_passengers_are_assigned_seats_and_pay_for_chosen_window_seats() {
+        let context = TestContext::build();
+        let id = context.reserve(2);
+
+        let assign_seats = || block_on(context.reservation_usecase.assign_seats(AssignSeats {
+            reservation: id,
+            flight: flight_eham_enli().id,
+            seats: vec![("Jane Doe".parse().unwrap(), "1A".parse().unwrap())],
+        }, &ReservationPolicy::test()));
+        assert_eq!(assign_seats(), Err(AssignSeatsError::SeatAssignmentError(SeatAssignmentError::PassengersNotListed)));
+
+        context.list_passengers(id, ["Jane Doe", "John Doe"]);
+        assert!(all_stages(&context.get(id), |stage| matches!(stage, ItineraryStage::Reserved(_, _))));
+
+        let seats: Vec<_> = assign_seats().unwrap().into_iter()
+            .map(|seat| (seat.passenger.to_string(), seat.seat.to_string(), seat.chosen))
+            .collect();
+        assert_eq!(seats, vec![
+            ("Jane Doe".to_owned(), "1A".to_owned(), true),
+            ("John Doe".to_owned(), "1B".to_owned(), false),
+        ]);
+        assert_eq!(context.get(id).seats().len(), 2);
+
+        let price = block_on(context.reservation_usecase.price(GetReservationPrice { id }, &ReservationPolicy::test())).unwrap();
+        assert_eq!(price.window_seats, 1);
+        assert_eq!(price.seats, Money::usd(2500));
+
+        // revised passengers choose their seats anew
+        context.list_passengers(id, ["Jane Doe", "Jim Doe"]);
+
+        assert!(context.get(id).seats().is_empty());
+        let availability = context.flight_availability(&flight_eham_enli().id);
+        assert!(!availability.seat_taken(&"1A".parse().unwrap()));
+    }
+
+    #[test]
+    fn seats_are_restored_when_the_reservation_conflicts_with_a_concurrent_commit() {
+        let context = TestContext::build();
+        let id = context.reserve(2);
+        context.list_passengers(id, ["Jane Doe", "John Doe"]);
+
+        FLIGHT_AVAILABILITY_REPOSITORY.set(Some(context.repository.clone()));
+        let reservation_usecase = ReservationUseCase::new(context.repository.clone(), Rc::new(ConflictingFlightAvailabilityRepository), context.repository.clone(), context.publisher.clone(), context.payment_gateway.clone());
+        let assign_seats = || block_on(reservation_usecase.assign_seats(AssignSeats {
+            reservation: id,
+            flight: flight_eham_enli().id,
+            seats: vec![("Jane Doe".parse().unwrap(), "1A".parse().unwrap())],
+        }, &ReservationPolicy::test()));
+
+        // e.g. a contact revision, committed after the seats on the flight
+        CONCURRENT_COMMIT.set(Some((context.repository.clone(), |reservation| reservation)));
+        assert_eq!(assign_seats(), Err(AssignSeatsError::VersionConflict));
+        assert!(context.get(id).seats().is_empty());
+        assert!(context.flight_availability(&flight_eham_enli().id).seat_assignments.is_empty());
+
+        // retrying succeeds
+        assert_eq!(assign_seats().unwrap().len(), 2);
+        assert_eq!(context.get(id).seats().len(), 2);
+        assert!(context.flight_availability(&flight_eham_enli().id).seat_taken(&"1A".parse().unwrap()));
+    }
+
+    #[test]
+    fn passengers_board_with_boarding_pass_and_others_are_no_shows() {
+        let context = TestContext::build();
+        let id = context.reserve(2);
+        context.list_passengers(id, ["Jane Doe", "John Doe"]);
+
+        let boarding_passes = block_on(context.reservation_usecase.check_in(CheckInPassengers {
+            reservation: id,
+            flight: flight_eham_enli().id,
+            passengers: vec!["Jane Doe".parse().unwrap()],
+            now: "2024-05-07T09:00:00Z".parse().unwrap(),
+        }, &ReservationPolicy::test())).unwrap();
+        assert_eq!(boarding_passes.len(), 1);
+
+        let board = |code: &str| block_on(context.reservation_usecase.board(BoardPassenger {
+            reservation: id,
+            flight: flight_eham_enli().id,
+            code: code.to_owned(),
+            now: "2024-05-08T03:30:00Z".parse().unwrap(),
+        }, &ReservationPolicy::test()));
+        assert_eq!(board("0000"), Err(BoardPassengerError::CheckInError(CheckInError::BoardingPassDontMatch)));
+        assert_eq!(board(boarding_passes[0].code()), Ok("Jane Doe".parse().unwrap()));
+
+        let close_boarding = |now: &str| block_on(context.reservation_usecase.close_boarding(CloseBoarding {
+            flight: flight_eham_enli().id,
+            now: now.parse().unwrap(),
+        }));
+        assert_eq!(close_boarding("2024-05-08T03:45:00Z"), Err(CloseBoardingError::CheckInError(CheckInError::FlightNotDeparted)));
+        assert_eq!(close_boarding("2024-05-08T04:15:00Z"), Ok(vec![id]));
+        context.deliver();
+
+        // closing again records no other no-shows, nor commits the reservation
+        let version = block_on(ReservationRepository::set_begin(context.repository.as_ref(), &id)).unwrap().version;
+        assert_eq!(close_boarding("2024-05-08T04:30:00Z"), Ok(vec![]));
+        assert_eq!(block_on(ReservationRepository::set_begin(context.repository.as_ref(), &id)).unwrap().version, version);
+
+        let csv = context.manifest(&flight_eham_enli().id).to_csv();
+        assert!(csv.contains(",Jane Doe,1980-01-01,,boarded,"));
+        assert!(csv.contains(",John Doe,1980-01-01,,no_show,"));
+    }
+
+    #[test]
+    fn searches_itineraries_with_seats_for_the_party() {
+        let context = TestContext::build();
+        context.reserve(2);
+
+        let search = |adults: u8| block_on(context.reservation_usecase.search(SearchItineraries {
+            journey: context.journey,
+            departure: "EHAM".parse().unwrap(),
+            passengers: PassengerArrangement::build(adults, 0).unwrap(),
+            earliest_departure: "2024-05-01".parse().unwrap(),
+            latest_departure: "2024-05-10".parse().unwrap(),
+            days: 7,
+            currency: None,
+        }, &converter())).unwrap();
+
+        let candidates = search(1);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].itinerary.stages().map(ItineraryStage::flight).collect::<Vec<_>>(), vec![flight_eham_enli().id, flight_enli_eham().id]);
+        assert_eq!(candidates[0].price.amount_due, Money::usd(84000));
+        assert!(search(2).is_empty());
+
+        context.reserve(1);
+        assert!(search(1).is_empty());
+    }
+
+    #[test]
+    fn invalidates_contact_email_challenge_after_too_many_failed_attempts() {
+        let context = TestContext::build();
+        let id = context.confirm(1);
+        let now = Utc::now();
+
+        block_on(context.reservation_usecase.request_contact_email_verification(RequestContactEmailVerification {
+            reservation: id,
+            now,
+        })).unwrap();
+        let challenge = context.events().into_iter()
+            .find_map(|event| match event {
+                Event::ContactEmailVerificationRequestedV1(requested) => Some(requested.challenge),
+                _ => None,
+            })
+            .unwrap();
+
+        let verify = |challenge: &str| block_on(context.reservation_usecase.verify_contact_email(VerifyContactEmail {
+            reservation: id,
+            challenge: challenge.to_owned(),
+            now,
+        }));
+
+        for _ in 0..EMAIL_VERIFICATION_MAX_FAILED_ATTEMPTS {
+            assert_eq!(verify("0000"), Err(VerifyContactEmailError::VerificationError(EmailVerificationError::ChallengeDontMatch)));
+        }
+
+        assert_eq!(verify(&challenge), Err(VerifyContactEmailError::VerificationError(EmailVerificationError::TooManyFailedAttempts)));
+        assert!(!context.get(id).contact().email_is_verified());
+    }
+
+    // test data
+    fn get_quote(context: &TestContext, currency: Option<Currency>) -> QuoteReservationResult {
+        block_on(context.reservation_usecase.quote(QuoteReservation {
+            journey: context.journey,
+            passengers: PassengerArrangement::build(2, 0).unwrap(),
+            itinerary: round_trip(),
+            currency,
+        }, &converter()))
+    }
+
+    fn get_payments(context: &TestContext, id: ReservationId) -> ReservationPayments {
+        block_on(context.reservation_usecase.payments(GetReservationPayments { id }, &ReservationPolicy::test())).unwrap()
+    }
+
+    fn pay(context: &TestContext, id: ReservationId, amount: Money, payment_method: &str) -> Result<PaymentReference, PayReservationError> {
+        block_on(context.reservation_usecase.pay(PayReservation {
+            id,
+            amount,
+            payment_method: payment_method.to_owned(),
+            now: Utc::now(),
+        }, &ReservationPolicy::test()))
+    }
+
+    type ConcurrentCommit = (Rc<InMemoryReservationRepository>, fn(Reservation) -> Reservation);
+
+    thread_local! {
+        /// committed to the reservation while it's charged or refunded, or its seats are assigned, once
+        static CONCURRENT_COMMIT: Cell<Option<ConcurrentCommit>> = const { Cell::new(None) };
+
+        /// the test context's, as repositories themselves should be Sync
+        static FLIGHT_AVAILABILITY_REPOSITORY: RefCell<Option<Rc<InMemoryReservationRepository>>> = const { RefCell::new(None) };
+    }
+
+    fn commit_concurrently(reservation: &ReservationId) {
+        if let Some((repository, commit)) = CONCURRENT_COMMIT.take() {
+            let transaction = ReservationRepository::set_begin(repository.as_ref(), reservation).now_or_never().unwrap().unwrap();
+            let value = commit(transaction.value_or(()).unwrap());
+            ReservationRepository::set_commit(repository.as_ref(), transaction.with_value(value)).now_or_never().unwrap().unwrap();
+        }
+    }
+
+    #[derive(Default)]
+    struct ConflictingPaymentGateway {
+        gateway: FakePaymentGateway,
+    }
+
+    #[async_trait]
+    impl PaymentGateway for ConflictingPaymentGateway {
+        async fn charge(&self, reservation: &ReservationId, amount: Money, payment_method: &str, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+            commit_concurrently(reservation);
+
+            self.gateway.charge(reservation, amount, payment_method, idempotency_key).await
+        }
+
+        async fn refund(&self, reservation: &ReservationId, payment: &PaymentReference, amount: Money, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+            commit_concurrently(reservation);
+
+            self.gateway.refund(reservation, payment, amount, idempotency_key).await
+        }
+    }
+
+    /// commits the concurrent revision of the reservation, if any, right after committing the flight
+    struct ConflictingFlightAvailabilityRepository;
+
+    fn flight_availability_repository() -> Rc<InMemoryReservationRepository> {
+        FLIGHT_AVAILABILITY_REPOSITORY.with_borrow(|repository| repository.clone().unwrap())
+    }
+
+    #[async_trait]
+    impl FlightAvailabilityRepository for ConflictingFlightAvailabilityRepository {
+        async fn get(&self, id: &FlightId) -> Result<Option<FlightAvailability>, FlightAvailabilityRepositoryError> {
+            FlightAvailabilityRepository::get(flight_availability_repository().as_ref(), id).now_or_never().unwrap()
+        }
+
+        async fn set_begin(&self, id: &FlightId) -> Result<Transaction<FlightId, FlightAvailability>, FlightAvailabilityRepositoryError> {
+            FlightAvailabilityRepository::set_begin(flight_availability_repository().as_ref(), id).now_or_never().unwrap()
+        }
+
+        async fn set_commit(&self, transaction: Transaction<FlightId, FlightAvailability>) -> Result<(), FlightAvailabilityRepositoryError> {
+            let reservation = transaction.value.iter()
+                .flat_map(|availability| availability.seat_assignments.iter().map(|assignment| assignment.reservation))
+                .next();
+
+            FlightAvailabilityRepository::set_commit(flight_availability_repository().as_ref(), transaction).now_or_never().unwrap()?;
+
+            if let Some(reservation) = reservation {
+                commit_concurrently(&reservation);
+            }
+
+            Ok(())
+        }
+
+        async fn list_due_for_reconciliation(&self, departing_before: DateTime<Utc>) -> Result<Vec<FlightId>, FlightAvailabilityRepositoryError> {
+            flight_availability_repository().list_due_for_reconciliation(departing_before).now_or_never().unwrap()
+        }
+
+        async fn list_by_route(&self, route: &FlightRoute, departing_after: DateTime<Utc>, departing_before: DateTime<Utc>) -> Result<Vec<FlightAvailability>, FlightAvailabilityRepositoryError> {
+            flight_availability_repository().list_by_route(route, departing_after, departing_before).now_or_never().unwrap()
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::io::Error;
 use thiserror::Error;
-use crate::aggregate::ReservationId;
-use crate::repository::ReservationRepositoryError;
+use crate::aggregate::{JourneyId, ReservationId};
+use crate::repository::{JourneyRepositoryError, ReservationRepositoryError};
 
 // handle flight reserved
 #[derive(Error, Debug, PartialEq)]
@@ -60,4 +60,55 @@ impl From<std::io::Error> for HandleFlightReservationFailedError {
     fn from(value: Error) -> Self {
         Self::IoError(value.to_string())
     }
-}
\ No newline at end of file
+}
+
+// handle flight disrupted
+#[derive(Error, Debug, PartialEq)]
+pub enum HandleFlightDisruptedError {
+    #[error("version conflict")]
+    VersionConflict,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown reservation")]
+    UnknownReservation(ReservationId),
+
+    #[error("unknown journey")]
+    UnknownJourney(JourneyId),
+}
+
+pub type HandleFlightDisruptedResult = Result<(), HandleFlightDisruptedError>;
+
+impl From<ReservationRepositoryError> for HandleFlightDisruptedError {
+    fn from(value: ReservationRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<JourneyRepositoryError> for HandleFlightDisruptedError {
+    fn from(value: JourneyRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+impl From<std::io::Error> for HandleFlightDisruptedError {
+    fn from(value: Error) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+// handle event
+#[derive(Error, Debug, PartialEq)]
+pub enum HandleEventError {
+    #[error("{0}")]
+    UseCaseError(String),
+}
+
+pub type HandleEventResult = Result<(), HandleEventError>;
+
+impl HandleEventError {
+    pub(crate) fn from_error(error: impl std::error::Error) -> Self {
+        Self::UseCaseError(error.to_string())
+    }
+}
@@ -32,6 +32,18 @@ impl Airship {
 
         (airship, event)
     }
+
+    pub fn name(&self) -> &AirshipName {
+        &self.name
+    }
+
+    pub fn model(&self) -> &AirshipModel {
+        &self.model
+    }
+
+    pub fn number_of_seats(&self) -> &AirshipNumberOfSeats {
+        &self.number_of_seats
+    }
 }
 
 impl Hash for Airship {
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Duration, FixedOffset};
+use thiserror::Error;
+use crate::aggregate::{Flight, FlightId, FlightStatus};
+
+/// flights further apart are not verified against each other,
+/// the airship is expected to be repositioned in between (e.g. for maintenance)
+pub const AIRSHIP_SCHEDULE_WINDOW_DAYS: i64 = 30;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AirshipScheduleError {
+    #[error("airship is already scheduled on flight {0}")]
+    OverlappingFlight(FlightId),
+
+    #[error("airship position is discontinuous with flight {0}")]
+    PositionDiscontinuity(FlightId),
+}
+
+/// (non-cancelled) flights of a single airship within a window,
+/// a flight fits in if the airship is available and departs from where it arrived last
+pub struct AirshipSchedule(Vec<Flight>);
+
+impl AirshipSchedule {
+    pub fn from_flights(flights: impl IntoIterator<Item=Flight>) -> Self {
+        let mut flights: Vec<Flight> = flights.into_iter()
+            .filter(|flight| flight.status() == &FlightStatus::Scheduled)
+            .collect();
+        flights.sort_by_key(|flight| flight.departure.time);
+
+        Self(flights)
+    }
+
+    /// window of flights to verify the flight against, a rescheduled flight is verified at its former slot too
+    pub fn window<'a>(flights: impl IntoIterator<Item=&'a Flight>) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        let window = Duration::days(AIRSHIP_SCHEDULE_WINDOW_DAYS);
+
+        flights.into_iter()
+            .map(|flight| (flight.departure.time - window, flight.arrival.time + window))
+            .reduce(|(from, until), (other_from, other_until)| (from.min(other_from), until.max(other_until)))
+            .expect("window of at least one flight")
+    }
+
+    /// flight itself (e.g. when rescheduled) is replaced, flights that become consecutive
+    /// (i.e. around the flight and around its former slot) are verified to be continuous
+    pub fn verify(&self, flight: &Flight) -> Result<(), AirshipScheduleError> {
+        let current: Vec<&Flight> = self.0.iter()
+            .filter(|other| other.airship == flight.airship)
+            .collect();
+
+        let mut next: Vec<&Flight> = current.iter()
+            .copied()
+            .filter(|other| other.id != flight.id)
+            .collect();
+
+        if let Some(overlapping) = next.iter().find(|other| other.departure.time < flight.arrival.time && other.arrival.time > flight.departure.time) {
+            return Err(AirshipScheduleError::OverlappingFlight(overlapping.id));
+        }
+
+        next.push(flight);
+        next.sort_by_key(|flight| flight.departure.time);
+
+        // discontinuities that are already there are not for this flight to resolve
+        let consecutive = |previous: &Flight, next: &Flight| current.windows(2)
+            .any(|pair| pair[0].id == previous.id && pair[1].id == next.id);
+
+        for pair in next.windows(2) {
+            let (previous, next) = (pair[0], pair[1]);
+
+            if previous.arrival.location != next.departure.location && !consecutive(previous, next) {
+                let discontinuous = if previous.id == flight.id { next } else { previous };
+                return Err(AirshipScheduleError::PositionDiscontinuity(discontinuous.id));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{Airfield, Airship, AirshipNumberOfSeats, AirshipSchedule, AirshipScheduleError, Flight, FlightCancellationReason, FlightId};
+
+    #[test]
+    fn accepts_consecutive_flights() {
+        let schedule = AirshipSchedule::from_flights([
+            flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00"),
+            flight(id3(), eham(), "2024-01-12T09:00:00+00:00", enli(), "2024-01-12T11:00:00+00:00"),
+        ]);
+
+        let result = schedule.verify(&flight(id2(), enli(), "2024-01-10T09:00:00+00:00", eham(), "2024-01-10T11:00:00+00:00"));
+        assert_eq!(result, Ok(()));
+
+        // first flight of airship can depart anywhere
+        let schedule = AirshipSchedule::from_flights([]);
+        let result = schedule.verify(&flight(id2(), enli(), "2024-01-10T09:00:00+00:00", eham(), "2024-01-10T11:00:00+00:00"));
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn errors_on_overlapping_flight() {
+        let schedule = AirshipSchedule::from_flights([
+            flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00"),
+        ]);
+
+        let result = schedule.verify(&flight(id2(), enli(), "2024-01-08T10:00:00+00:00", eham(), "2024-01-08T12:00:00+00:00"));
+        assert_eq!(result, Err(AirshipScheduleError::OverlappingFlight(id1())));
+    }
+
+    #[test]
+    fn errors_on_position_discontinuity() {
+        let schedule = AirshipSchedule::from_flights([
+            flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00"),
+            flight(id3(), eham(), "2024-01-12T09:00:00+00:00", enli(), "2024-01-12T11:00:00+00:00"),
+        ]);
+
+        // airship is at ENLI, not EHAM
+        let result = schedule.verify(&flight(id2(), eham(), "2024-01-10T09:00:00+00:00", enli(), "2024-01-10T11:00:00+00:00"));
+        assert_eq!(result, Err(AirshipScheduleError::PositionDiscontinuity(id1())));
+
+        // airship needs to be back at EHAM for the next flight
+        let schedule = AirshipSchedule::from_flights([
+            flight(id3(), eham(), "2024-01-12T09:00:00+00:00", enli(), "2024-01-12T11:00:00+00:00"),
+        ]);
+        let result = schedule.verify(&flight(id2(), eham(), "2024-01-10T09:00:00+00:00", enli(), "2024-01-10T11:00:00+00:00"));
+        assert_eq!(result, Err(AirshipScheduleError::PositionDiscontinuity(id3())));
+    }
+
+    #[test]
+    fn errors_on_position_discontinuity_at_former_slot() {
+        let schedule = AirshipSchedule::from_flights([
+            flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00"),
+            flight(id2(), enli(), "2024-01-10T09:00:00+00:00", eham(), "2024-01-10T11:00:00+00:00"),
+            flight(id3(), eham(), "2024-01-12T09:00:00+00:00", enli(), "2024-01-12T11:00:00+00:00"),
+        ]);
+
+        // airship would be left at ENLI for the flight departing EHAM
+        let result = schedule.verify(&flight(id2(), enli(), "2024-01-14T09:00:00+00:00", eham(), "2024-01-14T11:00:00+00:00"));
+        assert_eq!(result, Err(AirshipScheduleError::PositionDiscontinuity(id1())));
+
+        // rescheduling within its slot is fine
+        let result = schedule.verify(&flight(id2(), enli(), "2024-01-11T09:00:00+00:00", eham(), "2024-01-11T11:00:00+00:00"));
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn ignores_cancelled_flights_and_flight_itself() {
+        let (cancelled, _) = flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00")
+            .cancel(FlightCancellationReason::Weather)
+            .unwrap();
+        let schedule = AirshipSchedule::from_flights([cancelled]);
+
+        let result = schedule.verify(&flight(id2(), eham(), "2024-01-08T10:00:00+00:00", enli(), "2024-01-08T12:00:00+00:00"));
+        assert_eq!(result, Ok(()));
+
+        // rescheduled flight doesn't conflict with its former self
+        let schedule = AirshipSchedule::from_flights([
+            flight(id1(), eham(), "2024-01-08T09:00:00+00:00", enli(), "2024-01-08T11:00:00+00:00"),
+        ]);
+        let result = schedule.verify(&flight(id1(), eham(), "2024-01-08T10:00:00+00:00", enli(), "2024-01-08T12:00:00+00:00"));
+        assert_eq!(result, Ok(()));
+    }
+
+    // test data
+    fn flight(id: FlightId, departure_location: Airfield, departure_time: &str, arrival_location: Airfield, arrival_time: &str) -> Flight {
+        let (flight, _) = Flight::build(
+            id,
+            departure_location,
+            departure_time.parse().unwrap(),
+            arrival_location,
+            arrival_time.parse().unwrap(),
+            airship(),
+        ).unwrap();
+
+        flight
+    }
+
+    fn id1() -> FlightId {
+        "5EPFciXgSxB70tAE8iERl1".parse().unwrap()
+    }
+
+    fn id2() -> FlightId {
+        "5EPFciXgSxB70tAE8iERl2".parse().unwrap()
+    }
+
+    fn id3() -> FlightId {
+        "5EPFciXgSxB70tAE8iERl3".parse().unwrap()
+    }
+
+    fn airship() -> Airship {
+        let (airship, _) = Airship::build(
+            "PH-1A1".parse().unwrap(),
+            "Galaxy One".parse().unwrap(),
+            "Blimp 1".parse().unwrap(),
+            AirshipNumberOfSeats::try_from(10).unwrap(),
+        );
+
+        airship
+    }
+
+    fn enli() -> Airfield {
+        let (airfield, _) = Airfield::build(
+            "ENLI".parse().unwrap(),
+            "Farsund Airport".parse().unwrap(),
+            "u4kdwc".parse().unwrap()
+        );
+
+        airfield
+    }
+
+    fn eham() -> Airfield {
+        let (airfield, _) = Airfield::build(
+            "EHAM".parse().unwrap(),
+            "Amsterdam Airport".parse().unwrap(),
+            "u173se".parse().unwrap()
+        );
+
+        airfield
+    }
+}
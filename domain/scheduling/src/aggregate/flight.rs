@@ -2,11 +2,11 @@ use std::hash::{Hash, Hasher};
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use crate::aggregate::{Airfield, Airship, AirshipId};
+use crate::aggregate::{Airfield, Airship, AirshipId, FlightCancellationReason, FlightStatus};
 use crate::aggregate::flight_arrival::FlightArrival;
 use crate::aggregate::flight_departure::FlightDeparture;
 use crate::aggregate::flight_id::FlightId;
-use crate::event::{FlightScheduledV1, Airship as FlightScheduledV1Airship};
+use crate::event::{FlightCancelledV1, FlightRescheduledV1, FlightScheduledV1, Airship as FlightScheduledV1Airship};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum FlightError {
@@ -15,14 +15,19 @@ pub enum FlightError {
 
     #[error("departure is later then arrival")]
     DepartureIsLaterThenArrival,
+
+    #[error("flight is cancelled")]
+    FlightIsCancelled,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Flight {
     pub id: FlightId,
-    departure: FlightDeparture,
-    arrival: FlightArrival,
-    airship: AirshipId,
+    pub(crate) departure: FlightDeparture,
+    pub(crate) arrival: FlightArrival,
+    pub(crate) airship: AirshipId,
+    #[serde(default)]
+    status: FlightStatus,
 }
 
 impl Flight {
@@ -54,6 +59,7 @@ impl Flight {
                 time: arrival_time,
             },
             airship: airship.id.clone(),
+            status: FlightStatus::Scheduled,
         };
 
         let event = FlightScheduledV1 {
@@ -67,6 +73,7 @@ impl Flight {
                 time: arrival_time,
             },
             airship: FlightScheduledV1Airship {
+                model: airship.model().clone(),
                 id: airship.id,
                 number_of_seats: airship.number_of_seats,
             },
@@ -74,6 +81,77 @@ impl Flight {
 
         Ok((flight, event))
     }
+
+    pub fn cancel(self, reason: FlightCancellationReason) -> Result<(Self, FlightCancelledV1), FlightError> {
+        if let FlightStatus::Cancelled(_) = self.status {
+            return Err(FlightError::FlightIsCancelled);
+        }
+
+        let event = FlightCancelledV1 {
+            id: self.id,
+            reason,
+        };
+
+        let flight = Self {
+            status: FlightStatus::Cancelled(reason),
+            ..self
+        };
+
+        Ok((flight, event))
+    }
+
+    pub fn reschedule(self, departure_time: DateTime<FixedOffset>, arrival_time: DateTime<FixedOffset>) -> Result<(Self, FlightRescheduledV1), FlightError> {
+        if let FlightStatus::Cancelled(_) = self.status {
+            return Err(FlightError::FlightIsCancelled);
+        }
+
+        if departure_time >= arrival_time {
+            return Err(FlightError::DepartureIsLaterThenArrival);
+        }
+
+        let flight = Self {
+            departure: FlightDeparture {
+                time: departure_time,
+                ..self.departure
+            },
+            arrival: FlightArrival {
+                time: arrival_time,
+                ..self.arrival
+            },
+            ..self
+        };
+
+        let event = FlightRescheduledV1 {
+            id: flight.id,
+            departure: flight.departure.clone(),
+            arrival: flight.arrival.clone(),
+        };
+
+        Ok((flight, event))
+    }
+
+    pub fn departure(&self) -> &FlightDeparture {
+        &self.departure
+    }
+
+    pub fn arrival(&self) -> &FlightArrival {
+        &self.arrival
+    }
+
+    pub fn airship(&self) -> &AirshipId {
+        &self.airship
+    }
+
+    pub fn status(&self) -> &FlightStatus {
+        &self.status
+    }
+
+    /// flight is operated by the airship, and (partially) takes place between from and until
+    pub fn is_scheduled_within(&self, airship: &AirshipId, from: DateTime<FixedOffset>, until: DateTime<FixedOffset>) -> bool {
+        &self.airship == airship
+            && self.departure.time < until
+            && self.arrival.time > from
+    }
 }
 
 impl PartialEq for Flight {
@@ -91,7 +169,7 @@ impl Hash for Flight {
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, FixedOffset};
-    use crate::aggregate::{Airfield, Airship, AirshipNumberOfSeats, FlightId};
+    use crate::aggregate::{Airfield, Airship, AirshipNumberOfSeats, FlightCancellationReason, FlightId, FlightStatus};
     use crate::aggregate::flight::{Flight, FlightError};
     use prelude::domain::Event;
 
@@ -193,6 +271,90 @@ mod tests {
         assert!(data.is_ok());
     }
 
+    #[test]
+    fn can_be_cancelled() {
+        let (flight, _) = Flight::build(
+            flight_id(),
+            airfield_eham(),
+            datetime_departure(),
+            airfield_enli(),
+            datetime_arrival(),
+            airship(),
+        ).unwrap();
+
+        let (flight, event) = flight.cancel(FlightCancellationReason::Weather).unwrap();
+        assert_eq!(flight.status(), &FlightStatus::Cancelled(FlightCancellationReason::Weather));
+        assert_eq!(event.id, flight_id());
+        assert_eq!(event.reason, FlightCancellationReason::Weather);
+
+        // cannot be cancelled twice
+        let result = flight.cancel(FlightCancellationReason::Technical);
+        assert_eq!(result.map(|_| ()), Err(FlightError::FlightIsCancelled));
+    }
+
+    #[test]
+    fn can_be_rescheduled() {
+        let (flight, _) = Flight::build(
+            flight_id(),
+            airfield_eham(),
+            datetime_departure(),
+            airfield_enli(),
+            datetime_arrival(),
+            airship(),
+        ).unwrap();
+
+        let departure: DateTime<FixedOffset> = "2024-01-09T09:00:00+05:00".parse().unwrap();
+        let arrival: DateTime<FixedOffset> = "2024-01-09T12:00:00+05:00".parse().unwrap();
+
+        let (flight, event) = flight.reschedule(departure, arrival).unwrap();
+        assert_eq!(flight.departure.time, departure);
+        assert_eq!(flight.departure.location, airfield_eham().id);
+        assert_eq!(flight.arrival.time, arrival);
+        assert_eq!(flight.arrival.location, airfield_enli().id);
+        assert_eq!(event.id, flight_id());
+        assert_eq!(event.departure, flight.departure);
+        assert_eq!(event.arrival, flight.arrival);
+
+        // errors if departure is later or equal then arrival
+        let result = flight.clone().reschedule(arrival, departure);
+        assert_eq!(result.map(|_| ()), Err(FlightError::DepartureIsLaterThenArrival));
+
+        // cannot be rescheduled once cancelled
+        let (flight, _) = flight.cancel(FlightCancellationReason::Weather).unwrap();
+        let result = flight.reschedule(departure, arrival);
+        assert_eq!(result.map(|_| ()), Err(FlightError::FlightIsCancelled));
+    }
+
+    #[test]
+    fn is_scheduled_within_window() {
+        let (flight, _) = Flight::build(
+            flight_id(),
+            airfield_eham(),
+            datetime_departure(),
+            airfield_enli(),
+            datetime_arrival(),
+            airship(),
+        ).unwrap();
+
+        let before: DateTime<FixedOffset> = "2024-01-08T08:00:00+05:00".parse().unwrap();
+        let during: DateTime<FixedOffset> = "2024-01-08T10:00:00+05:00".parse().unwrap();
+        let after: DateTime<FixedOffset> = "2024-01-08T12:00:00+05:00".parse().unwrap();
+
+        assert!(flight.is_scheduled_within(&airship().id, before, after));
+        assert!(flight.is_scheduled_within(&airship().id, during, after));
+        assert!(!flight.is_scheduled_within(&airship().id, datetime_arrival(), after));
+        assert!(!flight.is_scheduled_within(&airship().id, before, datetime_departure()));
+        assert!(!flight.is_scheduled_within(&"PH-1A2".parse().unwrap(), before, after));
+    }
+
+    #[test]
+    fn is_deserializable_without_status() {
+        let serialized = r#"{"id":"5EPFciXgSxB70tAE8iERl6","departure":{"location":"EHAM","time":"2024-01-08T09:00:00+05:00"},"arrival":{"location":"ENLI","time":"2024-01-08T11:00:00+05:00"},"airship":"PH-1A1"}"#;
+        let deserialized: Flight = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(deserialized.status(), &FlightStatus::Scheduled);
+    }
+
     // test data
     fn flight_id() -> FlightId {
         "5EPFciXgSxB70tAE8iERl6".parse().unwrap()
@@ -46,7 +46,7 @@ mod tests {
     #[test]
     fn is_serializable() {
         let original: FlightId = "5EPFciXgSxB70tAE8iERl6".parse().unwrap();
-        let serialized = serde_json::to_value(&original).unwrap();
+        let serialized = serde_json::to_value(original).unwrap();
         let deserialized: FlightId = serde_json::from_value(serialized).unwrap();
 
         assert_eq!(original, deserialized);
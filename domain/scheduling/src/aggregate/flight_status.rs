@@ -0,0 +1,54 @@
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub enum FlightStatus {
+    #[default]
+    Scheduled,
+    Cancelled(FlightCancellationReason),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+pub enum FlightCancellationReason {
+    Weather,
+    Technical,
+    Operational,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FlightCancellationReasonError {
+    #[error("malformed value")]
+    MalformedValue,
+}
+
+impl FromStr for FlightCancellationReason {
+    type Err = FlightCancellationReasonError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "weather" => Ok(Self::Weather),
+            "technical" => Ok(Self::Technical),
+            "operational" => Ok(Self::Operational),
+            _ => Err(FlightCancellationReasonError::MalformedValue)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{FlightCancellationReason, FlightCancellationReasonError};
+
+    #[test]
+    fn can_parse_reason() {
+        assert_eq!("weather".parse(), Ok(FlightCancellationReason::Weather));
+        assert_eq!("technical".parse(), Ok(FlightCancellationReason::Technical));
+        assert_eq!("operational".parse(), Ok(FlightCancellationReason::Operational));
+    }
+
+    #[test]
+    fn errors_on_malformed_reason() {
+        let result: Result<FlightCancellationReason, _> = "storm".parse();
+        assert_eq!(result, Err(FlightCancellationReasonError::MalformedValue));
+    }
+}
@@ -11,6 +11,8 @@ mod flight_departure;
 mod flight_arrival;
 mod airfield_id;
 mod airship_number_of_seats;
+mod flight_status;
+mod airship_schedule;
 
 pub use airship_id::*;
 pub use airship::*;
@@ -25,4 +27,5 @@ pub use flight_id::*;
 pub use flight_departure::*;
 pub use flight_arrival::*;
 pub use airfield_id::*;
-
+pub use flight_status::*;
+pub use airship_schedule::*;
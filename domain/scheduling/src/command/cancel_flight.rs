@@ -0,0 +1,55 @@
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{FlightCancellationReason, FlightError, FlightId};
+use crate::repository::FlightRepositoryError;
+
+pub struct CancelFlight {
+    pub flight: FlightId,
+    pub reason: FlightCancellationReason,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CancelFlightError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight")]
+    UnknownFlight,
+
+    #[error("malformed flight: {0}")]
+    MalformedFlight(FlightError),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+pub type CancelFlightResult = Result<FlightId, CancelFlightError>;
+
+// transformers
+impl From<FlightRepositoryError> for CancelFlightError {
+    fn from(value: FlightRepositoryError) -> Self {
+        match value {
+            FlightRepositoryError::IoError(reason) => Self::IoError(reason),
+            FlightRepositoryError::NotFound => Self::UnknownFlight,
+            FlightRepositoryError::VersionConflict => Self::VersionConflict,
+        }
+    }
+}
+
+impl From<FlightError> for CancelFlightError {
+    fn from(value: FlightError) -> Self {
+        Self::MalformedFlight(value)
+    }
+}
+
+impl From<EventTryIntoError> for CancelFlightError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to serialize event".to_owned())
+    }
+}
+
+impl From<EventPublishError> for CancelFlightError {
+    fn from(_: EventPublishError) -> Self {
+        Self::IoError("unable to publish event".to_owned())
+    }
+}
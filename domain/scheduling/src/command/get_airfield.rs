@@ -0,0 +1,24 @@
+use thiserror::Error;
+use crate::aggregate::{Airfield, AirfieldId};
+use crate::repository::AirfieldRepositoryError;
+
+pub struct GetAirfield {
+    pub id: AirfieldId
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetAirfieldError {
+    #[error("unknown airfield")]
+    UnknownAirfield,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<AirfieldRepositoryError> for GetAirfieldError {
+    fn from(value: AirfieldRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type GetAirfieldResult = Result<Airfield, GetAirfieldError>;
@@ -0,0 +1,24 @@
+use thiserror::Error;
+use crate::aggregate::{Airship, AirshipId};
+use crate::repository::AirshipRepositoryError;
+
+pub struct GetAirship {
+    pub id: AirshipId
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetAirshipError {
+    #[error("unknown airship")]
+    UnknownAirship,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<AirshipRepositoryError> for GetAirshipError {
+    fn from(value: AirshipRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type GetAirshipResult = Result<Airship, GetAirshipError>;
@@ -0,0 +1,24 @@
+use thiserror::Error;
+use crate::aggregate::{Flight, FlightId};
+use crate::repository::FlightRepositoryError;
+
+pub struct GetFlight {
+    pub id: FlightId
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetFlightError {
+    #[error("unknown flight")]
+    UnknownFlight,
+
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<FlightRepositoryError> for GetFlightError {
+    fn from(value: FlightRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type GetFlightResult = Result<Flight, GetFlightError>;
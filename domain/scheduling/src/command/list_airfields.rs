@@ -0,0 +1,19 @@
+use thiserror::Error;
+use crate::aggregate::Airfield;
+use crate::repository::AirfieldRepositoryError;
+
+pub struct ListAirfields;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ListAirfieldsError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<AirfieldRepositoryError> for ListAirfieldsError {
+    fn from(value: AirfieldRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type ListAirfieldsResult = Result<Vec<Airfield>, ListAirfieldsError>;
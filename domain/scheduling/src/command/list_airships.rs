@@ -0,0 +1,19 @@
+use thiserror::Error;
+use crate::aggregate::Airship;
+use crate::repository::AirshipRepositoryError;
+
+pub struct ListAirships;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ListAirshipsError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<AirshipRepositoryError> for ListAirshipsError {
+    fn from(value: AirshipRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type ListAirshipsResult = Result<Vec<Airship>, ListAirshipsError>;
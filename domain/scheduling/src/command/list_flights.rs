@@ -0,0 +1,25 @@
+use chrono::{DateTime, FixedOffset};
+use thiserror::Error;
+use crate::aggregate::{AirfieldId, AirshipId, Flight};
+use crate::repository::FlightRepositoryError;
+
+pub struct ListFlights {
+    pub airship: Option<AirshipId>,
+    pub airfield: Option<AirfieldId>,
+    pub from: Option<DateTime<FixedOffset>>,
+    pub until: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ListFlightsError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+impl From<FlightRepositoryError> for ListFlightsError {
+    fn from(value: FlightRepositoryError) -> Self {
+        Self::IoError(value.to_string())
+    }
+}
+
+pub type ListFlightsResult = Result<Vec<Flight>, ListFlightsError>;
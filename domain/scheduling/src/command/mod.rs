@@ -1,7 +1,23 @@
 mod add_airship_to_fleet;
 mod register_airfield;
 mod schedule_flight;
+mod cancel_flight;
+mod reschedule_flight;
+mod get_airfield;
+mod get_airship;
+mod get_flight;
+mod list_airfields;
+mod list_airships;
+mod list_flights;
 
 pub use add_airship_to_fleet::*;
 pub use register_airfield::*;
 pub use schedule_flight::*;
+pub use cancel_flight::*;
+pub use reschedule_flight::*;
+pub use get_airfield::*;
+pub use get_airship::*;
+pub use get_flight::*;
+pub use list_airfields::*;
+pub use list_airships::*;
+pub use list_flights::*;
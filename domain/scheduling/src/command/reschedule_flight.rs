@@ -0,0 +1,85 @@
+use chrono::{DateTime, FixedOffset};
+use thiserror::Error;
+use prelude::domain::{EventPublishError, EventTryIntoError};
+use crate::aggregate::{AirshipScheduleError, FlightError, FlightId};
+use crate::repository::{AirshipRepositoryError, FlightRepositoryError};
+
+pub struct RescheduleFlight {
+    pub flight: FlightId,
+    pub departure_time: DateTime<FixedOffset>,
+    pub arrival_time: DateTime<FixedOffset>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RescheduleFlightError {
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    #[error("unknown flight")]
+    UnknownFlight,
+
+    #[error("unknown airship")]
+    UnknownAirship,
+
+    #[error("malformed flight: {0}")]
+    MalformedFlight(FlightError),
+
+    #[error("airship is already scheduled on flight {0}")]
+    AirshipOverlapsWithFlight(FlightId),
+
+    #[error("airship position is discontinuous with flight {0}")]
+    AirshipPositionDiscontinuousWithFlight(FlightId),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+pub type RescheduleFlightResult = Result<FlightId, RescheduleFlightError>;
+
+// transformers
+impl From<FlightRepositoryError> for RescheduleFlightError {
+    fn from(value: FlightRepositoryError) -> Self {
+        match value {
+            FlightRepositoryError::IoError(reason) => Self::IoError(reason),
+            FlightRepositoryError::NotFound => Self::UnknownFlight,
+            FlightRepositoryError::VersionConflict => Self::VersionConflict,
+        }
+    }
+}
+
+impl From<AirshipRepositoryError> for RescheduleFlightError {
+    fn from(value: AirshipRepositoryError) -> Self {
+        match value {
+            AirshipRepositoryError::IoError(reason) => Self::IoError(reason),
+            AirshipRepositoryError::NotFound => Self::UnknownAirship,
+            AirshipRepositoryError::VersionConflict => Self::VersionConflict,
+        }
+    }
+}
+
+impl From<FlightError> for RescheduleFlightError {
+    fn from(value: FlightError) -> Self {
+        Self::MalformedFlight(value)
+    }
+}
+
+impl From<AirshipScheduleError> for RescheduleFlightError {
+    fn from(value: AirshipScheduleError) -> Self {
+        match value {
+            AirshipScheduleError::OverlappingFlight(flight) => Self::AirshipOverlapsWithFlight(flight),
+            AirshipScheduleError::PositionDiscontinuity(flight) => Self::AirshipPositionDiscontinuousWithFlight(flight),
+        }
+    }
+}
+
+impl From<EventTryIntoError> for RescheduleFlightError {
+    fn from(_: EventTryIntoError) -> Self {
+        Self::IoError("unable to serialize event".to_owned())
+    }
+}
+
+impl From<EventPublishError> for RescheduleFlightError {
+    fn from(_: EventPublishError) -> Self {
+        Self::IoError("unable to publish event".to_owned())
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, FixedOffset};
 use thiserror::Error;
 use prelude::domain::{EventPublishError, EventTryIntoError};
-use crate::aggregate::{AirfieldId, AirshipId, FlightError, FlightId};
+use crate::aggregate::{AirfieldId, AirshipId, AirshipScheduleError, FlightError, FlightId};
 use crate::command::{AddAirshipToFleetError, RegisterAirfieldError};
 use crate::repository::{AirfieldRepositoryError, AirshipRepositoryError, FlightRepositoryError};
 
@@ -30,6 +30,12 @@ pub enum ScheduleFlightError {
     #[error("malformed flight: {0}")]
     MalformedFlight(FlightError),
 
+    #[error("airship is already scheduled on flight {0}")]
+    AirshipOverlapsWithFlight(FlightId),
+
+    #[error("airship position is discontinuous with flight {0}")]
+    AirshipPositionDiscontinuousWithFlight(FlightId),
+
     #[error("version conflict")]
     VersionConflict,
 }
@@ -62,7 +68,7 @@ impl From<AirshipRepositoryError> for ScheduleFlightError {
         match value {
             AirshipRepositoryError::IoError(reason) => Self::IoError(reason),
             AirshipRepositoryError::NotFound => Self::UnknownAirship,
-            AirshipRepositoryError::VersionConflict => Self::UnknownAirship,
+            AirshipRepositoryError::VersionConflict => Self::VersionConflict,
         }
     }
 }
@@ -73,6 +79,15 @@ impl From<FlightError> for ScheduleFlightError {
     }
 }
 
+impl From<AirshipScheduleError> for ScheduleFlightError {
+    fn from(value: AirshipScheduleError) -> Self {
+        match value {
+            AirshipScheduleError::OverlappingFlight(flight) => Self::AirshipOverlapsWithFlight(flight),
+            AirshipScheduleError::PositionDiscontinuity(flight) => Self::AirshipPositionDiscontinuousWithFlight(flight),
+        }
+    }
+}
+
 impl From<EventTryIntoError> for ScheduleFlightError {
     fn from(_: EventTryIntoError) -> Self {
         Self::IoError("unable to serialize event".to_owned())
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightCancellationReason, FlightId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct FlightCancelledV1 {
+    pub id: FlightId,
+    pub reason: FlightCancellationReason,
+}
+
+impl TryInto<Event> for FlightCancelledV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
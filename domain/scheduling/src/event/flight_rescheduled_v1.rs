@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use prelude::domain::{Event, EventTryIntoError};
+use crate::aggregate::{FlightArrival, FlightDeparture, FlightId};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct FlightRescheduledV1 {
+    pub id: FlightId,
+    pub departure: FlightDeparture,
+    pub arrival: FlightArrival,
+}
+
+impl TryInto<Event> for FlightRescheduledV1 {
+    type Error = EventTryIntoError;
+
+    fn try_into(self) -> Result<Event, Self::Error> {
+        Event::try_into(self)
+    }
+}
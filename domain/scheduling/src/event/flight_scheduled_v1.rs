@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use prelude::domain::{Event, EventTryIntoError};
-use crate::aggregate::{AirshipId, AirshipNumberOfSeats, FlightArrival, FlightDeparture, FlightId};
+use crate::aggregate::{AirshipId, AirshipModel, AirshipNumberOfSeats, FlightArrival, FlightDeparture, FlightId};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct FlightScheduledV1 {
@@ -13,7 +13,9 @@ pub struct FlightScheduledV1 {
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Airship {
     pub id: AirshipId,
-    pub number_of_seats: AirshipNumberOfSeats
+    pub number_of_seats: AirshipNumberOfSeats,
+    /// lays out the seats on board
+    pub model: AirshipModel,
 }
 
 impl TryInto<Event> for FlightScheduledV1 {
@@ -1,11 +1,15 @@
 mod flight_scheduled_v1;
 mod airship_added_to_fleet_v1;
 mod airfield_registered_v1;
+mod flight_cancelled_v1;
+mod flight_rescheduled_v1;
 
 use serde::Deserialize;
 pub use airfield_registered_v1::*;
 pub use flight_scheduled_v1::*;
 pub use airship_added_to_fleet_v1::*;
+pub use flight_cancelled_v1::*;
+pub use flight_rescheduled_v1::*;
 
 #[derive(Deserialize)]
 pub struct RawEvent(String);
@@ -21,4 +25,6 @@ pub enum Event {
     AirfieldRegisteredV1(AirfieldRegisteredV1),
     AirshipAddedToFleetV1(AirshipAddedToFleetV1),
     FlightScheduledV1(FlightScheduledV1),
+    FlightCancelledV1(FlightCancelledV1),
+    FlightRescheduledV1(FlightRescheduledV1),
 }
\ No newline at end of file
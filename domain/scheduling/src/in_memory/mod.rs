@@ -0,0 +1,3 @@
+mod repository;
+
+pub use repository::*;
@@ -0,0 +1,265 @@
+use chrono::{DateTime, FixedOffset};
+use prelude::async_trait;
+use prelude::domain::Transaction;
+use prelude::runtime::in_memory::{InMemoryStorage, InMemoryStorageError};
+use crate::aggregate::{Airfield, AirfieldId, Airship, AirshipId, Flight, FlightId};
+use crate::repository::{AirfieldRepository, AirfieldRepositoryError, AirshipRepository, AirshipRepositoryError, FlightFilter, FlightRepository, FlightRepositoryError};
+
+/// in-memory counterpart of the durable object repository, for tests and local development;
+/// uses the same keys and versioning rules
+#[derive(Default)]
+pub struct InMemorySchedulingRepository {
+    storage: InMemoryStorage
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AirfieldRepository for InMemorySchedulingRepository {
+    async fn get(&self, id: &AirfieldId) -> Result<Option<Airfield>, AirfieldRepositoryError> {
+        let key = ["airfield:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Airfield>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &AirfieldId) -> Result<Transaction<AirfieldId, Airfield>, AirfieldRepositoryError> {
+        let key = ["airfield:", &id.to_string()].concat();
+
+        match self.storage.get::<Airfield>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(id.clone(), value)),
+            None => Ok(Transaction::new(id.clone()))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<AirfieldId, Airfield>) -> Result<(), AirfieldRepositoryError> {
+        let key = ["airfield:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<Airfield>, AirfieldRepositoryError> {
+        Ok(self.storage.list::<Airfield>("airfield:")?
+            .into_iter()
+            .map(|value| value.value())
+            .collect())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AirshipRepository for InMemorySchedulingRepository {
+    async fn get(&self, id: &AirshipId) -> Result<Option<Airship>, AirshipRepositoryError> {
+        let key = ["airship:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Airship>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: &AirshipId) -> Result<Transaction<AirshipId, Airship>, AirshipRepositoryError> {
+        let key = ["airship:", &id.to_string()].concat();
+
+        match self.storage.get::<Airship>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(id.clone(), value)),
+            None => Ok(Transaction::new(id.clone()))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<AirshipId, Airship>) -> Result<(), AirshipRepositoryError> {
+        let key = ["airship:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<Airship>, AirshipRepositoryError> {
+        Ok(self.storage.list::<Airship>("airship:")?
+            .into_iter()
+            .map(|value| value.value())
+            .collect())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl FlightRepository for InMemorySchedulingRepository {
+    async fn get(&self, id: FlightId) -> Result<Option<Flight>, FlightRepositoryError> {
+        let key = ["flight:", &id.to_string()].concat();
+
+        Ok(self.storage.get::<Flight>(&key)?
+            .map(|value| value.value()))
+    }
+
+    async fn set_begin(&self, id: FlightId) -> Result<Transaction<FlightId, Flight>, FlightRepositoryError> {
+        let key = ["flight:", &id.to_string()].concat();
+
+        match self.storage.get::<Flight>(&key)? {
+            Some(value) => Ok(Transaction::from_versioned(id, value)),
+            None => Ok(Transaction::new(id))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<FlightId, Flight>) -> Result<(), FlightRepositoryError> {
+        let key = ["flight:", &transaction.id.to_string()].concat();
+
+        if let Some(value) = transaction.next_versioned_value() {
+            self.storage.put(&key, value)?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self, filter: &FlightFilter) -> Result<Vec<Flight>, FlightRepositoryError> {
+        Ok(self.storage.list::<Flight>("flight:")?
+            .into_iter()
+            .map(|value| value.value())
+            .filter(|flight| filter.matches(flight))
+            .collect())
+    }
+
+    async fn list_by_airship(&self, airship: &AirshipId, from: DateTime<FixedOffset>, until: DateTime<FixedOffset>) -> Result<Vec<Flight>, FlightRepositoryError> {
+        Ok(self.storage.list::<Flight>("flight:")?
+            .into_iter()
+            .map(|value| value.value())
+            .filter(|flight| flight.is_scheduled_within(airship, from, until))
+            .collect())
+    }
+}
+
+// transformers
+impl From<InMemoryStorageError> for AirfieldRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => AirfieldRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => AirfieldRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for AirshipRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => AirshipRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => AirshipRepositoryError::IoError(error),
+        }
+    }
+}
+
+impl From<InMemoryStorageError> for FlightRepositoryError {
+    fn from(error: InMemoryStorageError) -> Self {
+        match error {
+            InMemoryStorageError::VersionConflict => FlightRepositoryError::VersionConflict,
+            InMemoryStorageError::MalformedValue(error) => FlightRepositoryError::IoError(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use crate::aggregate::{Airfield, Airship, AirshipId, AirshipNumberOfSeats, Flight};
+    use crate::in_memory::InMemorySchedulingRepository;
+    use crate::repository::{AirshipRepository, AirshipRepositoryError};
+
+    #[test]
+    fn get_unknown_returns_none() {
+        let repository = InMemorySchedulingRepository::default();
+
+        let result = block_on(repository.get(&airship_id()));
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn commits_and_gets() {
+        let repository = InMemorySchedulingRepository::default();
+
+        let transaction = block_on(repository.set_begin(&airship_id())).unwrap();
+        assert!(transaction.is_empty());
+
+        let result = block_on(repository.set_commit(transaction.with_value(airship())));
+        assert_eq!(result, Ok(()));
+
+        let result = block_on(repository.get(&airship_id()));
+        assert_eq!(result, Ok(Some(airship())));
+    }
+
+    #[test]
+    fn rejects_concurrent_commits() {
+        let repository = InMemorySchedulingRepository::default();
+
+        let transaction1 = block_on(repository.set_begin(&airship_id())).unwrap();
+        let transaction2 = block_on(repository.set_begin(&airship_id())).unwrap();
+
+        let result = block_on(repository.set_commit(transaction1.with_value(airship())));
+        assert_eq!(result, Ok(()));
+
+        let result = block_on(repository.set_commit(transaction2.with_value(airship())));
+        assert_eq!(result, Err(AirshipRepositoryError::VersionConflict));
+    }
+
+    #[test]
+    fn lists_flights_by_airship_within_window() {
+        use crate::repository::FlightRepository;
+
+        let repository = InMemorySchedulingRepository::default();
+
+        for (id, departure, arrival) in [
+            ("5EPFciXgSxB70tAE8iERl1", "2024-01-08T09:00:00+00:00", "2024-01-08T11:00:00+00:00"),
+            ("5EPFciXgSxB70tAE8iERl2", "2024-02-08T09:00:00+00:00", "2024-02-08T11:00:00+00:00"),
+        ] {
+            let (flight, _) = Flight::build(
+                id.parse().unwrap(),
+                airfield("EHAM"),
+                departure.parse().unwrap(),
+                airfield("ENLI"),
+                arrival.parse().unwrap(),
+                airship(),
+            ).unwrap();
+
+            let transaction = block_on(FlightRepository::set_begin(&repository, flight.id)).unwrap();
+            block_on(FlightRepository::set_commit(&repository, transaction.with_value(flight))).unwrap();
+        }
+
+        let result = block_on(repository.list_by_airship(
+            &airship_id(),
+            "2024-01-01T00:00:00+00:00".parse().unwrap(),
+            "2024-02-01T00:00:00+00:00".parse().unwrap(),
+        )).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "5EPFciXgSxB70tAE8iERl1".parse().unwrap());
+
+        let result = block_on(repository.list_by_airship(
+            &"PH-1A2".parse().unwrap(),
+            "2024-01-01T00:00:00+00:00".parse().unwrap(),
+            "2024-03-01T00:00:00+00:00".parse().unwrap(),
+        )).unwrap();
+        assert!(result.is_empty());
+    }
+
+    // test data
+    fn airfield(id: &str) -> Airfield {
+        let (airfield, _) = Airfield::build(
+            id.parse().unwrap(),
+            "Airport".parse().unwrap(),
+            "u173se".parse().unwrap()
+        );
+        airfield
+    }
+
+    fn airship_id() -> AirshipId {
+        "PH-1A1".parse().unwrap()
+    }
+
+    fn airship() -> Airship {
+        let (airship, _) = Airship::build(
+            airship_id(),
+            "Galaxy One".parse().unwrap(),
+            "Blimp 1".parse().unwrap(),
+            AirshipNumberOfSeats::try_from(10).unwrap()
+        );
+        airship
+    }
+}
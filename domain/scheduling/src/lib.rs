@@ -3,4 +3,7 @@ pub mod event;
 pub mod repository;
 pub mod command;
 pub mod usecase;
-pub mod projection;
\ No newline at end of file
+pub mod projection;
+
+#[cfg(any(test, feature = "in_memory"))]
+pub mod in_memory;
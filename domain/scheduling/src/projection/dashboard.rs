@@ -47,11 +47,31 @@ impl Dashboard {
                         location: event.arrival.location.to_string(),
                     },
                     airship: event.airship.id.to_string(),
+                    cancelled: false,
                 };
 
                 self.flights.retain(|f| f.id != flight.id);
                 self.flights.push(flight);
             }
+
+            Event::FlightCancelledV1(event) => {
+                let id = event.id.to_string();
+
+                self.flights.iter_mut()
+                    .filter(|f| f.id == id)
+                    .for_each(|f| f.cancelled = true);
+            }
+
+            Event::FlightRescheduledV1(event) => {
+                let id = event.id.to_string();
+
+                self.flights.iter_mut()
+                    .filter(|f| f.id == id)
+                    .for_each(|f| {
+                        f.departure.time = event.departure.time;
+                        f.arrival.time = event.arrival.time;
+                    });
+            }
         }
 
         self
@@ -65,12 +85,6 @@ struct Airfield {
     location: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct GeoPosition {
-    latitude: f32,
-    longitude: f32
-}
-
 #[derive(Serialize, Deserialize, Debug)]
 struct Airship {
     id: String,
@@ -85,6 +99,8 @@ struct Flight {
     departure: FlightDeparture,
     arrival: FlightArrival,
     airship: String,
+    #[serde(default)]
+    cancelled: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -21,4 +21,5 @@ pub trait AirfieldRepository {
     async fn get(&self, id: &AirfieldId) -> Result<Option<Airfield>, AirfieldRepositoryError>;
     async fn set_begin(&self, id: &AirfieldId) -> Result<Transaction<AirfieldId, Airfield>, AirfieldRepositoryError>;
     async fn set_commit(&self, transaction: Transaction<AirfieldId, Airfield>) -> Result<(), AirfieldRepositoryError>;
+    async fn list(&self) -> Result<Vec<Airfield>, AirfieldRepositoryError>;
 }
\ No newline at end of file
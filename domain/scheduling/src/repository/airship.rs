@@ -21,4 +21,5 @@ pub trait AirshipRepository {
     async fn get(&self, id: &AirshipId) -> Result<Option<Airship>, AirshipRepositoryError>;
     async fn set_begin(&self, id: &AirshipId) -> Result<Transaction<AirshipId, Airship>, AirshipRepositoryError>;
     async fn set_commit(&self, transaction: Transaction<AirshipId, Airship>) -> Result<(), AirshipRepositoryError>;
+    async fn list(&self) -> Result<Vec<Airship>, AirshipRepositoryError>;
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use prelude::async_trait;
 use prelude::domain::Transaction;
-use crate::aggregate::{Flight, FlightId};
+use crate::aggregate::{AirfieldId, AirshipId, Flight, FlightId};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum FlightRepositoryError {
@@ -21,4 +23,116 @@ pub trait FlightRepository {
     async fn get(&self, id: FlightId) -> Result<Option<Flight>, FlightRepositoryError>;
     async fn set_begin(&self, id: FlightId) -> Result<Transaction<FlightId, Flight>, FlightRepositoryError>;
     async fn set_commit(&self, transaction: Transaction<FlightId, Flight>) -> Result<(), FlightRepositoryError>;
-}
\ No newline at end of file
+    async fn list(&self, filter: &FlightFilter) -> Result<Vec<Flight>, FlightRepositoryError>;
+    async fn list_by_airship(&self, airship: &AirshipId, from: DateTime<FixedOffset>, until: DateTime<FixedOffset>) -> Result<Vec<Flight>, FlightRepositoryError>;
+}
+
+/// flights matching all given criteria; airfield matches either departure or arrival location,
+/// from and until match flights (partially) taking place in between
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct FlightFilter {
+    pub airship: Option<AirshipId>,
+    pub airfield: Option<AirfieldId>,
+    pub from: Option<DateTime<FixedOffset>>,
+    pub until: Option<DateTime<FixedOffset>>,
+}
+
+impl FlightFilter {
+    pub fn matches(&self, flight: &Flight) -> bool {
+        let airship = self.airship.as_ref()
+            .map(|airship| &flight.airship == airship)
+            .unwrap_or(true);
+
+        let airfield = self.airfield.as_ref()
+            .map(|airfield| &flight.departure.location == airfield || &flight.arrival.location == airfield)
+            .unwrap_or(true);
+
+        let from = self.from
+            .map(|from| flight.arrival.time > from)
+            .unwrap_or(true);
+
+        let until = self.until
+            .map(|until| flight.departure.time < until)
+            .unwrap_or(true);
+
+        airship && airfield && from && until
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aggregate::{Airfield, Airship, AirshipNumberOfSeats, Flight};
+    use crate::repository::FlightFilter;
+
+    #[test]
+    fn filter_matches_all_criteria() {
+        let flight = flight();
+
+        assert!(FlightFilter::default().matches(&flight));
+
+        let filter = FlightFilter {
+            airship: Some("PH-1A1".parse().unwrap()),
+            airfield: Some("ENLI".parse().unwrap()),
+            from: Some("2024-01-08T00:00:00+05:00".parse().unwrap()),
+            until: Some("2024-01-09T00:00:00+05:00".parse().unwrap()),
+        };
+        assert!(filter.matches(&flight));
+
+        let filter = FlightFilter {
+            airship: Some("PH-1A2".parse().unwrap()),
+            ..FlightFilter::default()
+        };
+        assert!(!filter.matches(&flight));
+
+        let filter = FlightFilter {
+            airfield: Some("ENBR".parse().unwrap()),
+            ..FlightFilter::default()
+        };
+        assert!(!filter.matches(&flight));
+
+        let filter = FlightFilter {
+            from: Some("2024-01-08T11:00:00+05:00".parse().unwrap()),
+            ..FlightFilter::default()
+        };
+        assert!(!filter.matches(&flight));
+
+        let filter = FlightFilter {
+            until: Some("2024-01-08T09:00:00+05:00".parse().unwrap()),
+            ..FlightFilter::default()
+        };
+        assert!(!filter.matches(&flight));
+    }
+
+    // test data
+    fn flight() -> Flight {
+        let (airship, _) = Airship::build(
+            "PH-1A1".parse().unwrap(),
+            "Galaxy One".parse().unwrap(),
+            "Blimp 1".parse().unwrap(),
+            AirshipNumberOfSeats::try_from(10).unwrap(),
+        );
+
+        let (eham, _) = Airfield::build(
+            "EHAM".parse().unwrap(),
+            "Amsterdam Airport".parse().unwrap(),
+            "u173se".parse().unwrap()
+        );
+
+        let (enli, _) = Airfield::build(
+            "ENLI".parse().unwrap(),
+            "Farsund Airport".parse().unwrap(),
+            "u4kdwc".parse().unwrap()
+        );
+
+        let (flight, _) = Flight::build(
+            "5EPFciXgSxB70tAE8iERl6".parse().unwrap(),
+            eham,
+            "2024-01-08T09:00:00+05:00".parse().unwrap(),
+            enli,
+            "2024-01-08T11:00:00+05:00".parse().unwrap(),
+            airship,
+        ).unwrap();
+
+        flight
+    }
+}
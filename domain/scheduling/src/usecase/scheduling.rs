@@ -1,8 +1,8 @@
 use std::rc::Rc;
 use prelude::domain::{Event, EventPublisher};
-use crate::aggregate::{Airfield, Airship, Flight, FlightId};
-use crate::command::{AddAirshipToFleet, AddAirshipToFleetError, AddAirshipToFleetResult, RegisterAirfield, RegisterAirfieldError, RegisterAirfieldResult, ScheduleFlight, ScheduleFlightError, ScheduleFlightResult};
-use crate::repository::{AirfieldRepository, AirshipRepository, FlightRepository};
+use crate::aggregate::{Airfield, Airship, AirshipSchedule, Flight, FlightId};
+use crate::command::{GetAirfield, GetAirfieldError, GetAirfieldResult, GetAirship, GetAirshipError, GetAirshipResult, GetFlight, GetFlightError, GetFlightResult, ListAirfields, ListAirfieldsResult, ListAirships, ListAirshipsResult, ListFlights, ListFlightsResult, CancelFlight, CancelFlightError, CancelFlightResult, RescheduleFlight, RescheduleFlightError, RescheduleFlightResult, AddAirshipToFleet, AddAirshipToFleetError, AddAirshipToFleetResult, RegisterAirfield, RegisterAirfieldError, RegisterAirfieldResult, ScheduleFlight, ScheduleFlightError, ScheduleFlightResult};
+use crate::repository::{AirfieldRepository, AirshipRepository, FlightFilter, FlightRepository, FlightRepositoryError};
 
 pub struct SchedulingUseCase {
     airfield_repository: Rc<dyn AirfieldRepository>,
@@ -77,9 +77,10 @@ impl SchedulingUseCase {
             .get(&command.arrival_location).await?
             .ok_or(ScheduleFlightError::UnknownAirfield)?;
 
-        let airship = self.airship_repository
-            .get(&command.airship).await?
-            .ok_or(ScheduleFlightError::UnknownAirship)?;
+        // airship is committed along with its flights, concurrent changes to its schedule conflict
+        let airship_transaction = self.airship_repository.set_begin(&command.airship).await?;
+        let airship = airship_transaction
+            .value_or(ScheduleFlightError::UnknownAirship)?;
 
         let (flight, flight_scheduled) = Flight::build(
             id,
@@ -89,11 +90,96 @@ impl SchedulingUseCase {
             command.arrival_time,
             airship,
         )?;
+        self.airship_schedule([&flight]).await?
+            .verify(&flight)?;
         let event: Event = flight_scheduled.try_into()?;
 
+        self.airship_repository.set_commit(airship_transaction).await?;
         self.flight_repository.set_commit(transaction.with_value(flight)).await?;
         self.event_publisher.send(event).await?;
 
         Ok(id)
     }
+
+    pub async fn cancel_flight(&self, command: CancelFlight) -> CancelFlightResult {
+        let transaction = self.flight_repository.set_begin(command.flight).await?;
+
+        let (flight, flight_cancelled) = transaction
+            .value_or(CancelFlightError::UnknownFlight)?
+            .cancel(command.reason)?;
+        let event: Event = flight_cancelled.try_into()?;
+
+        self.flight_repository.set_commit(transaction.with_value(flight)).await?;
+        self.event_publisher.send(event).await?;
+
+        Ok(command.flight)
+    }
+
+    pub async fn reschedule_flight(&self, command: RescheduleFlight) -> RescheduleFlightResult {
+        let transaction = self.flight_repository.set_begin(command.flight).await?;
+
+        let current = transaction
+            .value_or(RescheduleFlightError::UnknownFlight)?;
+        let airship_transaction = self.airship_repository.set_begin(&current.airship).await?
+            .expect_non_empty(RescheduleFlightError::UnknownAirship)?;
+
+        let (flight, flight_rescheduled) = current.clone()
+            .reschedule(command.departure_time, command.arrival_time)?;
+        self.airship_schedule([&current, &flight]).await?
+            .verify(&flight)?;
+        let event: Event = flight_rescheduled.try_into()?;
+
+        self.airship_repository.set_commit(airship_transaction).await?;
+        self.flight_repository.set_commit(transaction.with_value(flight)).await?;
+        self.event_publisher.send(event).await?;
+
+        Ok(command.flight)
+    }
+
+    pub async fn get_airfield(&self, command: GetAirfield) -> GetAirfieldResult {
+        self.airfield_repository.get(&command.id).await?
+            .ok_or(GetAirfieldError::UnknownAirfield)
+    }
+
+    pub async fn list_airfields(&self, _: ListAirfields) -> ListAirfieldsResult {
+        Ok(self.airfield_repository.list().await?)
+    }
+
+    pub async fn get_airship(&self, command: GetAirship) -> GetAirshipResult {
+        self.airship_repository.get(&command.id).await?
+            .ok_or(GetAirshipError::UnknownAirship)
+    }
+
+    pub async fn list_airships(&self, _: ListAirships) -> ListAirshipsResult {
+        Ok(self.airship_repository.list().await?)
+    }
+
+    pub async fn get_flight(&self, command: GetFlight) -> GetFlightResult {
+        self.flight_repository.get(command.id).await?
+            .ok_or(GetFlightError::UnknownFlight)
+    }
+
+    /// flights are ordered by departure time
+    pub async fn list_flights(&self, command: ListFlights) -> ListFlightsResult {
+        let filter = FlightFilter {
+            airship: command.airship,
+            airfield: command.airfield,
+            from: command.from,
+            until: command.until,
+        };
+
+        let mut flights = self.flight_repository.list(&filter).await?;
+        flights.sort_by_key(|flight| flight.departure.time);
+
+        Ok(flights)
+    }
+
+    async fn airship_schedule<const N: usize>(&self, flights: [&Flight; N]) -> Result<AirshipSchedule, FlightRepositoryError> {
+        let (from, until) = AirshipSchedule::window(flights);
+        let flight = flights[0];
+        let flights = self.flight_repository
+            .list_by_airship(&flight.airship, from, until).await?;
+
+        Ok(AirshipSchedule::from_flights(flights))
+    }
 }
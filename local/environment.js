@@ -12,7 +12,7 @@ export function serve() {
                     {type: "CompiledWasm", include: ["build/scheduling_api/*.wasm"]},
                 ],
                 scriptPath: "build/scheduling_api/shim.mjs",
-                routes: ["http://127.0.0.1/flights", "http://127.0.0.1/airships", "http://127.0.0.1/airfields"],
+                routes: ["http://127.0.0.1/flights", "http://127.0.0.1/flights/*", "http://127.0.0.1/airships", "http://127.0.0.1/airships/*", "http://127.0.0.1/airfields", "http://127.0.0.1/airfields/*"],
                 bindings: {
                     api_key: "1234",
                 },
@@ -70,9 +70,12 @@ export function serve() {
                     {type: "CompiledWasm", include: ["build/reservation_api/*.wasm"]},
                 ],
                 scriptPath: "build/reservation_api/shim.mjs",
-                routes: ["http://127.0.0.1/journeys", "http://127.0.0.1/reservations", "http://127.0.0.1/reservations/*"],
+                routes: ["http://127.0.0.1/journeys", "http://127.0.0.1/journeys/*", "http://127.0.0.1/reservations", "http://127.0.0.1/reservations/*", "http://127.0.0.1/quotes", "http://127.0.0.1/itinerary-searches", "http://127.0.0.1/accommodations/*"],
                 bindings: {
                     api_key: "1234",
+                    jwt_hs256_secret: "local-jwt-secret",
+                    jwt_audience: "reservation-api",
+                    exchange_rates: '{"EUR": "0.92", "GBP": "0.79", "NOK": "10.85", "ISK": "137.50"}',
                 },
                 queueProducers: {
                     reservation_queue: "reservation_rs_queue",
@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+
+/// ISO 4217 currencies
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Currency {
+    USD,
+    EUR,
+    GBP,
+    NOK,
+    ISK,
+}
+
+impl Currency {
+    /// number of decimals of the minor unit, e.g. 2 for cents
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            Currency::USD | Currency::EUR | Currency::GBP | Currency::NOK => 2,
+            Currency::ISK => 0,
+        }
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Currency::USD => "USD",
+            Currency::EUR => "EUR",
+            Currency::GBP => "GBP",
+            Currency::NOK => "NOK",
+            Currency::ISK => "ISK",
+        };
+
+        write!(f, "{}", code)
+    }
+}
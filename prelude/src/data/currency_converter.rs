@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::ops::{Div, Mul};
+use rust_decimal::Decimal;
+use crate::data::{Currency, Money, MoneyError};
+
+pub trait CurrencyConverter {
+    /// the result is rounded to the minor unit of the target currency
+    fn convert(&self, money: Money, to: Currency) -> Result<Money, MoneyError>;
+}
+
+/// converts by fixed rates, e.g. configured at deployment
+pub struct StaticCurrencyConverter {
+    base: Currency,
+    /// units of the currency per unit of the base currency
+    rates: HashMap<Currency, Decimal>,
+}
+
+impl StaticCurrencyConverter {
+    pub fn new(base: Currency, rates: impl IntoIterator<Item=(Currency, Decimal)>) -> Self {
+        Self {
+            base,
+            rates: rates.into_iter().collect(),
+        }
+    }
+
+    fn rate(&self, currency: Currency) -> Option<Decimal> {
+        if currency == self.base {
+            Some(Decimal::ONE)
+        }
+        else {
+            self.rates.get(&currency).copied()
+                .filter(|rate| rate.is_sign_positive() && !rate.is_zero())
+        }
+    }
+}
+
+impl CurrencyConverter for StaticCurrencyConverter {
+    fn convert(&self, money: Money, to: Currency) -> Result<Money, MoneyError> {
+        if money.currency() == to {
+            return Ok(money);
+        }
+
+        let no_exchange_rate = || MoneyError::NoExchangeRate(money.currency(), to);
+        let from_rate = self.rate(money.currency()).ok_or_else(no_exchange_rate)?;
+        let to_rate = self.rate(to).ok_or_else(no_exchange_rate)?;
+
+        let amount = money.amount()
+            .div(from_rate)
+            .mul(to_rate);
+
+        Ok(Money::new(amount, to).round())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use rust_decimal::Decimal;
+    use crate::data::{Currency, CurrencyConverter, Money, MoneyError, StaticCurrencyConverter};
+
+    #[test]
+    fn converts_from_and_to_base_currency() {
+        let converter = converter();
+
+        let result = converter.convert(Money::usd(100_00), Currency::EUR);
+        assert_eq!(result, Ok(Money::from_minor_units(92_00, Currency::EUR)));
+
+        let result = converter.convert(Money::from_minor_units(92_00, Currency::EUR), Currency::USD);
+        assert_eq!(result, Ok(Money::usd(100_00)));
+    }
+
+    #[test]
+    fn converts_between_other_currencies() {
+        let converter = converter();
+
+        // 100.00 EUR = 108.70 USD = 14945.65 ISK
+        let result = converter.convert(Money::from_minor_units(100_00, Currency::EUR), Currency::ISK);
+        assert_eq!(result, Ok(Money::from_minor_units(14946, Currency::ISK)));
+    }
+
+    #[test]
+    fn errors_on_unknown_rate() {
+        let converter = converter();
+
+        let result = converter.convert(Money::usd(100_00), Currency::GBP);
+        assert_eq!(result, Err(MoneyError::NoExchangeRate(Currency::USD, Currency::GBP)));
+    }
+
+    // test data
+    fn converter() -> StaticCurrencyConverter {
+        StaticCurrencyConverter::new(Currency::USD, [
+            (Currency::EUR, Decimal::new(92, 2)),
+            (Currency::ISK, Decimal::new(1375, 1)),
+        ])
+    }
+}
@@ -1,10 +1,17 @@
 mod uid;
 mod geohash;
+mod currency;
 mod money;
+mod currency_converter;
 
 pub use uid::*;
 pub use geohash::*;
+pub use currency::*;
 pub use money::*;
+pub use currency_converter::*;
+
+/// amounts and exchange rates are decimal, not to lose precision
+pub use rust_decimal::Decimal;
 
 pub mod chrono {
     use chrono::NaiveDate;
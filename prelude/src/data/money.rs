@@ -1,38 +1,140 @@
-use std::ops::{Div, Mul};
-use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::data::Currency;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
-pub enum Currency {
-    USD
+#[derive(Error, Debug, PartialEq)]
+pub enum MoneyError {
+    #[error("currencies don't match: {0} and {1}")]
+    CurrencyMismatch(Currency, Currency),
+
+    #[error("malformed amount")]
+    MalformedAmount,
+
+    #[error("no exchange rate from {0} to {1}")]
+    NoExchangeRate(Currency, Currency),
 }
 
+/// amount is in major units (e.g. dollars, not cents), and unrounded,
+/// such that percentages and conversions don't accumulate rounding errors
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
-pub struct Money(Decimal, Currency);
+pub struct Money {
+    amount: Decimal,
+    currency: Currency,
+}
 
 impl Money {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self {
+            amount,
+            currency,
+        }
+    }
+
+    pub fn from_minor_units(amount: i64, currency: Currency) -> Self {
+        Self::new(Decimal::new(amount, currency.minor_units()), currency)
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(Decimal::ZERO, currency)
+    }
+
     pub fn usd(cents: i64) -> Self {
-        Self(Decimal::from(cents), Currency::USD)
+        Self::from_minor_units(cents, Currency::USD)
+    }
+
+    /// parses an amount in major units, e.g. "120.00"
+    pub fn parse(amount: &str, currency: Currency) -> Result<Self, MoneyError> {
+        let amount = Decimal::from_str(amount)
+            .map_err(|_| MoneyError::MalformedAmount)?;
+
+        Ok(Self::new(amount, currency))
     }
 
     pub fn currency(&self) -> Currency {
-        self.1
+        self.currency
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    /// amount rounded to, and with the decimals of, the minor unit
+    pub fn major_units(&self) -> Decimal {
+        let mut amount = self.round().amount;
+        amount.rescale(self.currency.minor_units());
+        amount
+    }
+
+    /// amount rounded to, and expressed in, the minor unit (e.g. cents)
+    pub fn minor_units(&self) -> i64 {
+        let mut amount = self.major_units();
+        amount.set_scale(0).expect("scale 0 is always valid");
+        amount.try_into().expect("amount should fit in i64")
+    }
+
+    /// rounds half to even (banker's rounding) to the minor unit
+    pub fn round(&self) -> Self {
+        let amount = self.amount.round_dp_with_strategy(self.currency.minor_units(), RoundingStrategy::MidpointNearestEven);
+        Self::new(amount, self.currency)
+    }
+
+    pub fn checked_add(&self, other: Money) -> Result<Self, MoneyError> {
+        self.same_currency(&other)?;
+        Ok(Self::new(self.amount.add(other.amount), self.currency))
+    }
+
+    pub fn checked_sub(&self, other: Money) -> Result<Self, MoneyError> {
+        self.same_currency(&other)?;
+        Ok(Self::new(self.amount.sub(other.amount), self.currency))
     }
 
     pub fn mul(&self, times: i64) -> Self {
         let times = Decimal::from(times);
-        Self(self.0.mul(times), self.1)
+        Self::new(self.amount.mul(times), self.currency)
     }
 
-    pub fn percentage(&self, percentage: u8) -> Self {
+    pub fn percentage(&self, percentage: u16) -> Self {
         let percentage = Decimal::from(percentage).div(Decimal::from(100));
-        Self(self.0.mul(percentage), self.1)
+        Self::new(self.amount.mul(percentage), self.currency)
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        }
+        else {
+            Err(MoneyError::CurrencyMismatch(self.currency, other.currency))
+        }
+    }
+}
+
+/// amounts in different currencies are incomparable
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.currency == other.currency {
+            self.amount.partial_cmp(&other.amount)
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.major_units(), self.currency)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::data::Money;
+    use rust_decimal::Decimal;
+    use crate::data::{Currency, Money, MoneyError};
 
     #[test]
     fn equals_by_amount_and_currency() {
@@ -42,6 +144,9 @@ mod tests {
 
         let money3 = Money::usd(101);
         assert_ne!(money1, money3);
+
+        let money4 = Money::from_minor_units(100, Currency::EUR);
+        assert_ne!(money1, money4);
     }
 
     #[test]
@@ -53,6 +158,13 @@ mod tests {
         assert_eq!(original, deserialized);
     }
 
+    #[test]
+    fn minor_units_depend_on_currency() {
+        assert_eq!(Money::from_minor_units(12000, Currency::EUR).amount(), Decimal::new(120, 0));
+        assert_eq!(Money::from_minor_units(12000, Currency::ISK).amount(), Decimal::new(12000, 0));
+        assert_eq!(Money::from_minor_units(12345, Currency::NOK).minor_units(), 12345);
+    }
+
     #[test]
     fn can_multiply() {
         let money = Money::usd(100)
@@ -60,10 +172,69 @@ mod tests {
         assert_eq!(money, Money::usd(200));
     }
 
+    #[test]
+    fn can_add_and_subtract_same_currency() {
+        let money = Money::usd(100)
+            .checked_add(Money::usd(50));
+        assert_eq!(money, Ok(Money::usd(150)));
+
+        let money = Money::usd(100)
+            .checked_sub(Money::usd(150));
+        assert_eq!(money, Ok(Money::usd(-50)));
+    }
+
+    #[test]
+    fn errors_on_mixed_currencies() {
+        let eur = Money::from_minor_units(50, Currency::EUR);
+
+        assert_eq!(Money::usd(100).checked_add(eur), Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR)));
+        assert_eq!(Money::usd(100).checked_sub(eur), Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR)));
+    }
+
+    #[test]
+    fn compares_same_currency_only() {
+        assert!(Money::usd(100) > Money::usd(50));
+        assert!(Money::usd(50) < Money::usd(100));
+        assert_eq!(Money::usd(100).partial_cmp(&Money::from_minor_units(50, Currency::EUR)), None);
+    }
+
+    #[test]
+    fn rounds_half_to_even() {
+        let money = Money::usd(5).percentage(50); // 0.025
+        assert_eq!(money.round(), Money::usd(2));
+
+        let money = Money::usd(15).percentage(50); // 0.075
+        assert_eq!(money.round(), Money::usd(8));
+
+        let money = Money::from_minor_units(5, Currency::ISK).percentage(50); // 2.5
+        assert_eq!(money.round(), Money::from_minor_units(2, Currency::ISK));
+    }
+
+    #[test]
+    fn can_get_major_units() {
+        assert_eq!(Money::usd(12000).major_units().to_string(), "120.00");
+        assert_eq!(Money::usd(12345).major_units().to_string(), "123.45");
+        assert_eq!(Money::from_minor_units(12345, Currency::ISK).major_units().to_string(), "12345");
+    }
+
+    #[test]
+    fn can_parse_major_units() {
+        assert_eq!(Money::parse("120.00", Currency::USD), Ok(Money::usd(12000)));
+        assert_eq!(Money::parse("0.5", Currency::USD), Ok(Money::usd(50)));
+        assert_eq!(Money::parse("twelve", Currency::USD), Err(MoneyError::MalformedAmount));
+    }
+
+    #[test]
+    fn is_formatted_with_currency() {
+        assert_eq!(Money::usd(12000).to_string(), "120.00 USD");
+        assert_eq!(Money::from_minor_units(1500, Currency::ISK).to_string(), "1500 ISK");
+    }
+
     #[test]
     fn can_get_percentage() {
         let money = Money::usd(100);
         assert_eq!(money.percentage(50), Money::usd(50));
         assert_eq!(money.percentage(25), Money::usd(25));
+        assert_eq!(money.percentage(150), Money::usd(150));
     }
-}
\ No newline at end of file
+}
@@ -104,7 +104,7 @@ mod tests {
     fn is_serializable() {
         let id: Uid = "5EPFciXgSxB70tAE8iERl6".parse().unwrap();
 
-        let serialized = serde_json::to_value(id.clone());
+        let serialized = serde_json::to_value(id);
         assert!(serialized.is_ok());
 
         let deserialized: Result<Uid, serde_json::Error> = serde_json::from_value(serialized.unwrap());
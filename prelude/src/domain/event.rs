@@ -13,6 +13,7 @@ macro_rules! event_name {
     };
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct Event(String, Value);
 
 pub type EventTryIntoError = serde_json::Error;
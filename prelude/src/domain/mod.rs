@@ -1,7 +1,11 @@
 mod event;
 mod version;
 mod transaction;
+#[cfg(any(test, feature = "in_memory"))]
+mod recording_event_publisher;
 
 pub use event::*;
 pub use version::*;
-pub use transaction::*;
\ No newline at end of file
+pub use transaction::*;
+#[cfg(any(test, feature = "in_memory"))]
+pub use recording_event_publisher::*;
\ No newline at end of file
@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use crate::domain::{Event, EventPublisher, EventPublishError};
+
+/// publisher that keeps events in-process; records every event sent and queues them for delivery,
+/// e.g. to drive a saga synchronously in tests
+#[derive(Default)]
+pub struct RecordingEventPublisher {
+    sent: Mutex<Vec<Event>>,
+    pending: Mutex<VecDeque<Event>>,
+}
+
+impl RecordingEventPublisher {
+    /// all events sent, in order of sending
+    pub fn sent(&self) -> Vec<Event> {
+        self.sent.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    /// takes the oldest event that is not yet delivered
+    pub fn next_pending(&self) -> Option<Event> {
+        self.pending.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .pop_front()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl EventPublisher for RecordingEventPublisher {
+    async fn send(&self, event: Event) -> Result<(), EventPublishError> {
+        self.sent.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push(event.clone());
+
+        self.pending.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push_back(event);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use serde::Serialize;
+    use crate::domain::{Event, EventPublisher, RecordingEventPublisher};
+
+    #[derive(Serialize)]
+    struct SomethingHappenedV1 {
+        value: u8
+    }
+
+    #[test]
+    fn records_sent_events_in_order() {
+        let publisher = RecordingEventPublisher::default();
+        block_on(publisher.send(event(1))).unwrap();
+        block_on(publisher.send(event(2))).unwrap();
+
+        assert_eq!(publisher.sent(), vec![event(1), event(2)]);
+    }
+
+    #[test]
+    fn delivers_pending_events_once() {
+        let publisher = RecordingEventPublisher::default();
+        block_on(publisher.send(event(1))).unwrap();
+        block_on(publisher.send(event(2))).unwrap();
+
+        assert_eq!(publisher.next_pending(), Some(event(1)));
+        assert_eq!(publisher.next_pending(), Some(event(2)));
+        assert_eq!(publisher.next_pending(), None);
+        assert_eq!(publisher.sent().len(), 2);
+    }
+
+    // test data
+    fn event(value: u8) -> Event {
+        Event::try_into(SomethingHappenedV1 { value }).unwrap()
+    }
+}
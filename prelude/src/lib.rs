@@ -4,6 +4,7 @@ pub mod parse;
 pub mod runtime;
 pub mod data;
 pub mod collection;
+pub mod security;
 
 pub use async_trait::async_trait;
 
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use crate::domain::{Version, Versioned};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum InMemoryStorageError {
+    #[error("malformed value: {0}")]
+    MalformedValue(String),
+
+    #[error("version conflict")]
+    VersionConflict,
+}
+
+/// key-value storage, mimics durable object storage used by repositories;
+/// values are stored serialized, and a put is only accepted if it increments the existing version by 1
+#[derive(Default)]
+pub struct InMemoryStorage {
+    values: Mutex<HashMap<String, (Value, Version)>>
+}
+
+impl InMemoryStorage {
+    pub fn get<V: DeserializeOwned>(&self, key: &str) -> Result<Option<Versioned<V>>, InMemoryStorageError> {
+        let values = self.values.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        match values.get(key) {
+            Some((value, version)) => {
+                let value = serde_json::from_value(value.clone())
+                    .map_err(|error| InMemoryStorageError::MalformedValue(error.to_string()))?;
+
+                Ok(Some(Versioned::from_version(value, *version)))
+            }
+            None => Ok(None)
+        }
+    }
+
+    pub fn put<V: Serialize>(&self, key: &str, value: Versioned<V>) -> Result<(), InMemoryStorageError> {
+        let mut values = self.values.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        let existing_version = values.get(key)
+            .map(|(_, version)| *version)
+            .unwrap_or_default();
+
+        if value.version() == existing_version + 1 {
+            let version = value.version();
+            let value = serde_json::to_value(value.value())
+                .map_err(|error| InMemoryStorageError::MalformedValue(error.to_string()))?;
+
+            values.insert(key.to_owned(), (value, version));
+            Ok(())
+        }
+        else {
+            Err(InMemoryStorageError::VersionConflict)
+        }
+    }
+
+    pub fn list<V: DeserializeOwned>(&self, prefix: &str) -> Result<Vec<Versioned<V>>, InMemoryStorageError> {
+        let values = self.values.lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        let mut keys: Vec<&String> = values.keys()
+            .filter(|key| key.starts_with(prefix))
+            .collect();
+        keys.sort();
+
+        keys.into_iter()
+            .map(|key| {
+                let (value, version) = &values[key];
+                serde_json::from_value(value.clone())
+                    .map(|value| Versioned::from_version(value, *version))
+                    .map_err(|error| InMemoryStorageError::MalformedValue(error.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Versioned;
+    use crate::runtime::in_memory::{InMemoryStorage, InMemoryStorageError};
+
+    #[test]
+    fn get_returns_none_if_absent() {
+        let storage = InMemoryStorage::default();
+
+        let result = storage.get::<String>("key:1");
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn put_requires_next_version() {
+        let storage = InMemoryStorage::default();
+
+        let result = storage.put("key:1", Versioned::from_version("a".to_owned(), 2));
+        assert_eq!(result, Err(InMemoryStorageError::VersionConflict));
+
+        let result = storage.put("key:1", Versioned::new("a".to_owned()));
+        assert_eq!(result, Ok(()));
+
+        let result = storage.put("key:1", Versioned::new("b".to_owned()));
+        assert_eq!(result, Err(InMemoryStorageError::VersionConflict));
+
+        let result = storage.put("key:1", Versioned::from_version("b".to_owned(), 2));
+        assert_eq!(result, Ok(()));
+
+        let value = storage.get::<String>("key:1").unwrap().unwrap();
+        assert_eq!(value.version(), 2);
+        assert_eq!(value.value(), "b");
+    }
+
+    #[test]
+    fn list_by_prefix() {
+        let storage = InMemoryStorage::default();
+        storage.put("a:1", Versioned::new(1)).unwrap();
+        storage.put("a:2", Versioned::new(2)).unwrap();
+        storage.put("b:3", Versioned::new(3)).unwrap();
+
+        let values: Vec<i32> = storage.list::<i32>("a:").unwrap()
+            .into_iter()
+            .map(|value| value.value())
+            .collect();
+
+        assert_eq!(values, vec![1, 2]);
+    }
+}
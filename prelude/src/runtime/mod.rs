@@ -1 +1,4 @@
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+
+#[cfg(any(test, feature = "in_memory"))]
+pub mod in_memory;
\ No newline at end of file
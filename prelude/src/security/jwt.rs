@@ -0,0 +1,294 @@
+use serde::{Deserialize, Deserializer};
+use serde::de::{Error, Unexpected};
+use thiserror::Error;
+
+/// allowed clock skew between issuer and verifier, in seconds
+pub const LEEWAY_SECONDS: i64 = 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TokenError {
+    #[error("malformed token")]
+    MalformedToken,
+
+    #[error("unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("invalid signature")]
+    InvalidSignature,
+
+    #[error("token is expired")]
+    Expired,
+
+    #[error("token is not yet valid")]
+    NotYetValid,
+
+    #[error("token is not issued for this audience")]
+    InvalidAudience,
+
+    #[error("token is not issued by the expected issuer")]
+    InvalidIssuer,
+
+    #[error("crypto error: {0}")]
+    CryptoError(String),
+}
+
+#[derive(Deserialize)]
+pub struct Header {
+    pub alg: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::Single(value) => value == audience,
+            Audience::Multiple(values) => values.iter().any(|value| value == audience),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Payload {
+    exp: i64,
+    nbf: Option<i64>,
+    aud: Audience,
+    iss: Option<String>,
+    /// space separated, as in RFC 8693
+    #[serde(default)]
+    scope: String,
+    email: Option<String>,
+    /// some identity providers send it as a string
+    #[serde(default, deserialize_with = "bool_or_string")]
+    email_verified: bool,
+}
+
+impl Payload {
+    /// the registered claims, the issuer is verified if given
+    pub fn validate(self, audience: &str, issuer: Option<&str>, now: i64) -> Result<Self, TokenError> {
+        if now >= self.exp + LEEWAY_SECONDS {
+            return Err(TokenError::Expired);
+        }
+
+        if self.nbf.is_some_and(|nbf| now < nbf - LEEWAY_SECONDS) {
+            return Err(TokenError::NotYetValid);
+        }
+
+        if !self.aud.contains(audience) {
+            return Err(TokenError::InvalidAudience);
+        }
+
+        if issuer.is_some_and(|issuer| self.iss.as_deref() != Some(issuer)) {
+            return Err(TokenError::InvalidIssuer);
+        }
+
+        Ok(self)
+    }
+
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.scope.split_whitespace()
+    }
+
+    /// only present when the issuer verified the email address
+    pub fn verified_email(self) -> Option<String> {
+        self.email.filter(|_| self.email_verified)
+    }
+}
+
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+
+    match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(value) => Ok(value),
+        BoolOrString::String(value) => match value.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(D::Error::invalid_value(Unexpected::Str(&value), &"true or false")),
+        },
+    }
+}
+
+/// header, payload, signature and the signed part of the token
+pub fn parse(token: &str) -> Result<(Header, Payload, Vec<u8>, &str), TokenError> {
+    let parts: Vec<&str> = token.split('.').collect();
+    let [header, payload, signature] = parts[..] else {
+        return Err(TokenError::MalformedToken);
+    };
+
+    let signing_input = &token[..header.len() + 1 + payload.len()];
+    let signature = decode_base64url(signature)?;
+    let header: Header = serde_json::from_slice(&decode_base64url(header)?)
+        .map_err(|_| TokenError::MalformedToken)?;
+    let payload: Payload = serde_json::from_slice(&decode_base64url(payload)?)
+        .map_err(|_| TokenError::MalformedToken)?;
+
+    Ok((header, payload, signature, signing_input))
+}
+
+/// JWT segments are url safe encoded, without padding (RFC 7515)
+pub fn decode_base64url(input: &str) -> Result<Vec<u8>, TokenError> {
+    decode(input, |c| match c {
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    })
+}
+
+/// PEM bodies are standard encoded, padding is optional
+pub fn decode_base64(input: &str) -> Result<Vec<u8>, TokenError> {
+    decode(input.trim_end_matches('='), |c| match c {
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    })
+}
+
+/// a single character remainder or non-zero trailing bits are not canonical
+fn decode(input: &str, alphabet: impl Fn(u8) -> Option<u8>) -> Result<Vec<u8>, TokenError> {
+    if input.len() % 4 == 1 {
+        return Err(TokenError::MalformedToken);
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            _ => alphabet(c).ok_or(TokenError::MalformedToken)?,
+        };
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    if buffer != 0 {
+        return Err(TokenError::MalformedToken);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::security::jwt::{decode_base64, decode_base64url, parse, Payload, TokenError, LEEWAY_SECONDS};
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn accepts_expired_and_not_yet_valid_tokens_within_leeway() {
+        let result = payload(json!({ "exp": NOW - LEEWAY_SECONDS + 1, "aud": "reservations" })).validate("reservations", None, NOW);
+        assert!(result.is_ok());
+
+        let result = payload(json!({ "exp": NOW - LEEWAY_SECONDS, "aud": "reservations" })).validate("reservations", None, NOW);
+        assert_eq!(result.err(), Some(TokenError::Expired));
+
+        let result = payload(json!({ "exp": NOW + 3600, "nbf": NOW + LEEWAY_SECONDS, "aud": "reservations" })).validate("reservations", None, NOW);
+        assert!(result.is_ok());
+
+        let result = payload(json!({ "exp": NOW + 3600, "nbf": NOW + LEEWAY_SECONDS + 1, "aud": "reservations" })).validate("reservations", None, NOW);
+        assert_eq!(result.err(), Some(TokenError::NotYetValid));
+    }
+
+    #[test]
+    fn verifies_audience_and_issuer() {
+        let result = payload(json!({ "exp": NOW + 3600, "aud": ["billing", "reservations"] })).validate("reservations", None, NOW);
+        assert!(result.is_ok());
+
+        let result = payload(json!({ "exp": NOW + 3600, "aud": "billing" })).validate("reservations", None, NOW);
+        assert_eq!(result.err(), Some(TokenError::InvalidAudience));
+
+        let issuer = Some("https://auth.example.com/");
+
+        let result = payload(json!({ "exp": NOW + 3600, "aud": "reservations", "iss": "https://auth.example.com/" })).validate("reservations", issuer, NOW);
+        assert!(result.is_ok());
+
+        let result = payload(json!({ "exp": NOW + 3600, "aud": "reservations", "iss": "https://evil.example.com/" })).validate("reservations", issuer, NOW);
+        assert_eq!(result.err(), Some(TokenError::InvalidIssuer));
+
+        let result = payload(json!({ "exp": NOW + 3600, "aud": "reservations" })).validate("reservations", issuer, NOW);
+        assert_eq!(result.err(), Some(TokenError::InvalidIssuer));
+    }
+
+    #[test]
+    fn claims_only_verified_email() {
+        let claims = |email_verified: serde_json::Value| serde_json::from_value::<Payload>(json!({
+            "exp": NOW + 3600,
+            "aud": "reservations",
+            "scope": "openid agent",
+            "email": "n.bergsma@internet.com",
+            "email_verified": email_verified,
+        }));
+
+        let verified = claims(json!(true)).unwrap();
+        assert_eq!(verified.scopes().collect::<Vec<_>>(), vec!["openid", "agent"]);
+        assert_eq!(verified.verified_email(), Some("n.bergsma@internet.com".to_owned()));
+
+        assert_eq!(claims(json!("true")).unwrap().verified_email(), Some("n.bergsma@internet.com".to_owned()));
+        assert_eq!(claims(json!(false)).unwrap().verified_email(), None);
+        assert_eq!(claims(json!("false")).unwrap().verified_email(), None);
+        assert!(claims(json!("yes")).is_err());
+
+        let unverified = payload(json!({ "exp": NOW + 3600, "aud": "reservations", "email": "n.bergsma@internet.com" }));
+        assert_eq!(unverified.verified_email(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        // {"alg":"HS256"}.{"exp":1700003600,"aud":"reservations"}.signature
+        let header = "eyJhbGciOiJIUzI1NiJ9";
+        let payload = "eyJleHAiOjE3MDAwMDM2MDAsImF1ZCI6InJlc2VydmF0aW9ucyJ9";
+
+        assert!(parse(&[header, payload, "c2lnbmF0dXJl"].join(".")).is_ok());
+
+        for token in [
+            [header, payload].join("."),
+            [header, payload, "c2lnbmF0dXJl", ""].join("."),
+            [header, payload, "c2lnbmF0dXJl="].join("."),
+            [header, payload, "c2lnbmF0dXJ"].join("."),
+            [header, "e30", "c2lnbmF0dXJl"].join("."),
+            ["e30", payload, "c2lnbmF0dXJl"].join("."),
+        ] {
+            assert_eq!(parse(&token).err(), Some(TokenError::MalformedToken), "{}", token);
+        }
+    }
+
+    #[test]
+    fn decodes_url_safe_alphabet_only() {
+        assert_eq!(decode_base64url("-_8"), Ok(vec![0xfb, 0xff]));
+        assert_eq!(decode_base64url("+/8"), Err(TokenError::MalformedToken));
+        assert_eq!(decode_base64url("-_8="), Err(TokenError::MalformedToken));
+
+        // single character remainder and non-zero trailing bits
+        assert_eq!(decode_base64url("AAAAA"), Err(TokenError::MalformedToken));
+        assert_eq!(decode_base64url("-_9"), Err(TokenError::MalformedToken));
+
+        assert_eq!(decode_base64("+/8="), Ok(vec![0xfb, 0xff]));
+        assert_eq!(decode_base64("-_8="), Err(TokenError::MalformedToken));
+    }
+
+    // test data
+    fn payload(value: serde_json::Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+}
@@ -0,0 +1,22 @@
+use worker::{Request, RouteContext};
+use reservation::command::AllotRooms;
+use crate::api::{ApiResult, RouteData};
+use crate::api::transfer_objects::{PutAccommodationAllotmentRequest, PutAccommodationAllotmentResponse};
+
+pub async fn put_accommodation_allotment(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PutAccommodationAllotmentResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let body: PutAccommodationAllotmentRequest = req.json().await?;
+
+    let usecase = ctx.data.availability_usecase;
+
+    let command = AllotRooms {
+        accommodation: id,
+        rooms: body.rooms,
+    };
+
+    usecase.allot_rooms(command).await?;
+
+    Ok(PutAccommodationAllotmentResponse {
+        id: id.to_string(),
+    })
+}
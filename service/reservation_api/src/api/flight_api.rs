@@ -0,0 +1,74 @@
+use worker::{Request, RouteContext};
+use chrono::Utc;
+use reservation::command::{CloseBoarding, GetSeatMap, OverbookFlight};
+use reservation::projection::Manifest;
+use crate::api::{ApiResult, RouteData};
+use crate::api::transfer_objects::{GetFlightSeatsResponse, PostFlightBoardingClosureResponse, PutFlightOverbookingRequest, PutFlightOverbookingResponse, seat_to_transfer_object};
+
+pub async fn put_flight_overbooking(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PutFlightOverbookingResponse> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+    let body: PutFlightOverbookingRequest = req.json().await?;
+
+    let usecase = ctx.data.availability_usecase;
+
+    let command = OverbookFlight {
+        flight: id.clone(),
+        percentage: body.percentage,
+    };
+
+    usecase.overbook_flight(command).await?;
+
+    Ok(PutFlightOverbookingResponse {
+        id,
+    })
+}
+
+pub async fn get_flight_seats(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetFlightSeatsResponse> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+
+    let usecase = ctx.data.availability_usecase;
+
+    let command = GetSeatMap {
+        flight: id,
+    };
+
+    let seats = usecase.seat_map(command).await?;
+
+    Ok(GetFlightSeatsResponse {
+        seats: seats.into_iter()
+            .map(seat_to_transfer_object)
+            .collect(),
+    })
+}
+
+pub async fn get_flight_manifest(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<Manifest> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+
+    Ok(ctx.data.manifest_repository.get(&id).await?)
+}
+
+pub async fn get_flight_manifest_csv(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<String> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+
+    let manifest = ctx.data.manifest_repository.get(&id).await?;
+    Ok(manifest.to_csv())
+}
+
+/// passengers that did not board are recorded as no-shows, once the flight departed
+pub async fn post_flight_boarding_closure(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostFlightBoardingClosureResponse> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+
+    let usecase = ctx.data.reservation_usecase;
+
+    let command = CloseBoarding {
+        flight: id.clone(),
+        now: Utc::now(),
+    };
+
+    let reservations = usecase.close_boarding(command).await?;
+
+    Ok(PostFlightBoardingClosureResponse {
+        id,
+        reservations,
+    })
+}
@@ -1,5 +1,7 @@
+use chrono::Utc;
 use worker::{Request, RouteContext};
-use reservation::command::{PublishJourney};
+use reservation::command::{PublishJourney, ReviseJourney, WithdrawJourney};
+use reservation::projection::{Journey, Journeys};
 use crate::api::{ApiResult, RouteData};
 use crate::api::transfer_objects::*;
 
@@ -7,9 +9,12 @@ pub async fn post_journeys(mut req: Request, ctx: RouteContext<RouteData>) -> Ap
     let usecase =  ctx.data.journey_usecase;
     let body: PostJourneyRequest = req.json().await?;
 
+    let (segments, surcharges) = parse_segments(body.segments)?;
     let command = PublishJourney {
         name: body.name.parse()?,
-        segments: parse_segments(body.segments)?,
+        shape: parse_journey_shape(body.shape)?,
+        segments,
+        pricing_policy: parse_pricing_policy(body.pricing, surcharges)?,
     };
 
     let result = usecase.publish(command).await?;
@@ -18,4 +23,53 @@ pub async fn post_journeys(mut req: Request, ctx: RouteContext<RouteData>) -> Ap
     })
 }
 
+pub async fn post_journey_revisions(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostJourneyRevisionsResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.journey_usecase;
+    let body: PostJourneyRevisionsRequest = req.json().await?;
+
+    let command = ReviseJourney {
+        journey: id,
+        revisions: parse_journey_revisions(body.revisions)?,
+    };
+
+    usecase.revise(command).await?;
+    Ok(PostJourneyRevisionsResponse {
+        id: id.to_string()
+    })
+}
 
+/// the journey leaves the catalogue, reservations made before stay valid
+pub async fn delete_journey(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<DeleteJourneyResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.journey_usecase;
+
+    let command = WithdrawJourney {
+        journey: id,
+    };
+
+    usecase.withdraw(command).await?;
+    Ok(DeleteJourneyResponse {
+        id: id.to_string()
+    })
+}
+
+
+
+/// journeys that can be booked, as filtered by the query
+pub async fn get_journeys(req: Request, ctx: RouteContext<RouteData>) -> ApiResult<Journeys> {
+    let (filter, period) = parse_journeys_query(&req.url()?, Utc::now())?;
+
+    let journeys = ctx.data.journeys_repository.get().await?;
+    let availability = ctx.data.availability_repository.get(period).await?;
+
+    Ok(journeys.filter(&filter, &availability))
+}
+
+pub async fn get_journey(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<Journey> {
+    let id = ctx.param("id").cloned().unwrap_or_default();
+
+    let journeys = ctx.data.journeys_repository.get().await?;
+    journeys.find(&id)
+        .ok_or_else(|| ["unknown journey: ", &id].concat().into())
+}
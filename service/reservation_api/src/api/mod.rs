@@ -1,21 +1,33 @@
 use std::future::Future;
 use serde::Serialize;
 use worker::{Env, Request, Response, Router, Result, RouteContext};
-use reservation::usecase::{JourneyUseCase, ReservationUseCase};
-use crate::api::security::{Claims, parse_bearer_token};
+use prelude::data::StaticCurrencyConverter;
+use reservation::usecase::{AvailabilityUseCase, JourneyUseCase, ReservationUseCase};
+use crate::api::security::{Claims, Scope, TokenVerifier};
+use crate::runtime::repository::{R2AvailabilityRepository, R2JourneysRepository, R2ManifestRepository};
 
 mod transfer_objects;
 mod journey_api;
 mod reservation_api;
-mod security;
+mod quote_api;
+mod payment_api;
+mod flight_api;
+mod accommodation_api;
+pub mod security;
 
 
 type ApiResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 pub struct RouteData {
     pub journey_usecase: JourneyUseCase,
+    pub availability_usecase: AvailabilityUseCase,
     pub reservation_usecase: ReservationUseCase,
+    pub journeys_repository: R2JourneysRepository,
+    pub availability_repository: R2AvailabilityRepository,
+    pub manifest_repository: R2ManifestRepository,
     pub api_key: String,
+    pub token_verifier: TokenVerifier,
+    pub currency_converter: StaticCurrencyConverter,
 }
 
 pub async fn route(request: Request, environment: Env, route_data: RouteData) -> Result<Response> {
@@ -25,29 +37,132 @@ pub async fn route(request: Request, environment: Env, route_data: RouteData) ->
                 to_response(journey_api::post_journeys(req, ctx).await)
             }).await
         })
+        .get_async("/journeys", |req, ctx| async move {
+            to_response(journey_api::get_journeys(req, ctx).await)
+        })
+        .get_async("/journeys/:id", |req, ctx| async move {
+            to_response(journey_api::get_journey(req, ctx).await)
+        })
+        .delete_async("/journeys/:id", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(journey_api::delete_journey(req, ctx).await)
+            }).await
+        })
+        .post_async("/journeys/:id/revisions", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(journey_api::post_journey_revisions(req, ctx).await)
+            }).await
+        })
+        .put_async("/flights/:id/overbooking", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(flight_api::put_flight_overbooking(req, ctx).await)
+            }).await
+        })
+        .put_async("/accommodations/:id/allotment", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(accommodation_api::put_accommodation_allotment(req, ctx).await)
+            }).await
+        })
+        .get_async("/flights/:id/seats", |req, ctx| async move {
+            to_response(flight_api::get_flight_seats(req, ctx).await)
+        })
+        .get_async("/flights/:id/manifest", |req, ctx| async move {
+            authorize_with_agent_bearer_token(req, ctx, |req, ctx| async move {
+                to_response(flight_api::get_flight_manifest(req, ctx).await)
+            }).await
+        })
+        .get_async("/flights/:id/manifest.csv", |req, ctx| async move {
+            authorize_with_agent_bearer_token(req, ctx, |req, ctx| async move {
+                to_csv_response(flight_api::get_flight_manifest_csv(req, ctx).await)
+            }).await
+        })
+        .post_async("/flights/:id/boarding/closure", |req, ctx| async move {
+            authorize_with_agent_bearer_token(req, ctx, |req, ctx| async move {
+                to_response(flight_api::post_flight_boarding_closure(req, ctx).await)
+            }).await
+        })
         .post_async("/reservations", |req, ctx| async move {
             authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
                 to_response(reservation_api::post_reservations(req, cls, ctx).await)
             }).await
         })
+        .post_async("/reservations/holds", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::post_reservation_holds(req, cls, ctx).await)
+            }).await
+        })
+        .post_async("/reservations/:id/confirmation", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::post_reservation_confirmation(req, cls, ctx).await)
+            }).await
+        })
         .put_async("/reservations/:id/passengers", |req, ctx| async move {
             authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
                 to_response(reservation_api::put_reservation_passengers(req, cls, ctx).await)
             }).await
         })
+        .put_async("/reservations/:id/flights/:flight/seats", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::put_reservation_seats(req, cls, ctx).await)
+            }).await
+        })
+        .post_async("/reservations/:id/flights/:flight/check-in", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::post_reservation_check_in(req, cls, ctx).await)
+            }).await
+        })
+        .post_async("/reservations/:id/flights/:flight/boarding", |req, ctx| async move {
+            authorize_with_agent_bearer_token(req, ctx, |req, ctx| async move {
+                to_response(reservation_api::post_reservation_boarding(req, ctx).await)
+            }).await
+        })
         .put_async("/reservations/:id/itinerary", |req, ctx| async move {
             authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
                 to_response(reservation_api::put_reservation_itinerary(req, cls, ctx).await)
             }).await
         })
+        .put_async("/reservations/:id/contact", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::put_reservation_contact(req, cls, ctx).await)
+            }).await
+        })
+        .get_async("/reservations/:id/price", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::get_reservation_price(req, cls, ctx).await)
+            }).await
+        })
+        .post_async("/reservations/:id/payments", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(payment_api::post_reservation_payments(req, cls, ctx).await)
+            }).await
+        })
+        .get_async("/reservations/:id/payments", |req, ctx| async move {
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(payment_api::get_reservation_payments(req, cls, ctx).await)
+            }).await
+        })
+        .post_async("/quotes", |req, ctx| async move {
+            to_response(quote_api::post_quotes(req, ctx).await)
+        })
+        .post_async("/itinerary-searches", |req, ctx| async move {
+            to_response(quote_api::post_itinerary_searches(req, ctx).await)
+        })
         .get_async("/reservations/:id", |req, ctx| async move {
-            to_response(reservation_api::get_reservation(req, ctx).await)
+            authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
+                to_response(reservation_api::get_reservation(req, cls, ctx).await)
+            }).await
         })
         .delete_async("/reservations/:id", |req, ctx| async move {
             authorize_with_optional_bearer_token(req, ctx, |req, cls, ctx| async move {
                 to_response(reservation_api::delete_reservation(req, cls, ctx).await)
             }).await
         })
+        .post_async("/reservations/:id/contact/email/verification", |req, ctx| async move {
+            to_response(reservation_api::post_contact_email_verification(req, ctx).await)
+        })
+        .put_async("/reservations/:id/contact/email/verification", |req, ctx| async move {
+            to_response(reservation_api::put_contact_email_verification(req, ctx).await)
+        })
         .run(request, environment)
         .await
 }
@@ -77,13 +192,43 @@ async fn authorize_with_optional_bearer_token<T: Future<Output=Result<Response>>
 
     if let Some(value) = request.headers().get("Authorization")? {
         if let Some(token) = value.strip_prefix("Bearer ") {
-            let claims = parse_bearer_token(token);
-            return next(request, Some(claims), ctx).await;
+            return match ctx.data.token_verifier.verify(token).await {
+                Ok(claims) => next(request, Some(claims), ctx).await,
+                Err(_) => Response::error("unauthorized", 401),
+            };
         }
     }
     next(request, None, ctx).await
 }
 
+async fn authorize_with_agent_bearer_token<T: Future<Output=Result<Response>>>(
+    request: Request,
+    ctx: RouteContext<RouteData>,
+    next: fn(Request, RouteContext<RouteData>) -> T) -> Result<Response> {
+
+    if let Some(value) = request.headers().get("Authorization")? {
+        if let Some(token) = value.strip_prefix("Bearer ") {
+            return match ctx.data.token_verifier.verify(token).await {
+                Ok(claims) if claims.scopes.contains(&Scope::AgentScope) => next(request, ctx).await,
+                Ok(_) => Response::error("forbidden", 403),
+                Err(_) => Response::error("unauthorized", 401),
+            };
+        }
+    }
+    Response::error("unauthorized", 401)
+}
+
+fn to_csv_response(result: ApiResult<String>) -> Result<Response> {
+    match result {
+        Ok(csv) => {
+            let mut response = Response::ok(csv)?;
+            response.headers_mut().set("Content-Type", "text/csv")?;
+            Ok(response)
+        }
+        Err(error) => to_response::<()>(Err(error)),
+    }
+}
+
 fn to_response<T:Serialize>(result: ApiResult<T>) -> Result<Response> {
     match result {
         Ok(success) => Response::from_json(&success),
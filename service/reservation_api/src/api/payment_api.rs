@@ -0,0 +1,35 @@
+use chrono::Utc;
+use worker::{Request, RouteContext};
+use reservation::command::{GetReservationPayments, PayReservation};
+use crate::api::{ApiResult, RouteData};
+use crate::api::security::{Claims, reservation_policy_from_claims};
+use crate::api::transfer_objects::{GetReservationPaymentsResponse, parse_money, PostReservationPaymentsRequest, PostReservationPaymentsResponse, reservation_payments_to_transfer_object};
+
+pub async fn post_reservation_payments(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationPaymentsResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let body: PostReservationPaymentsRequest = req.json().await?;
+
+    let command = PayReservation {
+        id,
+        amount: parse_money(body.amount)?,
+        payment_method: body.payment_method,
+        now: Utc::now(),
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    let reference = ctx.data.reservation_usecase.pay(command, &policy).await?;
+    Ok(PostReservationPaymentsResponse {
+        reference: reference.to_string()
+    })
+}
+
+pub async fn get_reservation_payments(_: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<GetReservationPaymentsResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let command = GetReservationPayments {
+        id
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    let payments = ctx.data.reservation_usecase.payments(command, &policy).await?;
+    Ok(reservation_payments_to_transfer_object(payments))
+}
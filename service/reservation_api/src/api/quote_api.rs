@@ -0,0 +1,42 @@
+use worker::{Request, RouteContext};
+use reservation::command::{QuoteReservation, SearchItineraries};
+use crate::api::{ApiResult, RouteData};
+use crate::api::transfer_objects::{itinerary_candidate_to_transfer_object, parse_itinerary, parse_passenger_arrangement, PostItinerarySearchesRequest, PostItinerarySearchesResponse, PostQuotesRequest, PostQuotesResponse, price_breakdown_to_transfer_object};
+
+pub async fn post_quotes(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostQuotesResponse> {
+    let usecase =  ctx.data.reservation_usecase;
+    let body: PostQuotesRequest = req.json().await?;
+
+    let command = QuoteReservation {
+        journey: body.journey.parse()?,
+        passengers: parse_passenger_arrangement(body.passengers)?,
+        itinerary: parse_itinerary(body.itinerary)?,
+        currency: body.currency,
+    };
+
+    let result = usecase.quote(command, &ctx.data.currency_converter).await?;
+    Ok(price_breakdown_to_transfer_object(result))
+}
+
+/// priced itineraries with seats for the party, to choose from before posting a reservation
+pub async fn post_itinerary_searches(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostItinerarySearchesResponse> {
+    let usecase =  ctx.data.reservation_usecase;
+    let body: PostItinerarySearchesRequest = req.json().await?;
+
+    let command = SearchItineraries {
+        journey: body.journey.parse()?,
+        departure: body.departure.parse()?,
+        passengers: parse_passenger_arrangement(body.passengers)?,
+        earliest_departure: body.earliest_departure,
+        latest_departure: body.latest_departure,
+        days: body.days,
+        currency: body.currency,
+    };
+
+    let result = usecase.search(command, &ctx.data.currency_converter).await?;
+    Ok(PostItinerarySearchesResponse {
+        itineraries: result.into_iter()
+            .map(itinerary_candidate_to_transfer_object)
+            .collect(),
+    })
+}
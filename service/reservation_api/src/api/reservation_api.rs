@@ -1,8 +1,10 @@
+use chrono::Utc;
 use worker::{Request, RouteContext};
-use reservation::command::{CancelReservation, ConfirmReservation, GetReservation, ReviseItinerary, RevisePassengers};
+use reservation::command::{AssignSeats, BoardPassenger, CancelReservation, CheckInPassengers, ConfirmHeldReservation, ConfirmReservation, GetReservation, HoldReservation, GetReservationPrice, RequestContactEmailVerification, ReviseContact, ReviseItinerary, RevisePassengers, VerifyContactEmail};
+use reservation::policy::ReservationPolicy;
 use crate::api::{ApiResult, RouteData};
 use crate::api::security::{Claims, reservation_policy_from_claims};
-use crate::api::transfer_objects::{DeleteReservationResponse, GetReservationResponse, parse_contact, parse_itinerary, parse_passenger_arrangement, parse_passengers, PostReservationsRequest, PostReservationsResponse, PutReservationItineraryRequest, PutReservationItineraryResponse, PutReservationPassengersRequest, PutReservationPassengersResponse, reservation_to_transfer_object};
+use crate::api::transfer_objects::{assigned_seat_to_transfer_object, boarding_pass_to_transfer_object, PostReservationBoardingRequest, PostReservationBoardingResponse, PostReservationCheckInRequest, PostReservationCheckInResponse, DeleteReservationResponse, GetReservationPriceResponse, GetReservationResponse, price_breakdown_to_transfer_object, PostContactEmailVerificationResponse, PostReservationConfirmationResponse, PostReservationHoldsRequest, PostReservationHoldsResponse, PutContactEmailVerificationRequest, PutContactEmailVerificationResponse, PutReservationContactRequest, PutReservationContactResponse, parse_contact, parse_itinerary, parse_passenger_arrangement, parse_passengers, parse_seat_choices, PostReservationsRequest, PostReservationsResponse, PutReservationItineraryRequest, PutReservationItineraryResponse, PutReservationPassengersRequest, PutReservationPassengersResponse, PutReservationSeatsRequest, PutReservationSeatsResponse, reservation_to_transfer_object};
 
 pub async fn post_reservations(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationsResponse> {
     let usecase =  ctx.data.reservation_usecase;
@@ -23,16 +25,64 @@ pub async fn post_reservations(mut req: Request, claims: Option<Claims>, ctx: Ro
     })
 }
 
-pub async fn get_reservation(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetReservationResponse> {
+/// seats are held until confirmed, see post_reservation_confirmation; only by the contact (verified token email) or an agent
+pub async fn post_reservation_holds(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationHoldsResponse> {
+    let usecase =  ctx.data.reservation_usecase;
+    let body: PostReservationHoldsRequest = req.json().await?;
+
+    let command = HoldReservation {
+        journey: body.journey.parse()?,
+        contact: parse_contact(body.contact)?,
+        passengers: parse_passenger_arrangement(body.passengers)?,
+        itinerary: parse_itinerary(body.itinerary)?,
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+
+    let result = usecase.hold(command, &policy).await?;
+    Ok(PostReservationHoldsResponse{
+        id: result.to_string(),
+    })
+}
+
+pub async fn post_reservation_confirmation(_: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationConfirmationResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = ConfirmHeldReservation {
+        id
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    usecase.confirm_hold(command, &policy).await?;
+
+    Ok(PostReservationConfirmationResponse{
+        id,
+    })
+}
+
+pub async fn get_reservation(_: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<GetReservationResponse> {
     let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
     let command = GetReservation {
         id
     };
 
-    let reservation = ctx.data.reservation_usecase.get(command).await?;
+    let policy = reservation_policy_from_claims(claims);
+    let reservation = ctx.data.reservation_usecase.get(command, &policy).await?;
     Ok(reservation_to_transfer_object(reservation))
 }
 
+pub async fn get_reservation_price(_: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<GetReservationPriceResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let command = GetReservationPrice {
+        id
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    let price = ctx.data.reservation_usecase.price(command, &policy).await?;
+    Ok(price_breakdown_to_transfer_object(price))
+}
+
 pub async fn put_reservation_passengers(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PutReservationPassengersResponse> {
     let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
     let body: PutReservationPassengersRequest = req.json().await?;
@@ -52,6 +102,77 @@ pub async fn put_reservation_passengers(mut req: Request, claims: Option<Claims>
     })
 }
 
+pub async fn put_reservation_seats(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PutReservationSeatsResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let flight = ctx.param("flight").cloned().unwrap_or_default();
+    let body: PutReservationSeatsRequest = req.json().await?;
+
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = AssignSeats {
+        reservation: id,
+        flight,
+        seats: parse_seat_choices(body.seats)?,
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    let seats = usecase.assign_seats(command, &policy).await?;
+
+    Ok(PutReservationSeatsResponse {
+        seats: seats.into_iter()
+            .map(assigned_seat_to_transfer_object)
+            .collect(),
+    })
+}
+
+pub async fn post_reservation_check_in(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationCheckInResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let flight = ctx.param("flight").cloned().unwrap_or_default();
+    let body: PostReservationCheckInRequest = req.json().await?;
+
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = CheckInPassengers {
+        reservation: id,
+        flight,
+        passengers: body.passengers.iter()
+            .map(|passenger| passenger.parse())
+            .collect::<Result<_, _>>()?,
+        now: Utc::now(),
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    let boarding_passes = usecase.check_in(command, &policy).await?;
+
+    Ok(PostReservationCheckInResponse {
+        boarding_passes: boarding_passes.into_iter()
+            .map(boarding_pass_to_transfer_object)
+            .collect(),
+    })
+}
+
+/// the boarding pass is scanned at the gate by an agent
+pub async fn post_reservation_boarding(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostReservationBoardingResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let flight = ctx.param("flight").cloned().unwrap_or_default();
+    let body: PostReservationBoardingRequest = req.json().await?;
+
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = BoardPassenger {
+        reservation: id,
+        flight,
+        code: body.code,
+        now: Utc::now(),
+    };
+
+    let passenger = usecase.board(command, &ReservationPolicy::agent()).await?;
+
+    Ok(PostReservationBoardingResponse {
+        passenger: passenger.to_string(),
+    })
+}
+
 pub async fn put_reservation_itinerary(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PutReservationItineraryResponse> {
     let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
     let body: PutReservationItineraryRequest = req.json().await?;
@@ -71,6 +192,25 @@ pub async fn put_reservation_itinerary(mut req: Request, claims: Option<Claims>,
     })
 }
 
+pub async fn put_reservation_contact(mut req: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<PutReservationContactResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let body: PutReservationContactRequest = req.json().await?;
+
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = ReviseContact {
+        reservation: id,
+        contact: parse_contact(body)?,
+    };
+
+    let policy = reservation_policy_from_claims(claims);
+    usecase.revise_contact(command, &policy).await?;
+
+    Ok(PutReservationContactResponse{
+        id,
+    })
+}
+
 pub async fn delete_reservation(_: Request, claims: Option<Claims>, ctx: RouteContext<RouteData>) -> ApiResult<DeleteReservationResponse> {
     let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
     let usecase =  ctx.data.reservation_usecase;
@@ -85,4 +225,40 @@ pub async fn delete_reservation(_: Request, claims: Option<Claims>, ctx: RouteCo
     Ok(DeleteReservationResponse{
         id,
     })
-}
\ No newline at end of file
+}
+
+/// anyone may request a challenge, it is only sent to the contact email address
+pub async fn post_contact_email_verification(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostContactEmailVerificationResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = RequestContactEmailVerification {
+        reservation: id,
+        now: Utc::now(),
+    };
+
+    usecase.request_contact_email_verification(command).await?;
+
+    Ok(PostContactEmailVerificationResponse{
+        id,
+    })
+}
+
+pub async fn put_contact_email_verification(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PutContactEmailVerificationResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let body: PutContactEmailVerificationRequest = req.json().await?;
+
+    let usecase =  ctx.data.reservation_usecase;
+
+    let command = VerifyContactEmail {
+        reservation: id,
+        challenge: body.challenge,
+        now: Utc::now(),
+    };
+
+    usecase.verify_contact_email(command).await?;
+
+    Ok(PutContactEmailVerificationResponse{
+        id,
+    })
+}
@@ -0,0 +1,117 @@
+use chrono::Utc;
+use prelude::security::jwt::{decode_base64, parse, Payload, TokenError};
+use worker::js_sys::{Array, JsString, Object, Reflect, Uint8Array};
+use worker::wasm_bindgen::{JsCast, JsValue};
+use worker::wasm_bindgen_futures::JsFuture;
+use web_sys::{CryptoKey, SubtleCrypto, WorkerGlobalScope};
+use crate::api::security::{Claims, Scope};
+
+/// verifies HS256 or RS256 signed JWTs, keys are taken from worker secrets
+pub struct TokenVerifier {
+    hs256_secret: Option<String>,
+    rs256_public_key: Option<String>,
+    audience: String,
+    issuer: Option<String>,
+}
+
+impl TokenVerifier {
+    /// rs256_public_key is a PEM encoded SPKI public key, the issuer is verified if given
+    pub fn new(hs256_secret: Option<String>, rs256_public_key: Option<String>, audience: String, issuer: Option<String>) -> Self {
+        Self {
+            hs256_secret,
+            rs256_public_key,
+            audience,
+            issuer,
+        }
+    }
+
+    pub async fn verify(&self, token: &str) -> Result<Claims, TokenError> {
+        let (header, payload, signature, signing_input) = parse(token)?;
+
+        let valid = match (header.alg.as_str(), &self.hs256_secret, &self.rs256_public_key) {
+            ("HS256", Some(secret), _) => verify_hs256(secret, &signature, signing_input).await?,
+            ("RS256", _, Some(public_key)) => verify_rs256(public_key, &signature, signing_input).await?,
+            _ => return Err(TokenError::UnsupportedAlgorithm(header.alg)),
+        };
+
+        if !valid {
+            return Err(TokenError::InvalidSignature);
+        }
+
+        self.validate(payload, Utc::now().timestamp())
+    }
+
+    fn validate(&self, payload: Payload, now: i64) -> Result<Claims, TokenError> {
+        let payload = payload.validate(&self.audience, self.issuer.as_deref(), now)?;
+
+        let scopes = payload.scopes()
+            .filter_map(|scope| match scope {
+                "agent" => Some(Scope::AgentScope),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Claims {
+            scopes,
+            email: payload.verified_email(),
+        })
+    }
+}
+
+async fn verify_hs256(secret: &str, signature: &[u8], data: &str) -> Result<bool, TokenError> {
+    let algorithm = algorithm("HMAC")?;
+    let key_data = Uint8Array::from(secret.as_bytes());
+
+    verify_signature("raw", &key_data, &algorithm, signature, data).await
+}
+
+async fn verify_rs256(public_key: &str, signature: &[u8], data: &str) -> Result<bool, TokenError> {
+    let algorithm = algorithm("RSASSA-PKCS1-v1_5")?;
+    let der: String = public_key
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let key_data = Uint8Array::from(decode_base64(der.trim())?.as_slice());
+
+    verify_signature("spki", &key_data, &algorithm, signature, data).await
+}
+
+async fn verify_signature(format: &str, key_data: &Object, algorithm: &Object, signature: &[u8], data: &str) -> Result<bool, TokenError> {
+    let subtle = subtle_crypto()?;
+    let usages = Array::of1(&JsString::from("verify"));
+
+    let key = subtle.import_key_with_object(format, key_data, algorithm, false, &usages)
+        .map_err(crypto_error)?;
+    let key: CryptoKey = JsFuture::from(key).await
+        .map_err(crypto_error)?
+        .unchecked_into();
+
+    let valid = subtle.verify_with_object_and_u8_array_and_u8_slice(algorithm, &key, &Uint8Array::from(signature), data.as_bytes())
+        .map_err(crypto_error)?;
+    let valid = JsFuture::from(valid).await
+        .map_err(crypto_error)?;
+
+    Ok(valid.as_bool().unwrap_or(false))
+}
+
+/// import and verify parameters, both algorithms hash with SHA-256
+fn algorithm(name: &str) -> Result<Object, TokenError> {
+    let algorithm = Object::new();
+    Reflect::set(&algorithm, &"name".into(), &name.into()).map_err(crypto_error)?;
+    Reflect::set(&algorithm, &"hash".into(), &"SHA-256".into()).map_err(crypto_error)?;
+
+    Ok(algorithm)
+}
+
+fn subtle_crypto() -> Result<SubtleCrypto, TokenError> {
+    let crypto = worker::js_sys::global()
+        .unchecked_into::<WorkerGlobalScope>()
+        .crypto()
+        .map_err(crypto_error)?;
+
+    Ok(crypto.subtle())
+}
+
+fn crypto_error(error: JsValue) -> TokenError {
+    TokenError::CryptoError(format!("{:?}", error))
+}
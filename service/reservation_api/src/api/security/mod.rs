@@ -1,31 +1,29 @@
 use reservation::policy::ReservationPolicy;
 
+mod jwt;
+
+pub use jwt::TokenVerifier;
+
 pub struct Claims {
-    pub scopes: Vec<Scope>
+    pub scopes: Vec<Scope>,
+    /// only present when the issuer verified the email address
+    pub email: Option<String>,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Debug)]
 pub enum Scope {
     AgentScope
 }
 
-pub fn parse_bearer_token(value: &str) -> Claims {
-    let mut scopes = Vec::new();
-    if value.contains("agent") {
-        scopes.push(Scope::AgentScope);
-    }
-
-    Claims {
-        scopes
-    }
-}
-
 pub fn reservation_policy_from_claims(claims: Option<Claims>) -> ReservationPolicy {
     claims
         .map(|claims| {
             if claims.scopes.contains(&Scope::AgentScope) {
                 ReservationPolicy::agent()
             }
+            else if let Some(email) = claims.email.and_then(|email| email.parse().ok()) {
+                ReservationPolicy::passenger_with_email(email)
+            }
             else {
                 ReservationPolicy::passenger()
             }
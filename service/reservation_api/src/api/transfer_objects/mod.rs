@@ -1,9 +1,15 @@
 use std::collections::LinkedList;
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use worker::Url;
 use prelude::collection::SortedSet;
+use prelude::data::Currency;
 use reservation::aggregate;
 use reservation::aggregate::ReservationId;
+use reservation::command::ReservationPayments;
+use reservation::policy;
+use reservation::projection::{JourneysFilter, YearMonth};
+use reservation::services::{itinerary_search, reservation_pricing_strategy};
 use crate::api::ApiResult;
 
 #[derive(Serialize, Deserialize)]
@@ -14,7 +20,13 @@ pub struct Error {
 #[derive(Serialize, Deserialize)]
 pub struct PostJourneyRequest {
     pub name: String,
+    /// a round trip when left out
+    #[serde(default)]
+    pub shape: JourneyShape,
     pub segments: Vec<Segment>,
+    /// standard pricing when left out
+    #[serde(default)]
+    pub pricing: Option<PricingPolicy>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -22,26 +34,189 @@ pub struct PostJourneyResponse {
     pub id: String,
 }
 
+/// e.g. "one_way", or {"open_jaw": {"from": "ENLI", "to": "ENBR"}} when travelling overland from one airfield to the other
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JourneyShape {
+    #[default]
+    RoundTrip,
+    OneWay,
+    OpenJaw {
+        from: String,
+        to: String,
+    },
+}
+
+pub fn parse_journey_shape(value: JourneyShape) -> ApiResult<aggregate::JourneyShape> {
+    match value {
+        JourneyShape::RoundTrip => Ok(aggregate::JourneyShape::RoundTrip),
+        JourneyShape::OneWay => Ok(aggregate::JourneyShape::OneWay),
+        JourneyShape::OpenJaw { from, to } => Ok(aggregate::JourneyShape::OpenJaw {
+            from: from.parse()?,
+            to: to.parse()?,
+        }),
+    }
+}
+
+/// applied in order, all or none
+#[derive(Serialize, Deserialize)]
+pub struct PostJourneyRevisionsRequest {
+    pub revisions: Vec<JourneyRevision>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostJourneyRevisionsResponse {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteJourneyResponse {
+    pub id: String,
+}
+
+/// segments are referred to by the route of their flight
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JourneyRevision {
+    Rename(String),
+    AddAccommodation {
+        segment: FlightRoute,
+        accommodation: Accommodation,
+    },
+    RemoveAccommodation {
+        segment: FlightRoute,
+        /// id as listed in the journey catalogue
+        accommodation: String,
+    },
+}
+
+pub fn parse_journey_revisions(value: Vec<JourneyRevision>) -> ApiResult<Vec<aggregate::JourneyRevision>> {
+    value.into_iter()
+        .map(parse_journey_revision)
+        .collect()
+}
+
+pub fn parse_journey_revision(value: JourneyRevision) -> ApiResult<aggregate::JourneyRevision> {
+    match value {
+        JourneyRevision::Rename(name) => {
+            Ok(aggregate::JourneyRevision::Rename(name.parse()?))
+        }
+
+        JourneyRevision::AddAccommodation { segment, mut accommodation } => {
+            let surcharge = accommodation.surcharge_per_night.take()
+                .map(parse_money)
+                .transpose()?;
+
+            Ok(aggregate::JourneyRevision::AddAccommodation(
+                parse_flight_route(segment)?,
+                parse_accommodation(accommodation)?,
+                surcharge,
+            ))
+        }
+
+        JourneyRevision::RemoveAccommodation { segment, accommodation } => {
+            Ok(aggregate::JourneyRevision::RemoveAccommodation(
+                parse_flight_route(segment)?,
+                accommodation.parse()?,
+            ))
+        }
+    }
+}
+
+/// e.g. ?departure=EHAM&month=2024-05&seats=2, searches the current month for a single seat by default
+pub fn parse_journeys_query(url: &Url, now: DateTime<Utc>) -> ApiResult<(JourneysFilter, YearMonth)> {
+    let mut filter = JourneysFilter {
+        departure: None,
+        seats: 1,
+        now,
+    };
+    let mut period = YearMonth::from_naive_date(now.date_naive());
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "departure" => filter.departure = Some(value.parse::<aggregate::AirfieldId>()?.to_string()),
+            "month" => period = YearMonth::from_naive_date(NaiveDate::parse_from_str(&[&value, "-01"].concat(), "%Y-%m-%d")?),
+            "seats" => filter.seats = value.parse()?,
+            _ => {}
+        }
+    }
+
+    Ok((filter, period))
+}
+
+/// seats sold relative to the seats on board, e.g. 110 sells 11 seats for every 10
+#[derive(Serialize, Deserialize)]
+pub struct PutFlightOverbookingRequest {
+    pub percentage: u16,
+}
+
+#[derive(Serialize)]
+pub struct PutFlightOverbookingResponse {
+    pub id: String,
+}
+
+/// rooms the accommodation holds for us each night
+#[derive(Serialize, Deserialize)]
+pub struct PutAccommodationAllotmentRequest {
+    pub rooms: u8,
+}
+
+#[derive(Serialize)]
+pub struct PutAccommodationAllotmentResponse {
+    pub id: String,
+}
+
+#[derive(Serialize)]
+pub struct PostFlightBoardingClosureResponse {
+    pub id: String,
+    /// reservations with passengers that did not show up
+    pub reservations: Vec<ReservationId>,
+}
+
+#[derive(Serialize)]
+pub struct GetFlightSeatsResponse {
+    pub seats: Vec<Seat>,
+}
+
+#[derive(Serialize)]
+pub struct Seat {
+    pub number: String,
+    pub window: bool,
+    pub available: bool,
+}
+
+pub fn seat_to_transfer_object((seat, taken): (aggregate::Seat, bool)) -> Seat {
+    Seat {
+        number: seat.number.to_string(),
+        window: seat.window,
+        available: !taken,
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Segment {
     pub flight: FlightRoute,
     pub accommodations: Vec<Accommodation>
 }
 
-pub fn parse_segments(value: Vec<Segment>) -> ApiResult<SortedSet<aggregate::Segment>> {
+/// accommodations are assigned their id while parsing, hence their surcharges are collected along
+pub fn parse_segments(value: Vec<Segment>) -> ApiResult<(SortedSet<aggregate::Segment>, Vec<policy::AccommodationSurcharge>)> {
     let mut segments = SortedSet::empty();
+    let mut surcharges = Vec::new();
     for segment in value {
-        segments = segments.insert(parse_segment(segment)?);
+        let (segment, segment_surcharges) = parse_segment(segment)?;
+        segments = segments.insert(segment);
+        surcharges.extend(segment_surcharges);
     }
-    Ok(segments)
+    Ok((segments, surcharges))
 }
 
-pub fn parse_segment(value: Segment) -> ApiResult<aggregate::Segment> {
+pub fn parse_segment(value: Segment) -> ApiResult<(aggregate::Segment, Vec<policy::AccommodationSurcharge>)> {
     let flight_route = parse_flight_route(value.flight)?;
-    let accommodations = parse_accommodations(value.accommodations)?;
+    let (accommodations, surcharges) = parse_accommodations(value.accommodations)?;
 
-    aggregate::Segment::build(flight_route, accommodations)
-        .map_err(|error| error.into())
+    let segment = aggregate::Segment::build(flight_route, accommodations)?;
+    Ok((segment, surcharges))
 }
 
 #[derive(Serialize, Deserialize)]
@@ -63,6 +238,9 @@ pub struct Accommodation {
     pub name: String,
     pub place: Place,
     pub pictures: Vec<Picture>,
+    /// per night per passenger, on top of the daily rates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surcharge_per_night: Option<Money>,
 }
 
 pub fn accommodation_to_transfer_object(value: aggregate::Accommodation) -> Accommodation {
@@ -71,16 +249,27 @@ pub fn accommodation_to_transfer_object(value: aggregate::Accommodation) -> Acco
         place: place_to_transfer_object(value.place),
         pictures: value.pictures.into_iter()
             .map(picture_to_transfer_object)
-            .collect()
+            .collect(),
+        surcharge_per_night: None,
     }
 }
 
-pub fn parse_accommodations(value: Vec<Accommodation>) -> ApiResult<SortedSet<aggregate::Accommodation>> {
+pub fn parse_accommodations(value: Vec<Accommodation>) -> ApiResult<(SortedSet<aggregate::Accommodation>, Vec<policy::AccommodationSurcharge>)> {
     let mut accommodations = SortedSet::empty();
-    for accommodation in value {
-        accommodations = accommodations.insert(parse_accommodation(accommodation)?);
+    let mut surcharges = Vec::new();
+    for mut accommodation in value {
+        let surcharge = accommodation.surcharge_per_night.take();
+        let accommodation = parse_accommodation(accommodation)?;
+
+        if let Some(surcharge) = surcharge {
+            surcharges.push(policy::AccommodationSurcharge {
+                accommodation: accommodation.id,
+                price_per_night_per_passenger: parse_money(surcharge)?,
+            });
+        }
+        accommodations = accommodations.insert(accommodation);
     }
-    Ok(accommodations)
+    Ok((accommodations, surcharges))
 }
 
 pub fn parse_accommodation(value: Accommodation) -> ApiResult<aggregate::Accommodation> {
@@ -155,27 +344,45 @@ pub struct PostReservationsResponse {
     pub id: String,
 }
 
+pub type PostReservationHoldsRequest = PostReservationsRequest;
+
+pub type PostReservationHoldsResponse = PostReservationsResponse;
+
+#[derive(Serialize)]
+pub struct PostReservationConfirmationResponse {
+    pub id: ReservationId
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Contact {
     pub name: String,
     pub email: String,
+    #[serde(default)]
+    pub phone: Option<String>,
 }
 
 pub fn parse_contact(value: Contact) -> ApiResult<aggregate::Contact> {
-    Ok(aggregate::Contact::new(value.name.parse()?, value.email.parse()?, None))
+    let phone = value.phone
+        .map(|phone| phone.parse())
+        .transpose()?;
+
+    Ok(aggregate::Contact::new(value.name.parse()?, value.email.parse()?, phone))
 }
 
 pub fn contact_to_transfer_object(value: aggregate::Contact) -> Contact {
     Contact {
         name: value.name.to_string(),
         email: value.email.to_string(),
+        phone: value.phone.map(|phone| phone.to_string()),
     }
 }
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Passengers {
-    Arrangement(PassengerArrangement)
+    Arrangement(PassengerArrangement),
+    /// by name, as needed to assign seats
+    List(Vec<Passenger>),
 }
 
 pub fn parse_passengers(value: Passengers) -> ApiResult<aggregate::Passengers> {
@@ -185,6 +392,14 @@ pub fn parse_passengers(value: Passengers) -> ApiResult<aggregate::Passengers> {
                 parse_passenger_arrangement(arrangement_value)?
             ))
         }
+
+        Passengers::List(list_value) => {
+            let passengers = list_value.into_iter()
+                .map(parse_passenger)
+                .collect::<ApiResult<Vec<_>>>()?;
+
+            Ok(aggregate::Passengers::List(SortedSet::from_vec(passengers)))
+        }
     }
 }
 
@@ -192,9 +407,36 @@ pub fn parse_passengers(value: Passengers) -> ApiResult<aggregate::Passengers> {
 pub fn passengers_to_transfer_object(value: aggregate::Passengers) -> Passengers {
     let today = Utc::now().date_naive();
 
-    Passengers::Arrangement(passengers_arrangement_to_transfer_object(
-        value.arrangement(today)
-    ))
+    match value {
+        aggregate::Passengers::List(list) => {
+            Passengers::List(list.into_iter()
+                .map(passenger_to_transfer_object)
+                .collect())
+        }
+
+        arrangement => {
+            Passengers::Arrangement(passengers_arrangement_to_transfer_object(
+                arrangement.arrangement(today)
+            ))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Passenger {
+    pub name: String,
+    pub date_of_birth: NaiveDate,
+}
+
+pub fn parse_passenger(value: Passenger) -> ApiResult<aggregate::Passenger> {
+    Ok(aggregate::Passenger::new(value.name.parse()?, value.date_of_birth))
+}
+
+pub fn passenger_to_transfer_object(value: aggregate::Passenger) -> Passenger {
+    Passenger {
+        name: value.name().to_string(),
+        date_of_birth: value.date_of_birth(),
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -229,7 +471,8 @@ pub enum FullItineraryStage {
     Planned(FlightAndAccommodation),
     Reserved(FlightAndAccommodation),
     ReservedFailed(FlightAndAccommodation),
-    Annulled(FlightAndAccommodation)
+    Annulled(FlightAndAccommodation),
+    Disrupted(FlightAndAccommodation),
 }
 
 pub fn itinerary_stage_to_transfer_object(value: aggregate::ItineraryStage) -> FullItineraryStage {
@@ -265,6 +508,14 @@ pub fn itinerary_stage_to_transfer_object(value: aggregate::ItineraryStage) -> F
             };
             FullItineraryStage::Annulled(flight_and_accommodation)
         }
+
+        aggregate::ItineraryStage::Disrupted(flight, accommodation, _) => {
+            let flight_and_accommodation = FlightAndAccommodation {
+                flight: flight_to_transfer_object(flight),
+                accommodation: accommodation.map(accommodation_to_transfer_object)
+            };
+            FullItineraryStage::Disrupted(flight_and_accommodation)
+        }
     }
 }
 
@@ -333,12 +584,20 @@ pub type GetReservationResponse = Reservation;
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Reservation {
+    Held(HeldReservation),
     Confirmed(ConfirmedReservation),
     Cancelled(CancelledReservation),
 }
 
 pub fn reservation_to_transfer_object(value: aggregate::Reservation) -> Reservation {
     match value {
+        aggregate::Reservation::Held(held) => {
+            Reservation::Held(HeldReservation {
+                expires_at: held.expires_at,
+                reservation: confirmed_reservation_to_transfer_object(held.reservation),
+            })
+        }
+
         aggregate::Reservation::Confirmed(confirmed) => {
             Reservation::Confirmed(confirmed_reservation_to_transfer_object(confirmed))
         }
@@ -369,6 +628,8 @@ pub struct ConfirmedReservation {
     pub contact: Contact,
     pub passengers: Passengers,
     pub itinerary: Itinerary,
+    pub seats: Vec<AssignedSeat>,
+    pub boarding: Vec<PassengerBoarding>,
     pub revisions: Vec<Revision>
 }
 
@@ -379,12 +640,57 @@ pub fn confirmed_reservation_to_transfer_object(value: aggregate::ConfirmedReser
         contact: contact_to_transfer_object(value.contact),
         passengers: passengers_to_transfer_object(value.passengers),
         itinerary: itinerary_to_transfer_object(value.itinerary),
+        seats: value.seats.into_iter()
+            .map(assigned_seat_to_transfer_object)
+            .collect(),
+        boarding: value.boarding.into_iter()
+            .map(passenger_boarding_to_transfer_object)
+            .collect(),
         revisions: value.revisions.into_iter()
             .map(revision_to_transfer_object)
             .collect()
     }
 }
 
+/// the code of the boarding pass is left out, it is only handed out at check-in
+#[derive(Serialize)]
+pub struct PassengerBoarding {
+    pub flight: String,
+    pub passenger: String,
+    pub status: BoardingStatus,
+    pub time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardingStatus {
+    CheckedIn,
+    Boarded,
+    NoShow,
+}
+
+pub fn passenger_boarding_to_transfer_object(value: aggregate::PassengerBoarding) -> PassengerBoarding {
+    let (status, time) = match value.status {
+        aggregate::BoardingStatus::CheckedIn(_) => (BoardingStatus::CheckedIn, None),
+        aggregate::BoardingStatus::Boarded(_, time) => (BoardingStatus::Boarded, Some(time)),
+        aggregate::BoardingStatus::NoShow => (BoardingStatus::NoShow, None),
+    };
+
+    PassengerBoarding {
+        flight: value.flight,
+        passenger: value.passenger.to_string(),
+        status,
+        time,
+    }
+}
+
+#[derive(Serialize)]
+pub struct HeldReservation {
+    #[serde(flatten)]
+    pub reservation: ConfirmedReservation,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Serialize)]
 pub struct CancelledReservation {
     pub id: String,
@@ -406,11 +712,6 @@ pub fn cancelled_reservation_to_transfer_object(value: aggregate::CancelledReser
     }
 }
 
-#[derive(Deserialize)]
-pub struct DeleteReservationRequest {
-
-}
-
 #[derive(Serialize)]
 pub struct DeleteReservationResponse {
     pub id: ReservationId
@@ -424,9 +725,348 @@ pub struct PutReservationPassengersResponse {
 }
 
 
+/// passengers left out are assigned a seat automatically
+#[derive(Serialize, Deserialize)]
+pub struct PutReservationSeatsRequest {
+    pub seats: Vec<SeatChoice>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SeatChoice {
+    pub passenger: String,
+    pub seat: String,
+}
+
+pub fn parse_seat_choices(value: Vec<SeatChoice>) -> ApiResult<Vec<(aggregate::PersonFullName, aggregate::SeatNumber)>> {
+    value.into_iter()
+        .map(|choice| Ok((choice.passenger.parse()?, choice.seat.parse()?)))
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct PutReservationSeatsResponse {
+    pub seats: Vec<AssignedSeat>,
+}
+
+#[derive(Serialize)]
+pub struct AssignedSeat {
+    pub flight: String,
+    pub passenger: String,
+    pub seat: String,
+    pub window: bool,
+    pub chosen: bool,
+}
+
+pub fn assigned_seat_to_transfer_object(value: aggregate::AssignedSeat) -> AssignedSeat {
+    AssignedSeat {
+        flight: value.flight,
+        passenger: value.passenger.to_string(),
+        seat: value.seat.to_string(),
+        window: value.window,
+        chosen: value.chosen,
+    }
+}
+
+/// all passengers are checked in when none are named
+#[derive(Serialize, Deserialize, Default)]
+pub struct PostReservationCheckInRequest {
+    #[serde(default)]
+    pub passengers: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PostReservationCheckInResponse {
+    pub boarding_passes: Vec<BoardingPass>,
+}
+
+#[derive(Serialize)]
+pub struct BoardingPass {
+    pub flight: String,
+    pub passenger: String,
+    pub seat: Option<String>,
+    pub code: String,
+    pub issued_at: DateTime<Utc>,
+}
+
+pub fn boarding_pass_to_transfer_object(value: aggregate::BoardingPass) -> BoardingPass {
+    BoardingPass {
+        code: value.code().to_owned(),
+        flight: value.flight,
+        passenger: value.passenger.to_string(),
+        seat: value.seat.map(|seat| seat.to_string()),
+        issued_at: value.issued_at,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostReservationBoardingRequest {
+    pub code: String,
+}
+
+#[derive(Serialize)]
+pub struct PostReservationBoardingResponse {
+    pub passenger: String,
+}
+
 pub type PutReservationItineraryRequest = Vec<ItineraryStage>;
 
 #[derive(Serialize)]
 pub struct PutReservationItineraryResponse {
     pub id: ReservationId
-}
\ No newline at end of file
+}
+
+pub type PutReservationContactRequest = Contact;
+
+#[derive(Serialize)]
+pub struct PutReservationContactResponse {
+    pub id: ReservationId
+}
+
+#[derive(Serialize)]
+pub struct PostContactEmailVerificationResponse {
+    pub id: ReservationId
+}
+
+#[derive(Deserialize)]
+pub struct PutContactEmailVerificationRequest {
+    pub challenge: String
+}
+
+#[derive(Serialize)]
+pub struct PutContactEmailVerificationResponse {
+    pub id: ReservationId
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Money {
+    amount: String,
+    currency: Currency,
+}
+
+pub fn money_to_transfer_object(value: prelude::data::Money) -> Money {
+    Money {
+        amount: value.major_units().to_string(),
+        currency: value.currency(),
+    }
+}
+
+pub fn parse_money(value: Money) -> ApiResult<prelude::data::Money> {
+    Ok(prelude::data::Money::parse(&value.amount, value.currency)?)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PricingPolicy {
+    pub adult_price_per_day: Money,
+    pub child_price_per_day: Money,
+    #[serde(default)]
+    pub seasons: Vec<Season>,
+    #[serde(default)]
+    pub refund_tiers: Vec<RefundTier>,
+    #[serde(default)]
+    pub window_seat_price: Option<Money>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Season {
+    pub from: NaiveDate,
+    pub until: NaiveDate,
+    pub percentage: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RefundTier {
+    pub days_before_departure: i64,
+    pub refund_percentage: u8,
+}
+
+/// surcharges are declared with the accommodations, see parse_segments
+pub fn parse_pricing_policy(value: Option<PricingPolicy>, surcharges: Vec<policy::AccommodationSurcharge>) -> ApiResult<policy::PricingPolicy> {
+    let standard = policy::PricingPolicy::standard();
+
+    let policy = match value {
+        Some(value) => {
+            let window_seat_price = value.window_seat_price.map(parse_money).transpose()?;
+            let policy = policy::PricingPolicy::build(
+                parse_money(value.adult_price_per_day)?,
+                parse_money(value.child_price_per_day)?,
+                value.seasons.into_iter()
+                    .map(|season| policy::Season {
+                        from: season.from,
+                        until: season.until,
+                        percentage: season.percentage,
+                    })
+                    .collect(),
+                surcharges,
+                value.refund_tiers.into_iter()
+                    .map(|tier| policy::RefundTier {
+                        days_before_departure: tier.days_before_departure,
+                        refund_percentage: tier.refund_percentage,
+                    })
+                    .collect(),
+            )?;
+
+            match window_seat_price {
+                Some(price) => policy.with_window_seat_price(price)?,
+                None => policy,
+            }
+        }
+        None => policy::PricingPolicy::build(
+            standard.adult_price_per_day(),
+            standard.child_price_per_day(),
+            standard.seasons().to_vec(),
+            surcharges,
+            standard.refund_tiers().to_vec(),
+        )?,
+    };
+
+    Ok(policy)
+}
+
+#[derive(Serialize)]
+pub struct PriceBreakdown {
+    adult_price_per_day: Money,
+    child_price_per_day: Money,
+    days: i64,
+    adults: u8,
+    children: u8,
+    travel: Money,
+    accommodation_nights: i64,
+    accommodation: Money,
+    window_seats: u8,
+    seats: Money,
+    price: Money,
+    refund_percentage: u8,
+    amount_due: Money,
+}
+
+pub fn price_breakdown_to_transfer_object(value: reservation_pricing_strategy::PriceBreakdown) -> PriceBreakdown {
+    PriceBreakdown {
+        adult_price_per_day: money_to_transfer_object(value.adult_price_per_day),
+        child_price_per_day: money_to_transfer_object(value.child_price_per_day),
+        days: value.days,
+        adults: value.adults,
+        children: value.children,
+        travel: money_to_transfer_object(value.travel),
+        accommodation_nights: value.accommodation_nights,
+        accommodation: money_to_transfer_object(value.accommodation),
+        window_seats: value.window_seats,
+        seats: money_to_transfer_object(value.seats),
+        price: money_to_transfer_object(value.price),
+        refund_percentage: value.refund_percentage,
+        amount_due: money_to_transfer_object(value.amount_due),
+    }
+}
+
+pub type GetReservationPriceResponse = PriceBreakdown;
+
+#[derive(Serialize, Deserialize)]
+pub struct PostQuotesRequest {
+    pub journey: String,
+    pub passengers: PassengerArrangement,
+    pub itinerary: Vec<ItineraryStage>,
+    /// the journey's currency when left out
+    #[serde(default)]
+    pub currency: Option<Currency>,
+}
+
+pub type PostQuotesResponse = PriceBreakdown;
+
+#[derive(Serialize, Deserialize)]
+pub struct PostItinerarySearchesRequest {
+    pub journey: String,
+    /// airfield the itineraries depart from
+    pub departure: String,
+    pub passengers: PassengerArrangement,
+    pub earliest_departure: NaiveDate,
+    pub latest_departure: NaiveDate,
+    /// preferred number of days
+    pub days: i64,
+    /// the journey's currency when left out
+    #[serde(default)]
+    pub currency: Option<Currency>,
+}
+
+#[derive(Serialize)]
+pub struct PostItinerarySearchesResponse {
+    pub itineraries: Vec<ItineraryCandidate>,
+}
+
+/// the itinerary is as expected when posting reservations
+#[derive(Serialize)]
+pub struct ItineraryCandidate {
+    pub itinerary: Vec<ItineraryStage>,
+    pub price: PriceBreakdown,
+}
+
+pub fn itinerary_candidate_to_transfer_object(value: itinerary_search::ItineraryCandidate) -> ItineraryCandidate {
+    ItineraryCandidate {
+        itinerary: value.itinerary.stages()
+            .map(|stage| {
+                let (flight, accommodation) = stage.clone().flight_and_accommodation();
+
+                ItineraryStage {
+                    flight: flight.id,
+                    accommodation: accommodation.map(|accommodation| accommodation.id.to_string()),
+                }
+            })
+            .collect(),
+        price: price_breakdown_to_transfer_object(value.price),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostReservationPaymentsRequest {
+    pub amount: Money,
+    /// token obtained from the payment gateway
+    pub payment_method: String,
+}
+
+#[derive(Serialize)]
+pub struct PostReservationPaymentsResponse {
+    pub reference: String,
+}
+
+#[derive(Serialize)]
+pub struct Payment {
+    reference: String,
+    amount: Money,
+    time: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct Refund {
+    payment: String,
+    reference: String,
+    amount: Money,
+    time: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct GetReservationPaymentsResponse {
+    amount_due: Money,
+    balance: Money,
+    payments: Vec<Payment>,
+    refunds: Vec<Refund>,
+}
+
+pub fn reservation_payments_to_transfer_object(value: ReservationPayments) -> GetReservationPaymentsResponse {
+    GetReservationPaymentsResponse {
+        amount_due: money_to_transfer_object(value.amount_due),
+        balance: money_to_transfer_object(value.balance),
+        payments: value.payments.payments().iter()
+            .map(|payment| Payment {
+                reference: payment.reference.to_string(),
+                amount: money_to_transfer_object(payment.amount),
+                time: payment.time,
+            })
+            .collect(),
+        refunds: value.payments.refunds().iter()
+            .map(|refund| Refund {
+                payment: refund.payment.to_string(),
+                reference: refund.reference.to_string(),
+                amount: money_to_transfer_object(refund.amount),
+                time: refund.time,
+            })
+            .collect(),
+    }
+}
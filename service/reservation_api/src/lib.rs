@@ -1,21 +1,58 @@
+use std::collections::HashMap;
 use std::rc::Rc;
+use chrono::Utc;
 use worker::*;
 use prelude::{durable_object_repository, queue_publisher};
-use reservation::command::{MakeFlightAvailable, RegisterAirfield, ReserveFlight};
+use prelude::data::{Currency, Decimal, StaticCurrencyConverter};
+use reservation::command::{ReconcileOversoldFlights, ReleaseExpiredHolds};
 use reservation::event::{Event, RawEvent};
-use reservation::projection::{JourneysApplyContext, YearMonth};
-use reservation::usecase::{AvailabilityUseCase, JourneyUseCase, ReservationUseCase};
+use reservation::policy::OverbookingPolicy;
+use reservation::projection::{JourneysApplyContext, ManifestApplyContext, YearMonth};
+use reservation::usecase::{AvailabilityUseCase, JourneyUseCase, NotificationUseCase, QueueUseCase, ReservationUseCase};
 use crate::api::RouteData;
+use crate::api::security::TokenVerifier;
+use crate::runtime::gateway::{HttpEmailGateway, HttpPaymentGateway};
 use crate::runtime::repository::{
     DurableObjectReservationRepository,
     DurableObjectReservationRepositoryProtocol,
     R2AvailabilityRepository,
-    R2JourneysRepository
+    R2JourneysRepository,
+    R2ManifestRepository
 };
 
 mod api;
 mod runtime;
 
+macro_rules! payment_gateway {
+    ($env:ident) => {
+        HttpPaymentGateway::new(
+            $env.var("payment_gateway_url").ok().map(|url| url.to_string()),
+            $env.secret("payment_gateway_api_key").ok().map(|secret| secret.to_string()),
+        )
+    }
+}
+
+macro_rules! email_gateway {
+    ($env:ident) => {
+        HttpEmailGateway::new(
+            $env.var("email_gateway_url").ok().map(|url| url.to_string()),
+            $env.secret("email_gateway_api_key").ok().map(|secret| secret.to_string()),
+        )
+    }
+}
+
+/// as JSON, e.g. {"percentage": 110, "routes": [], "check_in_hours_before_departure": 24}
+macro_rules! overbooking_policy {
+    ($env:ident) => {
+        match $env.var("overbooking_policy") {
+            Ok(policy) => serde_json::from_str::<OverbookingPolicy>(&policy.to_string())?
+                .validate()
+                .map_err(|error| error.to_string())?,
+            Err(_) => OverbookingPolicy::none(),
+        }
+    }
+}
+
 durable_object_repository!(ReservationRepository, DurableObjectReservationRepository, DurableObjectReservationRepositoryProtocol);
 
 #[event(queue)]
@@ -29,6 +66,10 @@ pub async fn main(message_batch: MessageBatch<RawEvent>, env: Env, _ctx: Context
     let availability_repository = R2AvailabilityRepository::build(
         env.bucket("reservation_rs_bucket")?
     );
+    let manifest_repository = R2ManifestRepository::build(
+        env.bucket("reservation_rs_bucket")?
+    );
+    let manifest_context = ManifestApplyContext::new(reservation_repository.clone());
 
     let event_publisher = Rc::new(queue_publisher!(env));
 
@@ -39,8 +80,10 @@ pub async fn main(message_batch: MessageBatch<RawEvent>, env: Env, _ctx: Context
     );
 
     let availability_usecase = AvailabilityUseCase::new(
+        reservation_repository.clone(),
         reservation_repository.clone(),
         event_publisher.clone(),
+        overbooking_policy!(env),
     );
 
     let reservation_usecase = ReservationUseCase::new(
@@ -48,11 +91,31 @@ pub async fn main(message_batch: MessageBatch<RawEvent>, env: Env, _ctx: Context
         reservation_repository.clone(),
         reservation_repository.clone(),
         event_publisher.clone(),
+        Rc::new(payment_gateway!(env)),
+    );
+
+    let queue_usecase = QueueUseCase::new(
+        journey_usecase,
+        availability_usecase,
+        reservation_usecase,
+        NotificationUseCase::new(Rc::new(email_gateway!(env))),
     );
 
     for message in message_batch.messages()? {
-        match message.body().deserialize()? {
-            event@Event::JourneyPublishedV1(_) => {
+        let event = message.body().deserialize()?;
+
+        // manifests are projected from events that may be handled by the use cases as well
+        let flights = manifest_context.affected_flights(&event).await
+            .map_err(|error| error.to_string())?;
+        for flight in flights {
+            let manifest = manifest_repository.get(&flight).await?;
+            let manifest = manifest.apply(&manifest_context, &event).await
+                .map_err(|error| error.to_string())?;
+            manifest_repository.set(&manifest).await?;
+        }
+
+        match event {
+            event@(Event::JourneyPublishedV1(_) | Event::JourneyRevisedV1(_) | Event::JourneyWithdrawnV1(_)) => {
                 let context = JourneysApplyContext::new(reservation_repository.clone());
 
                 let journeys = journeys_repository.get().await?;
@@ -61,59 +124,30 @@ pub async fn main(message_batch: MessageBatch<RawEvent>, env: Env, _ctx: Context
                 journeys_repository.set(&journeys).await?;
             }
 
-            Event::FlightScheduledV1(event) => {
-                let command = MakeFlightAvailable {
-                    flight: event.into(),
-                };
-
-                availability_usecase.make_flight_available(command).await
-                    .map_err(|error| error.to_string())?;
-            },
-
             Event::FlightAvailabilityChangedV1(flight) => {
-                let period = YearMonth::from_datetime(flight.departure);
-                let event = Event::FlightAvailabilityChangedV1(flight);
-
-                let availability = availability_repository.get(period).await?;
-                let availability = availability.apply(event);
-                availability_repository.set(availability).await?;
-            }
-
-            Event::AirfieldRegisteredV1(airfield) => {
-                let command = RegisterAirfield {
-                    id: airfield.id,
-                    name: airfield.name,
-                    location: airfield.location,
-                };
+                let mut periods = vec![YearMonth::from_datetime(flight.departure)];
 
-                journey_usecase.register_airfield(command).await
-                    .map_err(|error| error.to_string())?;
-            }
+                // a rescheduled flight may have moved out of its previous period
+                if let Some(previous_departure) = flight.rescheduled_from {
+                    let previous_period = YearMonth::from_datetime(previous_departure);
+                    if !periods.contains(&previous_period) {
+                        periods.push(previous_period);
+                    }
+                }
 
-            Event::FlightReservationRequestedV1(request) => {
-                let command = ReserveFlight {
-                    reservation: request.reservation,
-                    flight: request.flight,
-                    seats: request.seats,
-                };
+                for period in periods {
+                    let event = Event::FlightAvailabilityChangedV1(flight.clone());
 
-                availability_usecase.reserve_flight(command).await
-                    .map_err(|error| error.to_string())?;
+                    let availability = availability_repository.get(period).await?;
+                    let availability = availability.apply(event);
+                    availability_repository.set(availability).await?;
+                }
             }
 
-            Event::FlightReservedV1(event) => {
-                reservation_usecase.handle_flight_reserved(&event).await
+            event => {
+                queue_usecase.handle(event).await
                     .map_err(|error| error.to_string())?;
             }
-
-            Event::FlightReservationFailedV1(event) => {
-                reservation_usecase.handle_flight_reservation_failed(&event).await
-                    .map_err(|error| error.to_string())?;
-            }
-
-            _ => {
-                // ignore other events
-            }
         }
     }
 
@@ -123,6 +157,17 @@ pub async fn main(message_batch: MessageBatch<RawEvent>, env: Env, _ctx: Context
 #[event(fetch)]
 pub async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     let api_key = env.secret("api_key")?;
+    let token_verifier = TokenVerifier::new(
+        env.secret("jwt_hs256_secret").ok().map(|secret| secret.to_string()),
+        env.secret("jwt_rs256_public_key").ok().map(|secret| secret.to_string()),
+        env.var("jwt_audience")?.to_string(),
+        env.var("jwt_issuer").ok().map(|issuer| issuer.to_string()),
+    );
+    // units of currency per USD, as JSON object e.g. {"EUR": "0.92"}
+    let exchange_rates: HashMap<Currency, Decimal> = match env.var("exchange_rates") {
+        Ok(rates) => serde_json::from_str(&rates.to_string())?,
+        Err(_) => HashMap::new(),
+    };
     let repository = Rc::new(DurableObjectReservationRepository::new(
         env.durable_object("reservation_objects")?
     ));
@@ -134,14 +179,87 @@ pub async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
             repository.clone(),
             event_publisher.clone(),
         ),
+        availability_usecase: AvailabilityUseCase::new(
+            repository.clone(),
+            repository.clone(),
+            event_publisher.clone(),
+            overbooking_policy!(env),
+        ),
         reservation_usecase: ReservationUseCase::new(
             repository.clone(),
             repository.clone(),
             repository.clone(),
-            event_publisher.clone()
+            event_publisher.clone(),
+            Rc::new(payment_gateway!(env)),
+        ),
+        journeys_repository: R2JourneysRepository::build(
+            env.bucket("reservation_rs_bucket")?
+        ),
+        availability_repository: R2AvailabilityRepository::build(
+            env.bucket("reservation_rs_bucket")?
+        ),
+        manifest_repository: R2ManifestRepository::build(
+            env.bucket("reservation_rs_bucket")?
         ),
         api_key: api_key.to_string(),
+        token_verifier,
+        currency_converter: StaticCurrencyConverter::new(Currency::USD, exchange_rates),
     };
 
     api::route(req, env, route_data).await
-}
\ No newline at end of file
+}
+
+/// sweeps holds that weren't confirmed in time and reconciles flights of which check-in opened, triggered by cron
+#[event(scheduled)]
+pub async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    if let Err(error) = release_expired_holds(&env).await {
+        console_error!("unable to release expired holds: {}", error);
+    }
+
+    if let Err(error) = reconcile_oversold_flights(&env).await {
+        console_error!("unable to reconcile oversold flights: {}", error);
+    }
+}
+
+async fn release_expired_holds(env: &Env) -> Result<()> {
+    let repository = Rc::new(DurableObjectReservationRepository::new(
+        env.durable_object("reservation_objects")?
+    ));
+    let event_publisher = Rc::new(queue_publisher!(env));
+
+    let reservation_usecase = ReservationUseCase::new(
+        repository.clone(),
+        repository.clone(),
+        repository.clone(),
+        event_publisher.clone(),
+        Rc::new(payment_gateway!(env)),
+    );
+
+    reservation_usecase.release_expired_holds(ReleaseExpiredHolds { now: Utc::now() }).await
+        .map_err(|error| error.to_string())?;
+
+    Ok(())
+}
+
+async fn reconcile_oversold_flights(env: &Env) -> Result<()> {
+    let repository = Rc::new(DurableObjectReservationRepository::new(
+        env.durable_object("reservation_objects")?
+    ));
+    let event_publisher = Rc::new(queue_publisher!(env));
+
+    let availability_usecase = AvailabilityUseCase::new(
+        repository.clone(),
+        repository.clone(),
+        event_publisher.clone(),
+        overbooking_policy!(env),
+    );
+
+    let oversold = availability_usecase.reconcile_oversold_flights(ReconcileOversoldFlights { now: Utc::now() }).await
+        .map_err(|error| error.to_string())?;
+
+    for flight in oversold {
+        console_warn!("flight {} is oversold", flight);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use serde::Serialize;
+use worker::{Fetch, Headers, Method, Request, RequestInit};
+use worker::wasm_bindgen::JsValue;
+use prelude::async_trait;
+use reservation::aggregate::EmailAddress;
+use reservation::gateway::{EmailGateway, EmailGatewayError};
+
+#[derive(Serialize)]
+struct EmailRequest<'a> {
+    to: String,
+    subject: &'a str,
+    body: &'a str,
+}
+
+/// posts emails to the delivery service at url, authorized by the api key;
+/// responds 422 when the address is rejected
+pub struct HttpEmailGateway {
+    url: Option<String>,
+    api_key: Option<String>,
+}
+
+impl HttpEmailGateway {
+    /// when unconfigured, every email fails
+    pub fn new(url: Option<String>, api_key: Option<String>) -> Self {
+        Self {
+            url,
+            api_key,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl EmailGateway for HttpEmailGateway {
+    async fn send(&self, to: &EmailAddress, subject: &str, body: &str) -> Result<(), EmailGatewayError> {
+        let url = self.url.as_ref()
+            .ok_or(EmailGatewayError::IoError("email gateway is not configured".to_owned()))?;
+
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "application/json").map_err(io_error)?;
+        if let Some(api_key) = &self.api_key {
+            headers.set("Authorization", &["Bearer ", api_key].concat()).map_err(io_error)?;
+        }
+
+        let body = serde_json::to_string(&EmailRequest {
+            to: to.to_string(),
+            subject,
+            body,
+        })
+            .map(JsValue::from)
+            .map_err(io_error)?;
+
+        let request = Request::new_with_init(
+            &[url.trim_end_matches('/'), "/emails"].concat(),
+            RequestInit::new()
+                .with_method(Method::Post)
+                .with_headers(headers)
+                .with_body(Some(body))
+        ).map_err(io_error)?;
+
+        let mut response = Fetch::Request(request).send().await.map_err(io_error)?;
+        match response.status_code() {
+            200..=299 => Ok(()),
+            422 => Err(EmailGatewayError::Rejected(response.text().await.unwrap_or_default())),
+            status => Err(EmailGatewayError::IoError(format!("email gateway responded {}", status))),
+        }
+    }
+}
+
+fn io_error<E: ToString>(error: E) -> EmailGatewayError {
+    EmailGatewayError::IoError(error.to_string())
+}
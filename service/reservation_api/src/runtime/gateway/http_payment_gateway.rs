@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use worker::{Fetch, Headers, Method, Request, RequestInit};
+use worker::wasm_bindgen::JsValue;
+use prelude::async_trait;
+use prelude::data::{Currency, Money};
+use reservation::aggregate::{PaymentReference, ReservationId};
+use reservation::gateway::{PaymentGateway, PaymentGatewayError};
+
+#[derive(Serialize)]
+struct ChargeRequest<'a> {
+    reservation: String,
+    amount: String,
+    currency: Currency,
+    payment_method: &'a str,
+}
+
+#[derive(Serialize)]
+struct RefundRequest {
+    reservation: String,
+    payment: String,
+    amount: String,
+    currency: Currency,
+}
+
+#[derive(Deserialize)]
+struct Receipt {
+    reference: String,
+}
+
+/// posts charges and refunds to the payment service at url, authorized by the api key;
+/// charges and refunds carry an Idempotency-Key header; responds with the reference of the charge or refund, or 402 when declined
+pub struct HttpPaymentGateway {
+    url: Option<String>,
+    api_key: Option<String>,
+}
+
+impl HttpPaymentGateway {
+    /// when unconfigured, every charge and refund fails
+    pub fn new(url: Option<String>, api_key: Option<String>) -> Self {
+        Self {
+            url,
+            api_key,
+        }
+    }
+
+    async fn post<T: Serialize>(&self, path: &str, body: &T, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+        let url = self.url.as_ref()
+            .ok_or(PaymentGatewayError::IoError("payment gateway is not configured".to_owned()))?;
+
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "application/json").map_err(io_error)?;
+        if let Some(api_key) = &self.api_key {
+            headers.set("Authorization", &["Bearer ", api_key].concat()).map_err(io_error)?;
+        }
+        headers.set("Idempotency-Key", idempotency_key).map_err(io_error)?;
+
+        let body = serde_json::to_string(body)
+            .map(JsValue::from)
+            .map_err(io_error)?;
+
+        let request = Request::new_with_init(
+            &[url.trim_end_matches('/'), path].concat(),
+            RequestInit::new()
+                .with_method(Method::Post)
+                .with_headers(headers)
+                .with_body(Some(body))
+        ).map_err(io_error)?;
+
+        let mut response = Fetch::Request(request).send().await.map_err(io_error)?;
+        match response.status_code() {
+            200..=299 => {
+                let receipt: Receipt = response.json().await.map_err(io_error)?;
+                receipt.reference.parse().map_err(io_error)
+            }
+            402 => Err(PaymentGatewayError::Declined(response.text().await.unwrap_or_default())),
+            status => Err(PaymentGatewayError::IoError(format!("payment gateway responded {}", status))),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl PaymentGateway for HttpPaymentGateway {
+    async fn charge(&self, reservation: &ReservationId, amount: Money, payment_method: &str, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+        self.post("/charges", &ChargeRequest {
+            reservation: reservation.to_string(),
+            amount: amount.major_units().to_string(),
+            currency: amount.currency(),
+            payment_method,
+        }, idempotency_key).await
+    }
+
+    async fn refund(&self, reservation: &ReservationId, payment: &PaymentReference, amount: Money, idempotency_key: &str) -> Result<PaymentReference, PaymentGatewayError> {
+        self.post("/refunds", &RefundRequest {
+            reservation: reservation.to_string(),
+            payment: payment.to_string(),
+            amount: amount.major_units().to_string(),
+            currency: amount.currency(),
+        }, idempotency_key).await
+    }
+}
+
+fn io_error<E: ToString>(error: E) -> PaymentGatewayError {
+    PaymentGatewayError::IoError(error.to_string())
+}
@@ -0,0 +1,5 @@
+mod http_payment_gateway;
+mod http_email_gateway;
+
+pub use http_payment_gateway::*;
+pub use http_email_gateway::*;
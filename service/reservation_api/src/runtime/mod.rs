@@ -1 +1,2 @@
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+pub mod gateway;
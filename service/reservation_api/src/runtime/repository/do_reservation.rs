@@ -1,12 +1,14 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use worker::{Error, Method, ObjectNamespace, Request, RequestInit, Storage};
+use serde::de::DeserializeOwned;
+use worker::{js_sys, Error, ListOptions, Method, ObjectNamespace, Request, RequestInit, Storage};
 use worker::wasm_bindgen::JsValue;
 use prelude::async_trait;
 use prelude::domain::{Transaction, Versioned};
 use prelude::runtime::repository::Reply;
 use prelude::runtime::repository::Reply::{MalformedPrompt, NotFound, Success, VersionConflict};
-use reservation::aggregate::{Airfield, AirfieldId, FlightAvailability, FlightId, Journey, JourneyId, Reservation, ReservationId};
-use reservation::repository::{AirfieldRepository, AirfieldRepositoryError, FlightAvailabilityRepository, FlightAvailabilityRepositoryError, JourneyRepository, JourneyRepositoryError, ReservationRepository, ReservationRepositoryError};
+use reservation::aggregate::{AccommodationAvailability, AccommodationId, Airfield, AirfieldId, Flight, FlightAvailability, FlightId, FlightRoute, Journey, JourneyId, Reservation, ReservationId};
+use reservation::repository::{AccommodationAvailabilityRepository, AccommodationAvailabilityRepositoryError, AirfieldRepository, AirfieldRepositoryError, FlightAvailabilityRepository, FlightAvailabilityRepositoryError, JourneyRepository, JourneyRepositoryError, ReservationRepository, ReservationRepositoryError};
 use DurableObjectReservationRepositoryProtocol::*;
 
 #[derive(Serialize, Deserialize)]
@@ -32,12 +34,28 @@ pub enum DurableObjectReservationRepositoryProtocol {
     SetFlightAvailabilityPrompt(Versioned<FlightAvailability>),
     SetFlightAvailabilityReply(Reply<()>),
 
+    ListDueForReconciliationPrompt(DateTime<Utc>),
+    ListDueForReconciliationReply(Reply<Vec<FlightId>>),
+
+    ListByRoutePrompt(FlightRoute, DateTime<Utc>, DateTime<Utc>),
+    ListByRouteReply(Reply<Vec<FlightAvailability>>),
+
+    // accommodation availability
+    GetAccommodationAvailabilityPrompt(AccommodationId),
+    GetAccommodationAvailabilityReply(Reply<Versioned<AccommodationAvailability>>),
+
+    SetAccommodationAvailabilityPrompt(Versioned<AccommodationAvailability>),
+    SetAccommodationAvailabilityReply(Reply<()>),
+
     // reservation
     GetReservationPrompt(ReservationId),
     GetReservationReply(Reply<Versioned<Reservation>>),
 
     SetReservationPrompt(Versioned<Reservation>),
     SetReservationReply(Reply<()>),
+
+    ListExpiredHoldsPrompt(DateTime<Utc>),
+    ListExpiredHoldsReply(Reply<Vec<ReservationId>>),
 }
 
 pub struct DurableObjectReservationRepository {
@@ -130,11 +148,17 @@ impl DurableObjectReservationRepository {
                 let key = ["flight_availability:", &id.to_string()].concat();
                 let value: Option<Versioned<FlightAvailability>> = storage.get(&key).await.ok();
 
-                let existing_version = value
+                let existing_version = value.as_ref()
                     .map(|va| va.version())
                     .unwrap_or_default();
 
                 if flight_availability.version() == existing_version + 1 {
+                    // flights are indexed by route and departure, see ListByRoutePrompt
+                    if let Some(existing) = &value {
+                        storage.delete(&route_index_key(&existing.value_ref().flight)).await?;
+                    }
+                    storage.put(&route_index_key(&flight_availability.value_ref().flight), id).await?;
+
                     storage.put(&key, &flight_availability).await?;
                     Ok(SetFlightAvailabilityReply(Success(())))
                 }
@@ -143,6 +167,69 @@ impl DurableObjectReservationRepository {
                 }
             }
 
+            ListDueForReconciliationPrompt(departing_before) => {
+                let ids = list::<FlightAvailability>(storage, "flight_availability:").await?
+                    .into_iter()
+                    .filter(|availability| availability.is_due_for_reconciliation(departing_before))
+                    .map(|availability| availability.flight.id)
+                    .collect();
+
+                Ok(ListDueForReconciliationReply(Success(ids)))
+            }
+
+            ListByRoutePrompt(route, departing_after, departing_before) => {
+                let prefix = route_index_prefix(&route);
+                let ids = list_index::<FlightId>(
+                    storage,
+                    &[prefix.as_str(), &index_time(departing_after)].concat(),
+                    &[prefix.as_str(), &index_time(departing_before)].concat(),
+                ).await?;
+
+                let mut flights = Vec::new();
+                for id in ids {
+                    let key = ["flight_availability:", &id.to_string()].concat();
+                    if let Ok(availability) = storage.get::<Versioned<FlightAvailability>>(&key).await {
+                        flights.push(availability.value());
+                    }
+                }
+
+                let flights = flights.into_iter()
+                    .filter(|availability| availability.departs_on_route(&route, departing_after, departing_before))
+                    .collect();
+
+                Ok(ListByRouteReply(Success(flights)))
+            }
+
+            GetAccommodationAvailabilityPrompt(accommodation_id) => {
+                let key = ["accommodation_availability:", &accommodation_id.to_string()].concat();
+                let value = storage.get::<Versioned<AccommodationAvailability>>(&key).await.ok();
+
+                if let Some(accommodation_availability) = value {
+                    Ok(GetAccommodationAvailabilityReply(Success(accommodation_availability)))
+                }
+                else {
+                    Ok(GetAccommodationAvailabilityReply(NotFound))
+                }
+            }
+
+            SetAccommodationAvailabilityPrompt(accommodation_availability) => {
+                let id = &accommodation_availability.value_ref().accommodation;
+                let key = ["accommodation_availability:", &id.to_string()].concat();
+                let value: Option<Versioned<AccommodationAvailability>> = storage.get(&key).await.ok();
+
+                let existing_version = value
+                    .map(|va| va.version())
+                    .unwrap_or_default();
+
+                if accommodation_availability.version() == existing_version + 1 {
+                    storage.put(&key, &accommodation_availability).await?;
+                    Ok(SetAccommodationAvailabilityReply(Success(())))
+                }
+                else {
+                    Ok(SetAccommodationAvailabilityReply(VersionConflict))
+                }
+            }
+
             GetReservationPrompt(id) => {
                 let key = ["reservation:", &id.to_string()].concat();
                 let value =  storage.get::<Versioned<Reservation>>(&key).await.ok();
@@ -160,11 +247,19 @@ impl DurableObjectReservationRepository {
                 let key = ["reservation:", &id.to_string()].concat();
                 let value: Option<Versioned<Reservation>> = storage.get(&key).await.ok();
 
-                let existing_version = value
+                let existing_version = value.as_ref()
                     .map(|va| va.version())
                     .unwrap_or_default();
 
                 if reservation.version() == existing_version + 1 {
+                    // holds are indexed by expiry, see ListExpiredHoldsPrompt
+                    if let Some(expires_at) = value.as_ref().and_then(|existing| hold_expires_at(existing.value_ref())) {
+                        storage.delete(&index_key("hold:", expires_at, id)).await?;
+                    }
+                    if let Some(expires_at) = hold_expires_at(reservation.value_ref()) {
+                        storage.put(&index_key("hold:", expires_at, id), id).await?;
+                    }
+
                     storage.put(&key, &reservation).await?;
                     Ok(SetReservationReply(Success(())))
                 }
@@ -173,6 +268,14 @@ impl DurableObjectReservationRepository {
                 }
             }
 
+            ListExpiredHoldsPrompt(now) => {
+                // up to and including now, as ':' sorts before ';'
+                let end = ["hold:", &index_time(now), ";"].concat();
+                let ids = list_index::<ReservationId>(storage, "hold:", &end).await?;
+
+                Ok(ListExpiredHoldsReply(Success(ids)))
+            }
+
             _ => Err(Error::BadEncoding)
         }
     }
@@ -360,6 +463,104 @@ impl FlightAvailabilityRepository for DurableObjectReservationRepository {
             Ok(())
         }
     }
+
+    async fn list_due_for_reconciliation(&self, departing_before: DateTime<Utc>) -> Result<Vec<FlightId>, FlightAvailabilityRepositoryError> {
+        let reply = self
+            .dispatch(ListDueForReconciliationPrompt(departing_before)).await
+            .map_err(|e| FlightAvailabilityRepositoryError::IoError(e.to_string()))?;
+
+        if let ListDueForReconciliationReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(FlightAvailabilityRepositoryError::VersionConflict),
+                MalformedPrompt => Err(FlightAvailabilityRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(FlightAvailabilityRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
+
+    async fn list_by_route(&self, route: &FlightRoute, departing_after: DateTime<Utc>, departing_before: DateTime<Utc>) -> Result<Vec<FlightAvailability>, FlightAvailabilityRepositoryError> {
+        let reply = self
+            .dispatch(ListByRoutePrompt(route.clone(), departing_after, departing_before)).await
+            .map_err(|e| FlightAvailabilityRepositoryError::IoError(e.to_string()))?;
+
+        if let ListByRouteReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(FlightAvailabilityRepositoryError::VersionConflict),
+                MalformedPrompt => Err(FlightAvailabilityRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(FlightAvailabilityRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
+}
+
+
+#[async_trait(?Send)]
+impl AccommodationAvailabilityRepository for DurableObjectReservationRepository {
+    async fn get(&self, id: &AccommodationId) -> Result<Option<AccommodationAvailability>, AccommodationAvailabilityRepositoryError> {
+        let reply = self
+            .dispatch(GetAccommodationAvailabilityPrompt(*id)).await
+            .map_err(|e| AccommodationAvailabilityRepositoryError::IoError(e.to_string()))?;
+
+        if let GetAccommodationAvailabilityReply(get_reply) = reply {
+            match get_reply {
+                Success(result) => Ok(Some(result.value())),
+                NotFound => Ok(None),
+                VersionConflict => Err(AccommodationAvailabilityRepositoryError::VersionConflict),
+                MalformedPrompt => Err(AccommodationAvailabilityRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(AccommodationAvailabilityRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
+
+    async fn set_begin(&self, id: &AccommodationId) -> Result<Transaction<AccommodationId, AccommodationAvailability>, AccommodationAvailabilityRepositoryError> {
+        let reply = self
+            .dispatch(GetAccommodationAvailabilityPrompt(*id)).await
+            .map_err(|e| AccommodationAvailabilityRepositoryError::IoError(e.to_string()))?;
+
+        if let GetAccommodationAvailabilityReply(get_reply) = reply {
+            match get_reply {
+                Success(result) => Ok(Transaction::from_versioned(*id, result)),
+                NotFound => Ok(Transaction::new(*id)),
+                VersionConflict => Err(AccommodationAvailabilityRepositoryError::VersionConflict),
+                MalformedPrompt => Err(AccommodationAvailabilityRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(AccommodationAvailabilityRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
+
+    async fn set_commit(&self, transaction: Transaction<AccommodationId, AccommodationAvailability>) -> Result<(), AccommodationAvailabilityRepositoryError> {
+        if let Some(accommodation_availability) = transaction.next_versioned_value() {
+            let reply = self
+                .dispatch(SetAccommodationAvailabilityPrompt(accommodation_availability)).await
+                .map_err(|e| AccommodationAvailabilityRepositoryError::IoError(e.to_string()))?;
+
+            if let SetAccommodationAvailabilityReply(set_reply) = reply {
+                match set_reply {
+                    Success(result) => Ok(result),
+                    NotFound => Err(AccommodationAvailabilityRepositoryError::NotFound),
+                    VersionConflict => Err(AccommodationAvailabilityRepositoryError::VersionConflict),
+                    MalformedPrompt => Err(AccommodationAvailabilityRepositoryError::IoError("malformed prompt".to_owned()))
+                }
+            } else {
+                Err(AccommodationAvailabilityRepositoryError::IoError("unexpected reply".to_owned()))
+            }
+        }
+        else {
+            Ok(())
+        }
+    }
 }
 
 
@@ -422,6 +623,24 @@ impl ReservationRepository for DurableObjectReservationRepository {
             Ok(())
         }
     }
+
+    async fn list_expired_holds(&self, now: DateTime<Utc>) -> Result<Vec<ReservationId>, ReservationRepositoryError> {
+        let reply = self
+            .dispatch(ListExpiredHoldsPrompt(now)).await
+            .map_err(|e| ReservationRepositoryError::IoError(e.to_string()))?;
+
+        if let ListExpiredHoldsReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(ReservationRepositoryError::VersionConflict),
+                MalformedPrompt => Err(ReservationRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(ReservationRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
 }
 
 impl DurableObjectReservationRepository {
@@ -444,4 +663,58 @@ impl DurableObjectReservationRepository {
             .fetch_with_request(request).await?
             .json().await
     }
-}
\ No newline at end of file
+}
+
+/// values of all keys starting with prefix, ordered by key
+async fn list<V: DeserializeOwned>(storage: &Storage, prefix: &str) -> Result<Vec<V>, Error> {
+    let values = list_values::<Versioned<V>>(storage, ListOptions::new().prefix(prefix)).await?;
+
+    Ok(values.into_iter()
+        .map(|value| value.value())
+        .collect())
+}
+
+/// ids of the index keys from start (inclusive) up to end (exclusive), ordered by key
+async fn list_index<V: DeserializeOwned>(storage: &Storage, start: &str, end: &str) -> Result<Vec<V>, Error> {
+    list_values(storage, ListOptions::new().start(start).end(end)).await
+}
+
+async fn list_values<V: DeserializeOwned>(storage: &Storage, options: ListOptions<'_>) -> Result<Vec<V>, Error> {
+    let entries = storage.list_with_options(options).await?;
+
+    let mut values = Vec::new();
+    for entry in entries.values() {
+        let value = js_sys::JSON::stringify(&entry?)?
+            .as_string()
+            .unwrap_or_default();
+
+        values.push(serde_json::from_str::<V>(&value)?);
+    }
+
+    Ok(values)
+}
+
+/// index keys sort by time, e.g. hold:2024-05-08T04:00:00.000000Z:<id>
+fn index_key(prefix: &str, time: DateTime<Utc>, id: &impl ToString) -> String {
+    [prefix, &index_time(time), ":", &id.to_string()].concat()
+}
+
+/// e.g. flight_route:EHAM:ENLI:2024-05-08T04:00:00.000000Z:<id>
+fn route_index_key(flight: &Flight) -> String {
+    index_key(&route_index_prefix(&flight.route), flight.departure.with_timezone(&Utc), &flight.id)
+}
+
+fn route_index_prefix(route: &FlightRoute) -> String {
+    ["flight_route:", &route.departure.to_string(), ":", &route.arrival.to_string(), ":"].concat()
+}
+
+fn index_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}
+
+fn hold_expires_at(reservation: &Reservation) -> Option<DateTime<Utc>> {
+    match reservation {
+        Reservation::Held(held) => Some(held.expires_at),
+        _ => None,
+    }
+}
@@ -1,7 +1,9 @@
 mod do_reservation;
 mod r2_journeys;
 mod r2_availability;
+mod r2_manifest;
 
 pub use do_reservation::*;
 pub use r2_journeys::*;
-pub use r2_availability::*;
\ No newline at end of file
+pub use r2_availability::*;
+pub use r2_manifest::*;
\ No newline at end of file
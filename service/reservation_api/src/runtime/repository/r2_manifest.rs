@@ -0,0 +1,42 @@
+use worker::Bucket;
+use reservation::aggregate::FlightId;
+use reservation::projection::Manifest;
+
+/// manifests hold personal data, they're only served by the API to agents
+pub struct R2ManifestRepository {
+    bucket: Bucket
+}
+
+impl R2ManifestRepository {
+    pub fn build(bucket: Bucket) -> Self {
+        Self {
+            bucket
+        }
+    }
+
+    pub async fn get(&self, flight: &FlightId) -> worker::Result<Manifest> {
+        let key = self.object_key(flight);
+        let result = self.bucket.get(key).execute().await?;
+
+        if let Some(object) = result {
+            if let Some(value) = object.body() {
+                let data = value.bytes().await?;
+                let manifest = serde_json::from_slice(&data)?;
+                return Ok(manifest);
+            }
+        }
+        Ok(Manifest::from_flight(flight))
+    }
+
+    pub async fn set(&self, manifest: &Manifest) -> worker::Result<()> {
+        let key = self.object_key(manifest.flight());
+        let value = serde_json::to_vec(manifest)?;
+
+        self.bucket.put(key, value).execute().await
+            .map(|_| ())
+    }
+
+    fn object_key(&self, flight: &str) -> String {
+        ["manifests/", flight].concat()
+    }
+}
@@ -1,7 +1,7 @@
 use worker::{Request, RouteContext};
-use scheduling::command::{RegisterAirfield};
+use scheduling::command::{GetAirfield, ListAirfields, RegisterAirfield};
 use crate::api::{ApiResult, RouteData};
-use crate::api::transfer_objects::{PostAirfieldsRequest, PostAirfieldsResponse};
+use crate::api::transfer_objects::{airfield_to_transfer_object, GetAirfieldResponse, GetAirfieldsResponse, PostAirfieldsRequest, PostAirfieldsResponse};
 
 
 pub async fn post_airfields(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostAirfieldsResponse> {
@@ -19,3 +19,21 @@ pub async fn post_airfields(mut req: Request, ctx: RouteContext<RouteData>) -> A
         id: result.to_string()
     })
 }
+
+pub async fn get_airfields(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetAirfieldsResponse> {
+    let airfields = ctx.data.usecase.list_airfields(ListAirfields).await?;
+
+    Ok(airfields.into_iter()
+        .map(airfield_to_transfer_object)
+        .collect())
+}
+
+pub async fn get_airfield(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetAirfieldResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let command = GetAirfield {
+        id
+    };
+
+    let airfield = ctx.data.usecase.get_airfield(command).await?;
+    Ok(airfield_to_transfer_object(airfield))
+}
@@ -1,7 +1,7 @@
 use worker::{Request, RouteContext};
-use scheduling::command::AddAirshipToFleet;
+use scheduling::command::{AddAirshipToFleet, GetAirship, ListAirships};
 use crate::api::{ApiResult, RouteData};
-use crate::api::transfer_objects::{PostAirshipsRequest, PostAirshipsResponse};
+use crate::api::transfer_objects::{airship_to_transfer_object, GetAirshipResponse, GetAirshipsResponse, PostAirshipsRequest, PostAirshipsResponse};
 
 
 pub async fn post_airships(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostAirshipsResponse> {
@@ -20,3 +20,21 @@ pub async fn post_airships(mut req: Request, ctx: RouteContext<RouteData>) -> Ap
        id: result.to_string()
     })
 }
+
+pub async fn get_airships(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetAirshipsResponse> {
+    let airships = ctx.data.usecase.list_airships(ListAirships).await?;
+
+    Ok(airships.into_iter()
+        .map(airship_to_transfer_object)
+        .collect())
+}
+
+pub async fn get_airship(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetAirshipResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let command = GetAirship {
+        id
+    };
+
+    let airship = ctx.data.usecase.get_airship(command).await?;
+    Ok(airship_to_transfer_object(airship))
+}
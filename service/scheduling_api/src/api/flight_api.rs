@@ -1,7 +1,7 @@
 use worker::{Request, RouteContext};
-use scheduling::command::{ScheduleFlight};
+use scheduling::command::{CancelFlight, GetFlight, ListFlights, RescheduleFlight, ScheduleFlight};
 use crate::api::{ApiResult, RouteData};
-use crate::api::transfer_objects::{PostFlightsRequest, PostFlightsResponse};
+use crate::api::transfer_objects::{flight_to_transfer_object, GetFlightResponse, GetFlightsRequest, GetFlightsResponse, PostFlightCancellationRequest, PostFlightCancellationResponse, PostFlightReschedulingRequest, PostFlightReschedulingResponse, PostFlightsRequest, PostFlightsResponse};
 
 
 pub async fn post_flights(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostFlightsResponse> {
@@ -21,3 +21,62 @@ pub async fn post_flights(mut req: Request, ctx: RouteContext<RouteData>) -> Api
         id: result.to_string()
     })
 }
+
+pub async fn post_flight_cancellation(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostFlightCancellationResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.usecase;
+    let body: PostFlightCancellationRequest = req.json().await?;
+
+    let command = CancelFlight {
+        flight: id,
+        reason: body.reason.parse()?,
+    };
+
+    let result = usecase.cancel_flight(command).await?;
+    Ok(PostFlightCancellationResponse {
+        id: result.to_string()
+    })
+}
+
+pub async fn post_flight_rescheduling(mut req: Request, ctx: RouteContext<RouteData>) -> ApiResult<PostFlightReschedulingResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let usecase =  ctx.data.usecase;
+    let body: PostFlightReschedulingRequest = req.json().await?;
+
+    let command = RescheduleFlight {
+        flight: id,
+        departure_time: body.departure_time,
+        arrival_time: body.arrival_time,
+    };
+
+    let result = usecase.reschedule_flight(command).await?;
+    Ok(PostFlightReschedulingResponse {
+        id: result.to_string()
+    })
+}
+
+pub async fn get_flights(req: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetFlightsResponse> {
+    let query: GetFlightsRequest = req.query()?;
+
+    let command = ListFlights {
+        airship: query.airship.map(|airship| airship.parse()).transpose()?,
+        airfield: query.airfield.map(|airfield| airfield.parse()).transpose()?,
+        from: query.from,
+        until: query.until,
+    };
+
+    let flights = ctx.data.usecase.list_flights(command).await?;
+    Ok(flights.into_iter()
+        .map(flight_to_transfer_object)
+        .collect())
+}
+
+pub async fn get_flight(_: Request, ctx: RouteContext<RouteData>) -> ApiResult<GetFlightResponse> {
+    let id = ctx.param("id").unwrap_or(&String::default()).parse()?;
+    let command = GetFlight {
+        id
+    };
+
+    let flight = ctx.data.usecase.get_flight(command).await?;
+    Ok(flight_to_transfer_object(flight))
+}
@@ -22,16 +22,56 @@ pub async fn route(request: Request, environment: Env, route_data: RouteData) ->
                 to_response(airship_api::post_airships(req, ctx).await)
             }).await
         })
+        .get_async("/airships", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(airship_api::get_airships(req, ctx).await)
+            }).await
+        })
+        .get_async("/airships/:id", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(airship_api::get_airship(req, ctx).await)
+            }).await
+        })
         .post_async("/airfields", |req, ctx| async move {
             authorize_with_api_key(req, ctx, |req, ctx| async move {
                 to_response(airfield_api::post_airfields(req, ctx).await)
             }).await
         })
+        .get_async("/airfields", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(airfield_api::get_airfields(req, ctx).await)
+            }).await
+        })
+        .get_async("/airfields/:id", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(airfield_api::get_airfield(req, ctx).await)
+            }).await
+        })
         .post_async("/flights", |req, ctx| async move {
             authorize_with_api_key(req, ctx, |req, ctx| async move {
                 to_response(flight_api::post_flights(req, ctx).await)
             }).await
         })
+        .post_async("/flights/:id/cancellation", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(flight_api::post_flight_cancellation(req, ctx).await)
+            }).await
+        })
+        .post_async("/flights/:id/rescheduling", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(flight_api::post_flight_rescheduling(req, ctx).await)
+            }).await
+        })
+        .get_async("/flights", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(flight_api::get_flights(req, ctx).await)
+            }).await
+        })
+        .get_async("/flights/:id", |req, ctx| async move {
+            authorize_with_api_key(req, ctx, |req, ctx| async move {
+                to_response(flight_api::get_flight(req, ctx).await)
+            }).await
+        })
         .run(request, environment)
         .await
 }
@@ -1,5 +1,6 @@
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use scheduling::aggregate;
 
 #[derive(Serialize, Deserialize)]
 pub struct Error {
@@ -19,6 +20,27 @@ pub struct PostAirshipsResponse {
     pub id: String,
 }
 
+#[derive(Serialize)]
+pub struct Airship {
+    id: String,
+    name: String,
+    model: String,
+    number_of_seats: u8,
+}
+
+pub fn airship_to_transfer_object(value: aggregate::Airship) -> Airship {
+    Airship {
+        id: value.id.to_string(),
+        name: value.name().to_string(),
+        model: value.model().to_string(),
+        number_of_seats: value.number_of_seats().as_u8(),
+    }
+}
+
+pub type GetAirshipResponse = Airship;
+
+pub type GetAirshipsResponse = Vec<Airship>;
+
 #[derive(Serialize, Deserialize)]
 pub struct PostAirfieldsRequest {
     pub id: String,
@@ -31,6 +53,25 @@ pub struct PostAirfieldsResponse {
     pub id: String,
 }
 
+#[derive(Serialize)]
+pub struct Airfield {
+    id: String,
+    name: String,
+    location: String,
+}
+
+pub fn airfield_to_transfer_object(value: aggregate::Airfield) -> Airfield {
+    Airfield {
+        id: value.id.to_string(),
+        name: value.name.to_string(),
+        location: value.location.to_string(),
+    }
+}
+
+pub type GetAirfieldResponse = Airfield;
+
+pub type GetAirfieldsResponse = Vec<Airfield>;
+
 #[derive(Serialize, Deserialize)]
 pub struct PostFlightsRequest {
     pub departure_location: String,
@@ -44,3 +85,91 @@ pub struct PostFlightsRequest {
 pub struct PostFlightsResponse {
     pub id: String
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct PostFlightCancellationRequest {
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostFlightCancellationResponse {
+    pub id: String
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostFlightReschedulingRequest {
+    pub departure_time: DateTime<FixedOffset>,
+    pub arrival_time: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PostFlightReschedulingResponse {
+    pub id: String
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetFlightsRequest {
+    pub airship: Option<String>,
+    pub airfield: Option<String>,
+    pub from: Option<DateTime<FixedOffset>>,
+    pub until: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Serialize)]
+pub struct Flight {
+    id: String,
+    departure: FlightDeparture,
+    arrival: FlightArrival,
+    airship: String,
+    status: FlightStatus,
+}
+
+#[derive(Serialize)]
+pub struct FlightDeparture {
+    location: String,
+    time: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize)]
+pub struct FlightArrival {
+    location: String,
+    time: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "reason")]
+pub enum FlightStatus {
+    Scheduled,
+    Cancelled(String),
+}
+
+pub fn flight_to_transfer_object(value: aggregate::Flight) -> Flight {
+    Flight {
+        id: value.id.to_string(),
+        departure: FlightDeparture {
+            location: value.departure().location.to_string(),
+            time: value.departure().time,
+        },
+        arrival: FlightArrival {
+            location: value.arrival().location.to_string(),
+            time: value.arrival().time,
+        },
+        airship: value.airship().to_string(),
+        status: flight_status_to_transfer_object(value.status()),
+    }
+}
+
+pub fn flight_status_to_transfer_object(value: &aggregate::FlightStatus) -> FlightStatus {
+    match value {
+        aggregate::FlightStatus::Scheduled => FlightStatus::Scheduled,
+        aggregate::FlightStatus::Cancelled(reason) => FlightStatus::Cancelled(match reason {
+            aggregate::FlightCancellationReason::Weather => "weather",
+            aggregate::FlightCancellationReason::Technical => "technical",
+            aggregate::FlightCancellationReason::Operational => "operational",
+        }.to_owned()),
+    }
+}
+
+pub type GetFlightResponse = Flight;
+
+pub type GetFlightsResponse = Vec<Flight>;
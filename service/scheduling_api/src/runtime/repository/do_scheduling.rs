@@ -1,12 +1,14 @@
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
-use worker::{Error, Method, ObjectNamespace, Request, RequestInit, Storage};
+use serde::de::DeserializeOwned;
+use worker::{js_sys, Error, ListOptions, Method, ObjectNamespace, Request, RequestInit, Storage};
 use worker::wasm_bindgen::JsValue;
 use prelude::async_trait;
 use prelude::domain::{Transaction, Versioned};
 use prelude::runtime::repository::Reply;
 use prelude::runtime::repository::Reply::{MalformedPrompt, NotFound, Success, VersionConflict};
-use scheduling::aggregate::{Airfield, AirfieldId, Airship, AirshipId, Flight, FlightId};
-use scheduling::repository::{AirfieldRepository, AirfieldRepositoryError, AirshipRepository, AirshipRepositoryError, FlightRepository, FlightRepositoryError};
+use scheduling::aggregate::{Airfield, AirfieldId, Airship, AirshipId, Flight, FlightId, FlightStatus};
+use scheduling::repository::{AirfieldRepository, AirfieldRepositoryError, AirshipRepository, AirshipRepositoryError, FlightFilter, FlightRepository, FlightRepositoryError};
 use crate::runtime::repository::DurableObjectSchedulingRepositoryProtocol::*;
 
 #[derive(Serialize, Deserialize)]
@@ -18,6 +20,9 @@ pub enum DurableObjectSchedulingRepositoryProtocol {
     SetAirfieldPrompt(Versioned<Airfield>),
     SetAirfieldReply(Reply<()>),
 
+    ListAirfieldsPrompt,
+    ListAirfieldsReply(Reply<Vec<Airfield>>),
+
     // airship
     GetAirshipPrompt(AirshipId),
     GetAirshipReply(Reply<Versioned<Airship>>),
@@ -25,12 +30,21 @@ pub enum DurableObjectSchedulingRepositoryProtocol {
     SetAirshipPrompt(Versioned<Airship>),
     SetAirshipReply(Reply<()>),
 
+    ListAirshipsPrompt,
+    ListAirshipsReply(Reply<Vec<Airship>>),
+
     // flight
     GetFlightPrompt(FlightId),
     GetFlightReply(Reply<Versioned<Flight>>),
 
     SetFlightPrompt(Versioned<Flight>),
     SetFlightReply(Reply<()>),
+
+    ListFlightsPrompt(FlightFilter),
+    ListFlightsReply(Reply<Vec<Flight>>),
+
+    ListFlightsByAirshipPrompt(AirshipId, DateTime<FixedOffset>, DateTime<FixedOffset>),
+    ListFlightsByAirshipReply(Reply<Vec<Flight>>),
 }
 
 pub struct DurableObjectSchedulingRepository {
@@ -118,17 +132,23 @@ impl DurableObjectSchedulingRepository {
                 }
             }
 
-            SetFlightPrompt(airship) => {
-                let id = airship.value_ref().id;
+            SetFlightPrompt(flight) => {
+                let id = flight.value_ref().id;
                 let key = ["flight:", &id.to_string()].concat();
-                let value: Option<Versioned<FlightId>> = storage.get(&key).await.ok();
+                let value: Option<Versioned<Flight>> = storage.get(&key).await.ok();
 
-                let existing_version = value
+                let existing_version = value.as_ref()
                     .map(|va| va.version())
                     .unwrap_or_default();
 
-                if airship.version() == existing_version + 1 {
-                    storage.put(&key, &airship).await?;
+                if flight.version() == existing_version + 1 {
+                    // flights are indexed by airship and departure, see list_flights_by_airship
+                    if let Some(existing) = &value {
+                        storage.delete(&airship_index_key(existing.value_ref())).await?;
+                    }
+                    storage.put(&airship_index_key(flight.value_ref()), id).await?;
+
+                    storage.put(&key, &flight).await?;
                     Ok(SetFlightReply(Success(())))
                 }
                 else {
@@ -136,6 +156,38 @@ impl DurableObjectSchedulingRepository {
                 }
             }
 
+            ListAirfieldsPrompt => {
+                let airfields = list::<Airfield>(storage, "airfield:").await?;
+                Ok(ListAirfieldsReply(Success(airfields)))
+            }
+
+            ListAirshipsPrompt => {
+                let airships = list::<Airship>(storage, "airship:").await?;
+                Ok(ListAirshipsReply(Success(airships)))
+            }
+
+            ListFlightsPrompt(filter) => {
+                let flights = match &filter.airship {
+                    Some(airship) => list_flights_by_airship(storage, airship, filter.from, filter.until).await?,
+                    None => list::<Flight>(storage, "flight:").await?,
+                };
+
+                let flights = flights.into_iter()
+                    .filter(|flight| filter.matches(flight))
+                    .collect();
+
+                Ok(ListFlightsReply(Success(flights)))
+            }
+
+            ListFlightsByAirshipPrompt(airship, from, until) => {
+                let flights = list_flights_by_airship(storage, &airship, Some(from), Some(until)).await?
+                    .into_iter()
+                    .filter(|flight| flight.is_scheduled_within(&airship, from, until))
+                    .collect();
+
+                Ok(ListFlightsByAirshipReply(Success(flights)))
+            }
+
             _ => Err(Error::BadEncoding)
         }
     }
@@ -201,6 +253,24 @@ impl AirfieldRepository for DurableObjectSchedulingRepository {
             Ok(())
         }
     }
+
+    async fn list(&self) -> Result<Vec<Airfield>, AirfieldRepositoryError> {
+        let reply = self
+            .dispatch(ListAirfieldsPrompt).await
+            .map_err(|e| AirfieldRepositoryError::IoError(e.to_string()))?;
+
+        if let ListAirfieldsReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(AirfieldRepositoryError::VersionConflict),
+                MalformedPrompt => Err(AirfieldRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(AirfieldRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
 }
 
 
@@ -263,6 +333,24 @@ impl AirshipRepository for DurableObjectSchedulingRepository {
             Ok(())
         }
     }
+
+    async fn list(&self) -> Result<Vec<Airship>, AirshipRepositoryError> {
+        let reply = self
+            .dispatch(ListAirshipsPrompt).await
+            .map_err(|e| AirshipRepositoryError::IoError(e.to_string()))?;
+
+        if let ListAirshipsReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(AirshipRepositoryError::VersionConflict),
+                MalformedPrompt => Err(AirshipRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(AirshipRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
 }
 
 #[async_trait(?Send)]
@@ -324,6 +412,42 @@ impl FlightRepository for DurableObjectSchedulingRepository {
             Ok(())
         }
     }
+
+    async fn list(&self, filter: &FlightFilter) -> Result<Vec<Flight>, FlightRepositoryError> {
+        let reply = self
+            .dispatch(ListFlightsPrompt(filter.clone())).await
+            .map_err(|e| FlightRepositoryError::IoError(e.to_string()))?;
+
+        if let ListFlightsReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(FlightRepositoryError::VersionConflict),
+                MalformedPrompt => Err(FlightRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(FlightRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
+
+    async fn list_by_airship(&self, airship: &AirshipId, from: DateTime<FixedOffset>, until: DateTime<FixedOffset>) -> Result<Vec<Flight>, FlightRepositoryError> {
+        let reply = self
+            .dispatch(ListFlightsByAirshipPrompt(airship.clone(), from, until)).await
+            .map_err(|e| FlightRepositoryError::IoError(e.to_string()))?;
+
+        if let ListFlightsByAirshipReply(list_reply) = reply {
+            match list_reply {
+                Success(result) => Ok(result),
+                NotFound => Ok(Vec::new()),
+                VersionConflict => Err(FlightRepositoryError::VersionConflict),
+                MalformedPrompt => Err(FlightRepositoryError::IoError("malformed prompt".to_owned()))
+            }
+        }
+        else {
+            Err(FlightRepositoryError::IoError("unexpected reply".to_owned()))
+        }
+    }
 }
 
 impl DurableObjectSchedulingRepository {
@@ -346,4 +470,90 @@ impl DurableObjectSchedulingRepository {
             .fetch_with_request(request).await?
             .json().await
     }
-}
\ No newline at end of file
+}
+
+/// values of all keys starting with prefix, ordered by key
+/// values of all keys starting with prefix, ordered by key
+async fn list<V: DeserializeOwned>(storage: &Storage, prefix: &str) -> Result<Vec<V>, Error> {
+    let values = list_values::<Versioned<V>>(storage, ListOptions::new().prefix(prefix)).await?;
+
+    Ok(values.into_iter()
+        .map(|value| value.value())
+        .collect())
+}
+
+async fn list_values<V: DeserializeOwned>(storage: &Storage, options: ListOptions<'_>) -> Result<Vec<V>, Error> {
+    let entries = storage.list_with_options(options).await?;
+
+    let mut values = Vec::new();
+    for entry in entries.values() {
+        let value = js_sys::JSON::stringify(&entry?)?
+            .as_string()
+            .unwrap_or_default();
+
+        values.push(serde_json::from_str::<V>(&value)?);
+    }
+
+    Ok(values)
+}
+
+/// flights of the airship departing before until that may arrive after from, a superset to filter;
+/// scheduled flights of an airship don't overlap, hence of those departing before from
+/// only the flights since the last scheduled one may still be underway
+async fn list_flights_by_airship(storage: &Storage, airship: &AirshipId, from: Option<DateTime<FixedOffset>>, until: Option<DateTime<FixedOffset>>) -> Result<Vec<Flight>, Error> {
+    let prefix = airship_index_prefix(airship);
+    let start = from
+        .map(|from| [prefix.as_str(), &index_time(from)].concat())
+        .unwrap_or(prefix.clone());
+    let end = until
+        .map(|until| [prefix.as_str(), &index_time(until)].concat())
+        .unwrap_or([prefix.as_str(), ";"].concat());
+
+    let mut underway = Vec::new();
+    let mut before = start.clone();
+    while from.is_some() {
+        let ids = list_values::<FlightId>(storage, ListOptions::new().start(&prefix).end(&before).reverse(true).limit(1)).await?;
+        let Some(flight) = get_flights(storage, ids).await?.pop() else {
+            break;
+        };
+
+        before = airship_index_key(&flight);
+        let scheduled = flight.status() == &FlightStatus::Scheduled;
+        underway.push(flight);
+
+        if scheduled {
+            break;
+        }
+    }
+    underway.reverse();
+
+    let ids = list_values::<FlightId>(storage, ListOptions::new().start(&start).end(&end)).await?;
+    let flights = get_flights(storage, ids).await?;
+
+    Ok(underway.into_iter().chain(flights).collect())
+}
+
+async fn get_flights(storage: &Storage, ids: Vec<FlightId>) -> Result<Vec<Flight>, Error> {
+    let mut flights = Vec::new();
+    for id in ids {
+        let key = ["flight:", &id.to_string()].concat();
+        if let Ok(flight) = storage.get::<Versioned<Flight>>(&key).await {
+            flights.push(flight.value());
+        }
+    }
+
+    Ok(flights)
+}
+
+/// e.g. airship_flight:<airship>:2024-05-08T04:00:00.000000Z:<id>, not to be listed along with airships
+fn airship_index_key(flight: &Flight) -> String {
+    [&airship_index_prefix(flight.airship()), &index_time(flight.departure().time), ":", &flight.id.to_string()].concat()
+}
+
+fn airship_index_prefix(airship: &AirshipId) -> String {
+    ["airship_flight:", &airship.to_string(), ":"].concat()
+}
+
+fn index_time(time: DateTime<FixedOffset>) -> String {
+    time.with_timezone(&Utc).format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}